@@ -3,42 +3,62 @@
 //! Provides HTTP endpoints for communication with the frontend.
 //! Uses Axum as the web framework and supports CORS.
 
-use axum::extract::rejection::JsonRejection;
-use axum::extract::{Json, State};
+use axum::body::Bytes;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, Json, Query, Request, State};
+use axum::middleware::{self, Next};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::{
     Router,
-    http::{StatusCode, Uri, header},
+    http::{HeaderMap, StatusCode, Uri, header},
     response::{Html, IntoResponse, Response},
     routing::{get, post},
 };
 use rust_embed::RustEmbed;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 #[allow(unused_imports)]
 use serde_json::json;
-use std::sync::OnceLock;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, OnceLock};
 use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::ReceiverStream;
 use tower_http::cors::{Any, CorsLayer};
-use utoipa::{OpenApi, ToSchema};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 
 use crate::config::{ApiConfig, OptimizerConfig, RequestLimits};
-use crate::model::{Box3D, Container, ContainerBlueprint, ValidationError};
+use crate::dim_format::{self, DimFormatted, DimensionsValue, PositionValue};
+pub use crate::dim_format::{DimFormat, Dimensions, Position};
+use crate::geometry::{intersects, overlap_1d};
+use crate::model::{Box3D, Container, ContainerBlueprint, ObjectGap, PlacedBox, ValidationError};
 use crate::optimizer::{
-    ContainerDiagnostics, PackingConfig, PackingDiagnosticsSummary, PackingResult,
-    SupportDiagnostics, pack_objects_with_config, pack_objects_with_progress,
+    ContainerDiagnostics, ContainerOrder, ContainerSpec, LoadDirection, MassPlacement,
+    NewContainerPolicy, PackEvent, PackingConfig, PackingDiagnosticsSummary, PackingProfile,
+    PackingResult, PhaseTimings, PrimaryObjective, RejectionCounts, SnapshotObject,
+    SupportDiagnostics, TemplatePreference, compute_container_diagnostics, find_fit,
+    pack_objects_incremental_with_progress, summarize_diagnostics,
 };
 use crate::packaging::{PackagingFill, PackagingSummary};
+use crate::rate_limit::RateLimiter;
+use crate::result_cache::ResultCache;
+use crate::templates::TemplateLibrary;
 
 #[derive(Clone)]
 struct ApiState {
     optimizer_config: OptimizerConfig,
     limits: RequestLimits,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    templates: Arc<TemplateLibrary>,
+    result_cache: Option<Arc<ResultCache>>,
 }
 
 static OPENAPI_DOC: OnceLock<utoipa::openapi::OpenApi> = OnceLock::new();
 
+/// Response header of `/pack` telling whether the result cache was hit (`hit`/`miss`).
+const CACHE_HEADER: &str = "x-cache";
+
 // SRI hashes verified against https://unpkg.com/swagger-ui-dist@5.17.14/ on 2025-10-29.
 const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
 <html lang="en">
@@ -82,6 +102,95 @@ fn openapi_doc() -> &'static utoipa::openapi::OpenApi {
     OPENAPI_DOC.get_or_init(ApiDoc::openapi)
 }
 
+const COMPONENT_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Extracts a component schema from the OpenAPI document as a standalone JSON Schema.
+///
+/// All transitively referenced components are copied into `$defs` and their
+/// `$ref` paths rewritten, so the result validates without the OpenAPI document.
+fn standalone_json_schema(name: &str) -> Option<serde_json::Value> {
+    let doc = serde_json::to_value(openapi_doc()).ok()?;
+    let schemas = doc.pointer("/components/schemas")?.as_object()?;
+    let mut root = schemas.get(name)?.clone();
+
+    let mut defs = serde_json::Map::new();
+    let mut pending = Vec::new();
+    collect_component_refs(&root, &mut pending);
+    while let Some(reference) = pending.pop() {
+        if defs.contains_key(&reference) {
+            continue;
+        }
+        if let Some(schema) = schemas.get(&reference) {
+            collect_component_refs(schema, &mut pending);
+            defs.insert(reference, schema.clone());
+        }
+    }
+
+    rewrite_component_refs(&mut root);
+    for schema in defs.values_mut() {
+        rewrite_component_refs(schema);
+    }
+
+    let object = root.as_object_mut()?;
+    object.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    object.insert("title".to_string(), json!(name));
+    if !defs.is_empty() {
+        object.insert("$defs".to_string(), serde_json::Value::Object(defs));
+    }
+    Some(root)
+}
+
+fn collect_component_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, entry) in map {
+                match (key.as_str(), entry.as_str()) {
+                    ("$ref", Some(target)) => {
+                        if let Some(component) = target.strip_prefix(COMPONENT_REF_PREFIX) {
+                            refs.push(component.to_string());
+                        }
+                    }
+                    _ => collect_component_refs(entry, refs),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_component_refs(item, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rewrite_component_refs(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                if key == "$ref" {
+                    if let Some(component) = entry
+                        .as_str()
+                        .and_then(|target| target.strip_prefix(COMPONENT_REF_PREFIX))
+                    {
+                        *entry = json!(format!("#/$defs/{component}"));
+                    }
+                } else {
+                    rewrite_component_refs(entry);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                rewrite_component_refs(item);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Embedded Web Assets (HTML, CSS, JS)
 #[derive(RustEmbed)]
 #[folder = "web/"]
@@ -90,23 +199,164 @@ struct WebAssets;
 /// Request structure for the packing endpoint.
 ///
 /// `containers` contains the possible packaging types that can be combined.
-#[derive(Deserialize, Clone, ToSchema)]
+#[derive(Deserialize, Serialize, Clone, Debug, Default, ToSchema)]
 pub struct ContainerRequest {
     pub name: Option<String>,
     #[schema(value_type = [f64; 3], example = json!([120.0, 100.0, 80.0]))]
     pub dims: (f64, f64, f64),
     pub max_weight: f64,
+    /// Optional stepped ceiling as `[x_threshold, max_height]` pairs, sorted by threshold.
+    #[serde(default)]
+    #[schema(value_type = Vec<[f64; 2]>, example = json!([[80.0, 60.0]]))]
+    pub height_profile: Vec<(f64, f64)>,
+    /// Treats `dims` as outer dimensions; the interior shrinks by `2 × wall_thickness` per axis.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub wall_thickness: Option<f64>,
+    /// Reference point added to all reported positions of this container (output only).
+    #[serde(default)]
+    #[schema(value_type = Option<[f64; 3]>, nullable = true, example = json!([0.0, -100.0, 0.0]))]
+    pub origin: Option<(f64, f64, f64)>,
+    /// Fixed weight carried from the start (e.g. dunnage); must be below `max_weight`.
+    #[serde(default)]
+    #[schema(nullable = true, example = 25.0)]
+    pub reserved_weight: Option<f64>,
+    /// `[x, y, width, depth]` rectangle (e.g. near the door) where nothing may be stacked.
+    #[serde(default)]
+    #[schema(value_type = Option<[f64; 4]>, nullable = true, example = json!([0.0, 80.0, 120.0, 20.0]))]
+    pub floor_only_region: Option<(f64, f64, f64, f64)>,
+    /// Maximum number of objects per container of this type (at least 1).
+    #[serde(default)]
+    #[schema(nullable = true, minimum = 1, example = 20)]
+    pub max_items: Option<usize>,
+    /// Price of using one container of this type; containers without a cost count as free.
+    #[serde(default)]
+    #[schema(nullable = true, minimum = 0.0, example = 49.9)]
+    pub cost: Option<f64>,
 }
 
 impl ContainerRequest {
-    fn into_blueprint(self, id: usize) -> Result<ContainerBlueprint, ValidationError> {
-        ContainerBlueprint::new(id, self.name, self.dims, self.max_weight)
+    /// Builds the container type; `clamp_origin` rejects origins that could yield negative
+    /// reported positions.
+    pub(crate) fn into_blueprint(
+        self,
+        id: usize,
+        clamp_origin: bool,
+    ) -> Result<ContainerBlueprint, ValidationError> {
+        let origin = self.origin.unwrap_or_default();
+        if clamp_origin && (origin.0 < 0.0 || origin.1 < 0.0 || origin.2 < 0.0) {
+            return Err(ValidationError::InvalidDimension(format!(
+                "Origin offset {:?} would make reported positions negative",
+                origin
+            )));
+        }
+        // A blank name would only show up as an empty label.
+        let name = self.name.filter(|name| !name.trim().is_empty());
+        let mut blueprint = ContainerBlueprint::new(id, name, self.dims, self.max_weight)?
+            .with_wall_thickness(self.wall_thickness.unwrap_or(0.0))?
+            .with_origin(origin)?
+            .with_reserved_weight(self.reserved_weight.unwrap_or(0.0))?
+            .with_height_profile(self.height_profile)?;
+        if let Some(region) = self.floor_only_region {
+            blueprint = blueprint.with_floor_only_region(region)?;
+        }
+        if let Some(max) = self.max_items {
+            blueprint = blueprint.with_max_items(max)?;
+        }
+        match self.cost {
+            Some(cost) => blueprint.with_cost(cost),
+            None => Ok(blueprint),
+        }
+    }
+}
+
+/// Unit in which the weights of a [`PackRequest`] are given.
+///
+/// All weights are normalized to kilograms before packing; unknown units are rejected during
+/// deserialization.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WeightUnit {
+    #[default]
+    Kg,
+    Lb,
+}
+
+impl WeightUnit {
+    const KG_PER_LB: f64 = 0.453_592_37;
+
+    /// Converts a weight given in this unit to kilograms.
+    pub fn to_kg(self, weight: f64) -> f64 {
+        match self {
+            WeightUnit::Kg => weight,
+            WeightUnit::Lb => weight * Self::KG_PER_LB,
+        }
     }
 }
 
-#[derive(Deserialize, ToSchema)]
-#[schema(
-    example = json!({
+/// Space reserved for items that will be added later.
+///
+/// Reserved blocks are weightless, immovable obstacles: they apply to every container type they
+/// fit into, are avoided during placement, and never appear in the output.
+#[derive(Deserialize, Clone, Debug, ToSchema)]
+pub struct ReservedSpaceRequest {
+    #[schema(value_type = [f64; 3], example = json!([40.0, 100.0, 80.0]))]
+    pub dims: (f64, f64, f64),
+    #[schema(value_type = [f64; 3], example = json!([80.0, 0.0, 0.0]))]
+    pub pos: (f64, f64, f64),
+}
+
+impl ReservedSpaceRequest {
+    fn into_placed(self, index: usize) -> Result<PlacedBox, ValidationError> {
+        Box3D::reserved_space(index, self.dims).map(|block| PlacedBox::new(block, self.pos))
+    }
+}
+
+/// Minimum clearance between the objects `a` and `b`, e.g. for cabling.
+///
+/// Once both objects share a container, their bounding boxes must be at least `gap` apart along
+/// one axis; a placement violating this is rejected.
+#[derive(Deserialize, Clone, Debug, ToSchema)]
+pub struct GapConstraintRequest {
+    pub a: usize,
+    pub b: usize,
+    #[schema(example = 5.0)]
+    pub gap: f64,
+}
+
+/// Items strapped together into one rigid unit.
+///
+/// The bundle is packed as a single box spanning all members (summed weight, never rotated) and
+/// reported as its individual members, each at the bundle position plus its offset.
+#[derive(Deserialize, Clone, Debug, ToSchema)]
+pub struct BundleRequest {
+    #[schema(example = json!([1, 2]))]
+    pub member_ids: Vec<usize>,
+    /// Position of each member relative to the bundle's corner, in `member_ids` order.
+    #[schema(value_type = Vec<[f64; 3]>, example = json!([[0.0, 0.0, 0.0], [30.0, 0.0, 0.0]]))]
+    pub offsets: Vec<(f64, f64, f64)>,
+}
+
+/// A bundle resolved against the request's objects; packed as the object with id `id`.
+#[derive(Debug)]
+struct ObjectBundle {
+    id: usize,
+    members: Vec<(Box3D, (f64, f64, f64))>,
+}
+
+impl ObjectBundle {
+    /// Returns the members of the bundle placed at `pos`, each shifted by its offset.
+    fn placed_members(&self, pos: (f64, f64, f64)) -> impl Iterator<Item = PlacedBox> + '_ {
+        self.members.iter().map(move |(member, offset)| {
+            let position = (pos.0 + offset.0, pos.1 + offset.1, pos.2 + offset.2);
+            PlacedBox::new(member.clone(), position)
+        })
+    }
+}
+
+/// Example [`PackRequest`] shared by the OpenAPI schema and `GET /example`.
+pub fn pack_request_example() -> serde_json::Value {
+    json!({
         "containers": [
             {
                 "name": "Standardkiste",
@@ -119,13 +369,206 @@ impl ContainerRequest {
         ],
         "allow_rotations": true
     })
-)]
+}
+
+#[derive(Deserialize, Default, ToSchema)]
+#[schema(example = pack_request_example)]
 pub struct PackRequest {
     pub containers: Vec<ContainerRequest>,
+    /// Names of server-side library templates (see `GET /templates`) appended to `containers`.
+    #[serde(default)]
+    #[schema(example = json!(["euro-pallet"]))]
+    pub template_names: Vec<String>,
+    /// Container type ids (indices into `containers`) in the order they must be filled.
+    #[serde(default)]
+    #[schema(example = json!([0, 1]))]
+    pub fill_order: Vec<usize>,
     pub objects: Vec<Box3D>,
     #[serde(default)]
     #[schema(nullable = true)]
     pub allow_rotations: Option<bool>,
+    /// Treats any unplaced object as a failure of the whole request (`422` instead of `200`).
+    #[serde(default)]
+    pub strict: bool,
+    /// Rejects container `origin` offsets with negative components.
+    #[serde(default)]
+    pub clamp_origin: bool,
+    /// Drops objects repeating an earlier `id` with a warning instead of packing them anyway.
+    #[serde(default)]
+    pub dedupe_object_ids: bool,
+    /// Rejects the request (`422`) if an object repeats an earlier `id`.
+    #[serde(default)]
+    pub reject_duplicate_object_ids: bool,
+    /// Measures the sort, cluster and placement phases and reports them as `timings`.
+    #[serde(default)]
+    pub profile: bool,
+    /// Adds all eight corners of every placed object as `corners`, e.g. for AR overlays.
+    #[serde(default)]
+    pub include_corners: bool,
+    /// Reports the axis permutation applied to every placed object as `rotation`.
+    #[serde(default)]
+    pub include_rotation: bool,
+    /// Rounds all reported positions and dimensions to this many decimals (at most 15).
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub round_decimals: Option<u32>,
+    /// Pre-opens exactly this many containers and spreads objects across them.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub target_container_count: Option<usize>,
+    /// Overrides the container-wide heaviest-on-bottom layering rule for this request.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub strict_layering: Option<bool>,
+    /// Applies a named settings preset; explicit overrides in the request still take precedence.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub packing_profile: Option<PackingProfile>,
+    /// Overrides whether compactness or balance decides placements first.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub primary_objective: Option<PrimaryObjective>,
+    /// Overrides whether heavy objects are pulled toward the XY center of the container.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub mass_placement: Option<MassPlacement>,
+    /// Overrides whether objects accumulate from the front or the back of the container.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub load_direction: Option<LoadDirection>,
+    /// Longest allowed protrusion of a stacked object's base beyond its supports.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub max_overhang: Option<f64>,
+    /// Additional randomized packing runs; the best run is returned.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub restarts: Option<usize>,
+    /// Relative tolerance for collapsing near-identical container types into one.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub template_merge_tolerance: Option<f64>,
+    /// Scales the numeric tolerances with the median object dimension (unit-independent).
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub relative_tolerance: Option<bool>,
+    /// Minimum floor coverage ratio (0.0 to 1.0) reported via `diagnostics.floor_coverage_ok`.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub floor_min_coverage: Option<f64>,
+    /// Overrides the order in which result containers are listed (and numbered).
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub container_order: Option<ContainerOrder>,
+    /// Overrides which container types are tried first when a new container is opened.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub template_preference: Option<TemplatePreference>,
+    /// Overrides how the container type of a newly opened container is chosen.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub new_container_policy: Option<NewContainerPolicy>,
+    /// Rejects objects larger than this share of the largest container type's volume.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub max_object_volume_fraction: Option<f64>,
+    /// Overrides the headroom kept free below every container ceiling; must stay below the
+    /// height of every container.
+    #[serde(default)]
+    #[schema(nullable = true, minimum = 0.0)]
+    pub top_clearance: Option<f64>,
+    /// Objects carrying one of these tags may rotate (unless they set `allow_rotation` themselves).
+    #[serde(default)]
+    pub rotate_tags: Vec<String>,
+    /// Objects carrying one of these tags keep their orientation; wins over `rotate_tags`.
+    #[serde(default)]
+    pub no_rotate_tags: Vec<String>,
+    /// Pairs of objects that must keep a minimum clearance to each other.
+    #[serde(default)]
+    pub min_gap_between: Vec<GapConstraintRequest>,
+    /// Groups of objects packed as one rigid unit and reported individually.
+    #[serde(default)]
+    pub bundles: Vec<BundleRequest>,
+    /// Overrides whether boxes of equal weight may stack on each other.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub allow_equal_weight_stacking: Option<bool>,
+    /// Overrides whether objects exceeding a weight limit by any amount are rejected.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub strict_weight: Option<bool>,
+    /// Volume utilization (0.0 to 1.0) at which a container stops receiving further objects.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub target_utilization: Option<f64>,
+    /// Overrides whether packed objects are slid toward the loading corner after packing.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub compact: Option<bool>,
+    /// Overrides whether floating objects are lowered onto the surface beneath them.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub settle: Option<bool>,
+    /// Overrides whether objects prefer positions beside objects of the same `category`.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub cluster_by_category: Option<bool>,
+    /// Overrides whether objects prefer positions against the container walls.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub prefer_wall_contact: Option<bool>,
+    /// Overrides whether a floor position wins over an elevated one outside the balance limit.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub prefer_floor_fallback: Option<bool>,
+    /// Overrides whether objects are stacked onto placed objects with the same footprint first.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub prefer_column_stacking: Option<bool>,
+    /// Overrides whether unplaced objects report how many candidate positions failed each check.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub track_rejections: Option<bool>,
+    /// Overrides whether objects with similar footprints are grouped before packing.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub cluster: Option<bool>,
+    /// Emits live container diagnostics only every this many placements per container.
+    #[serde(default)]
+    #[schema(nullable = true, minimum = 1)]
+    pub diagnostics_interval: Option<usize>,
+    /// Streams a full `Snapshot` of every container each this many placements (`0` disables).
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub snapshot_every: Option<usize>,
+    /// Streams a `Progress` throughput event every this many processed objects (`0` disables).
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub progress_every: Option<usize>,
+    /// Streams a `Progress` event once this many milliseconds passed since the last one.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub progress_interval_ms: Option<u64>,
+    /// Overrides the server's minimum object dimension (`0` disables the check).
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub min_object_dimension: Option<f64>,
+    /// Incremental mode: a previous `/pack` response whose placements are kept as-is.
+    ///
+    /// Only the new objects need to be listed in `objects`; they fill the remaining space of the
+    /// prior containers before any new container is opened.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub prior_result: Option<PackResponse>,
+    /// Virtual boxes that block space in every container they fit into.
+    #[serde(default)]
+    pub reserved: Vec<ReservedSpaceRequest>,
+    /// Unit of all object weights and container `max_weight` values (default: `kg`).
+    ///
+    /// Weights are normalized to kilograms before packing, so the response reports kilograms.
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub weight_unit: Option<WeightUnit>,
 }
 
 #[derive(Debug)]
@@ -133,125 +576,1441 @@ struct ValidatedPackRequest {
     containers: Vec<ContainerBlueprint>,
     objects: Vec<Box3D>,
     allow_rotations: Option<bool>,
+    strict: bool,
+    profile: bool,
+    include_corners: bool,
+    include_rotation: bool,
+    round_decimals: Option<u32>,
+    target_container_count: Option<usize>,
+    strict_layering: Option<bool>,
+    packing_profile: Option<PackingProfile>,
+    primary_objective: Option<PrimaryObjective>,
+    mass_placement: Option<MassPlacement>,
+    load_direction: Option<LoadDirection>,
+    max_overhang: Option<f64>,
+    restarts: Option<usize>,
+    template_merge_tolerance: Option<f64>,
+    relative_tolerance: Option<bool>,
+    floor_min_coverage: Option<f64>,
+    container_order: Option<ContainerOrder>,
+    template_preference: Option<TemplatePreference>,
+    new_container_policy: Option<NewContainerPolicy>,
+    max_object_volume_fraction: Option<f64>,
+    top_clearance: Option<f64>,
+    allow_equal_weight_stacking: Option<bool>,
+    strict_weight: Option<bool>,
+    compact: Option<bool>,
+    settle: Option<bool>,
+    cluster_by_category: Option<bool>,
+    prefer_wall_contact: Option<bool>,
+    prefer_floor_fallback: Option<bool>,
+    prefer_column_stacking: Option<bool>,
+    track_rejections: Option<bool>,
+    cluster: Option<bool>,
+    diagnostics_interval: Option<usize>,
+    snapshot_every: Option<usize>,
+    progress_every: Option<usize>,
+    progress_interval_ms: Option<u64>,
+    target_utilization: Option<f64>,
+    existing: Vec<Container>,
+    bundles: Vec<ObjectBundle>,
+    warnings: Vec<String>,
+}
+
+impl ValidatedPackRequest {
+    /// Applies the request-level overrides on top of the server's base configuration.
+    ///
+    /// A server-wide `top_clearance` that leaves no usable height in some container is dropped
+    /// with a warning; request values were already validated.
+    fn packing_config(&mut self, base: PackingConfig) -> PackingConfig {
+        let mut config = match self.packing_profile {
+            Some(profile) => base.with_profile(profile),
+            None => base,
+        };
+        if let Some(allow_rotations) = self.allow_rotations {
+            config.allow_item_rotation = allow_rotations;
+        }
+        if self.target_container_count.is_some() {
+            config.target_container_count = self.target_container_count;
+        }
+        if let Some(strict_layering) = self.strict_layering {
+            config.strict_layering = strict_layering;
+        }
+        if let Some(primary_objective) = self.primary_objective {
+            config.primary_objective = primary_objective;
+        }
+        if let Some(mass_placement) = self.mass_placement {
+            config.mass_placement = mass_placement;
+        }
+        if let Some(load_direction) = self.load_direction {
+            config.load_direction = load_direction;
+        }
+        if self.max_overhang.is_some() {
+            config.max_overhang = self.max_overhang;
+        }
+        if let Some(restarts) = self.restarts {
+            config.restarts = restarts;
+        }
+        if let Some(tolerance) = self.template_merge_tolerance {
+            config.template_merge_tolerance = tolerance;
+        }
+        if let Some(relative_tolerance) = self.relative_tolerance {
+            config.relative_tolerance = relative_tolerance;
+        }
+        if self.floor_min_coverage.is_some() {
+            config.floor_min_coverage = self.floor_min_coverage;
+        }
+        if let Some(container_order) = self.container_order {
+            config.container_order = container_order;
+        }
+        if let Some(template_preference) = self.template_preference {
+            config.template_preference = template_preference;
+        }
+        if let Some(policy) = self.new_container_policy {
+            config.new_container_policy = policy;
+        }
+        if self.profile {
+            config.profile = true;
+        }
+        if self.max_object_volume_fraction.is_some() {
+            config.max_object_volume_fraction = self.max_object_volume_fraction;
+        }
+        if let Some(clearance) = self.top_clearance {
+            config.top_clearance = clearance;
+        } else if config.top_clearance > 0.0 {
+            let lowest = lowest_ceiling(&self.containers, &self.existing);
+            if config.top_clearance >= lowest {
+                self.warnings.push(format!(
+                    "SORT_IT_NOW_PACKING_TOP_CLEARANCE {} is not below the lowest container ceiling {lowest}; no top clearance applied",
+                    config.top_clearance
+                ));
+                config.top_clearance = 0.0;
+            }
+        }
+        if let Some(allow) = self.allow_equal_weight_stacking {
+            config.allow_equal_weight_stacking = allow;
+        }
+        if let Some(strict) = self.strict_weight {
+            config.strict_weight = strict;
+        }
+        if let Some(compact) = self.compact {
+            config.compact = compact;
+        }
+        if let Some(settle) = self.settle {
+            config.settle = settle;
+        }
+        if let Some(cluster) = self.cluster_by_category {
+            config.cluster_by_category = cluster;
+        }
+        if let Some(prefer) = self.prefer_wall_contact {
+            config.prefer_wall_contact = prefer;
+        }
+        if let Some(prefer) = self.prefer_floor_fallback {
+            config.prefer_floor_fallback = prefer;
+        }
+        if let Some(prefer) = self.prefer_column_stacking {
+            config.prefer_column_stacking = prefer;
+        }
+        if let Some(track) = self.track_rejections {
+            config.track_rejections = track;
+        }
+        if let Some(cluster) = self.cluster {
+            config.cluster_footprints = cluster;
+        }
+        if let Some(interval) = self.diagnostics_interval {
+            config.diagnostics_interval = interval;
+        }
+        if let Some(every) = self.snapshot_every {
+            config.snapshot_every = Some(every);
+        }
+        if let Some(every) = self.progress_every {
+            config.progress_every = Some(every);
+        }
+        if let Some(interval_ms) = self.progress_interval_ms {
+            config.progress_interval_ms = Some(interval_ms);
+        }
+        if self.target_utilization.is_some() {
+            config.target_utilization = self.target_utilization;
+        }
+        config
+    }
+
+    fn into_parts(self) -> (Vec<Container>, Vec<Box3D>, Vec<ContainerBlueprint>) {
+        (self.existing, self.objects, self.containers)
+    }
+}
+
+/// Reasons a [`PackRequest`] can be rejected before packing begins.
+#[derive(Debug)]
+pub enum PackRequestValidationError {
+    MissingContainers,
+    InvalidContainer(ValidationError),
+    InvalidObject(ValidationError),
+    TooManyContainers {
+        count: usize,
+        max: usize,
+    },
+    TooManyObjects {
+        count: usize,
+        max: usize,
+    },
+    /// An object is smaller than the configured minimum dimension along at least one axis.
+    ObjectBelowMinimumDimension {
+        id: usize,
+        dims: (f64, f64, f64),
+        min: f64,
+    },
+    /// A reserved block has invalid dimensions or does not fit into any container type.
+    InvalidReservedSpace {
+        index: usize,
+        reason: String,
+    },
+    /// A `min_gap_between` entry has a negative gap or references an unknown object.
+    InvalidGapConstraint {
+        index: usize,
+        reason: String,
+    },
+    /// A bundle is malformed or references objects that are missing or already bundled.
+    InvalidBundle {
+        index: usize,
+        reason: String,
+    },
+    /// A `template_names` entry is not part of the server-side template library.
+    UnknownTemplate {
+        name: String,
+    },
+    /// An object's `allowed_template_ids` lists a container type the request does not define.
+    UnknownTemplateReference {
+        id: usize,
+        template_id: usize,
+    },
+    /// The `prior_result` of an incremental request contains invalid placements.
+    InvalidPriorResult(RediagnoseError),
+    /// Two objects (or an object and a prior placement) share the same `id`.
+    DuplicateObjectId {
+        id: usize,
+    },
+    /// Strict mode was requested and at least one object could not be placed.
+    UnplacedInStrictMode {
+        unplaced_ids: Vec<usize>,
+    },
+}
+
+impl std::fmt::Display for PackRequestValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackRequestValidationError::MissingContainers => {
+                write!(f, "At least one packaging type must be specified")
+            }
+            PackRequestValidationError::InvalidContainer(err) => {
+                write!(f, "Invalid container configuration: {err}")
+            }
+            PackRequestValidationError::InvalidObject(err) => write!(f, "{err}"),
+            PackRequestValidationError::TooManyContainers { count, max } => write!(
+                f,
+                "Too many container types: {count} exceeds the configured limit of {max}"
+            ),
+            PackRequestValidationError::TooManyObjects { count, max } => write!(
+                f,
+                "Too many objects: {count} exceeds the configured limit of {max}"
+            ),
+            PackRequestValidationError::ObjectBelowMinimumDimension { id, dims, min } => write!(
+                f,
+                "Object {id} has dimensions {} × {} × {} below the minimum object dimension of {min}",
+                dims.0, dims.1, dims.2
+            ),
+            PackRequestValidationError::InvalidReservedSpace { index, reason } => {
+                write!(f, "Invalid reserved space #{index}: {reason}")
+            }
+            PackRequestValidationError::InvalidGapConstraint { index, reason } => {
+                write!(f, "Invalid gap constraint #{index}: {reason}")
+            }
+            PackRequestValidationError::InvalidBundle { index, reason } => {
+                write!(f, "Invalid bundle #{index}: {reason}")
+            }
+            PackRequestValidationError::UnknownTemplate { name } => {
+                write!(f, "Unknown container template '{name}'")
+            }
+            PackRequestValidationError::UnknownTemplateReference { id, template_id } => write!(
+                f,
+                "Object {id} references unknown container type {template_id} in allowed_template_ids"
+            ),
+            PackRequestValidationError::InvalidPriorResult(err) => {
+                write!(f, "Invalid prior result: {err}")
+            }
+            PackRequestValidationError::DuplicateObjectId { id } => write!(
+                f,
+                "Duplicate object id {id}: object ids must be unique (unset reject_duplicate_object_ids to accept duplicates with a warning)"
+            ),
+            PackRequestValidationError::UnplacedInStrictMode { unplaced_ids } => {
+                let ids = unplaced_ids
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "Strict mode: {} object(s) could not be placed (ids: {ids})",
+                    unplaced_ids.len()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PackRequestValidationError {}
+
+impl PackRequest {
+    /// Appends the library templates named in `template_names` to `containers`.
+    ///
+    /// Must run before validation so resolved templates count towards the container limit.
+    pub fn with_library_templates(
+        mut self,
+        library: &TemplateLibrary,
+    ) -> Result<Self, PackRequestValidationError> {
+        for name in std::mem::take(&mut self.template_names) {
+            let template = library
+                .get(&name)
+                .ok_or(PackRequestValidationError::UnknownTemplate { name: name.clone() })?;
+            self.containers.push(template.clone());
+        }
+        Ok(self)
+    }
+
+    fn into_validated(
+        self,
+        limits: RequestLimits,
+    ) -> Result<ValidatedPackRequest, PackRequestValidationError> {
+        let limits = match self.min_object_dimension {
+            Some(min) => limits.with_min_object_dimension(min),
+            None => limits,
+        };
+
+        let prior_containers = self
+            .prior_result
+            .map(|prior| prior.results)
+            .unwrap_or_default();
+
+        // Incremental requests may rely solely on the prior containers.
+        if self.containers.is_empty() && prior_containers.is_empty() {
+            return Err(PackRequestValidationError::MissingContainers);
+        }
+
+        if !limits.allows_containers(self.containers.len()) {
+            return Err(PackRequestValidationError::TooManyContainers {
+                count: self.containers.len(),
+                max: limits.max_containers(),
+            });
+        }
+
+        let object_count = self.objects.len()
+            + prior_containers
+                .iter()
+                .map(|c| c.placed.len())
+                .sum::<usize>();
+        if !limits.allows_objects(object_count) {
+            return Err(PackRequestValidationError::TooManyObjects {
+                count: object_count,
+                max: limits.max_objects(),
+            });
+        }
+
+        let weight_unit = self.weight_unit.unwrap_or_default();
+
+        let mut containers = self
+            .containers
+            .into_iter()
+            .enumerate()
+            .map(|(idx, mut spec)| {
+                spec.max_weight = weight_unit.to_kg(spec.max_weight);
+                spec.reserved_weight = spec.reserved_weight.map(|w| weight_unit.to_kg(w));
+                spec.into_blueprint(idx, self.clamp_origin)?
+                    .with_min_volume(limits.min_container_volume())?
+                    .with_max_aspect_ratio(limits.max_container_aspect_ratio())
+            })
+            .collect::<Result<Vec<_>, ValidationError>>()
+            .map_err(PackRequestValidationError::InvalidContainer)?;
+
+        for (rank, &template_id) in self.fill_order.iter().enumerate() {
+            if self.fill_order[..rank].contains(&template_id) {
+                return Err(PackRequestValidationError::InvalidContainer(
+                    ValidationError::InvalidConfiguration(format!(
+                        "fill_order lists container type {template_id} more than once"
+                    )),
+                ));
+            }
+            let Some(template) = containers.iter_mut().find(|t| t.id == template_id) else {
+                return Err(PackRequestValidationError::InvalidContainer(
+                    ValidationError::InvalidConfiguration(format!(
+                        "fill_order references unknown container type {template_id}"
+                    )),
+                ));
+            };
+            template.fill_rank = Some(rank);
+        }
+
+        let reserved = self
+            .reserved
+            .into_iter()
+            .enumerate()
+            .map(|(index, spec)| {
+                spec.into_placed(index).map_err(|err| {
+                    PackRequestValidationError::InvalidReservedSpace {
+                        index,
+                        reason: err.to_string(),
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let objects = self
+            .objects
+            .into_iter()
+            .map(|mut obj| {
+                obj.weight = weight_unit.to_kg(obj.weight);
+                obj.allow_rotation = obj.allow_rotation.or_else(|| {
+                    resolve_tag_rotation(&obj.tags, &self.rotate_tags, &self.no_rotate_tags)
+                });
+                obj.validated()
+            })
+            .collect::<Result<Vec<_>, ValidationError>>()
+            .map_err(PackRequestValidationError::InvalidObject)?;
+
+        let prior_ids = prior_containers
+            .iter()
+            .flat_map(|container| container.placed.iter().map(|object| object.id));
+        let (mut objects, warnings) = check_duplicate_object_ids(
+            objects,
+            prior_ids,
+            DuplicateIdPolicy::from_flags(self.reject_duplicate_object_ids, self.dedupe_object_ids),
+        )?;
+
+        if let Some(obj) = objects
+            .iter()
+            .find(|obj| !limits.allows_object_dims(obj.dims))
+        {
+            return Err(PackRequestValidationError::ObjectBelowMinimumDimension {
+                id: obj.id,
+                dims: obj.dims,
+                min: limits.min_object_dimension(),
+            });
+        }
+
+        let mut existing = prior_containers
+            .into_iter()
+            .map(|packed| packed.restore(PackingConfig::DEFAULT_GENERAL_EPSILON))
+            .collect::<Result<Vec<_>, RediagnoseError>>()
+            .map_err(PackRequestValidationError::InvalidPriorResult)?;
+
+        // Prior containers keep the template ids of the request that opened them.
+        let defines_template = |template_id: usize| {
+            containers.iter().any(|t| t.id == template_id)
+                || existing.iter().any(|c| c.template_id == Some(template_id))
+        };
+        if let Some((id, template_id)) = objects.iter().find_map(|obj| {
+            obj.allowed_template_ids
+                .iter()
+                .flatten()
+                .find(|&&template_id| !defines_template(template_id))
+                .map(|&template_id| (obj.id, template_id))
+        }) {
+            return Err(PackRequestValidationError::UnknownTemplateReference { id, template_id });
+        }
+
+        if let Some(clearance) = self.top_clearance {
+            let lowest = lowest_ceiling(&containers, &existing);
+            if !(clearance.is_finite() && clearance >= 0.0 && clearance < lowest) {
+                return Err(PackRequestValidationError::InvalidContainer(
+                    ValidationError::InvalidConfiguration(format!(
+                        "top_clearance {clearance} must be non-negative and below the lowest container ceiling {lowest}"
+                    )),
+                ));
+            }
+        }
+
+        attach_reserved_space(&reserved, &mut containers, &mut existing)?;
+        let bundles = form_bundles(&self.bundles, &mut objects)?;
+        attach_gap_constraints(&self.min_gap_between, &bundles, &mut objects, &mut existing)?;
+
+        Ok(ValidatedPackRequest {
+            containers,
+            objects,
+            existing,
+            allow_rotations: self.allow_rotations,
+            strict: self.strict,
+            profile: self.profile,
+            include_corners: self.include_corners,
+            include_rotation: self.include_rotation,
+            round_decimals: self.round_decimals,
+            target_container_count: self.target_container_count,
+            strict_layering: self.strict_layering,
+            packing_profile: self.packing_profile,
+            primary_objective: self.primary_objective,
+            mass_placement: self.mass_placement,
+            load_direction: self.load_direction,
+            max_overhang: self.max_overhang,
+            restarts: self.restarts,
+            template_merge_tolerance: self.template_merge_tolerance,
+            relative_tolerance: self.relative_tolerance,
+            floor_min_coverage: self.floor_min_coverage,
+            container_order: self.container_order,
+            template_preference: self.template_preference,
+            new_container_policy: self.new_container_policy,
+            max_object_volume_fraction: self.max_object_volume_fraction,
+            top_clearance: self.top_clearance,
+            allow_equal_weight_stacking: self.allow_equal_weight_stacking,
+            strict_weight: self.strict_weight,
+            compact: self.compact,
+            settle: self.settle,
+            cluster_by_category: self.cluster_by_category,
+            prefer_wall_contact: self.prefer_wall_contact,
+            prefer_floor_fallback: self.prefer_floor_fallback,
+            prefer_column_stacking: self.prefer_column_stacking,
+            track_rejections: self.track_rejections,
+            cluster: self.cluster,
+            diagnostics_interval: self.diagnostics_interval,
+            snapshot_every: self.snapshot_every,
+            progress_every: self.progress_every,
+            progress_interval_ms: self.progress_interval_ms,
+            target_utilization: self.target_utilization,
+            bundles,
+            warnings,
+        })
+    }
+}
+
+/// How objects repeating an earlier `id` are handled during validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DuplicateIdPolicy {
+    /// Keep every object and report each repeated id as a warning.
+    Warn,
+    /// Keep the first occurrence and drop later ones with a warning.
+    Dedupe,
+    /// Reject the request at the first repeated id.
+    Reject,
+}
+
+impl DuplicateIdPolicy {
+    /// Resolves the request flags; `reject` wins over `dedupe`.
+    fn from_flags(reject: bool, dedupe: bool) -> Self {
+        if reject {
+            DuplicateIdPolicy::Reject
+        } else if dedupe {
+            DuplicateIdPolicy::Dedupe
+        } else {
+            DuplicateIdPolicy::Warn
+        }
+    }
+}
+
+/// Detects objects whose `id` repeats an earlier object or a placement from the prior result.
+///
+/// By default duplicates are kept and each one is reported as a warning naming its id. With
+/// [`DuplicateIdPolicy::Dedupe`] later occurrences are dropped instead, and with
+/// [`DuplicateIdPolicy::Reject`] the first duplicate rejects the request.
+fn check_duplicate_object_ids(
+    objects: Vec<Box3D>,
+    prior_ids: impl IntoIterator<Item = usize>,
+    policy: DuplicateIdPolicy,
+) -> Result<(Vec<Box3D>, Vec<String>), PackRequestValidationError> {
+    let mut seen: HashSet<usize> = prior_ids.into_iter().collect();
+    let mut warnings = Vec::new();
+    let mut unique = Vec::with_capacity(objects.len());
+    for object in objects {
+        if seen.insert(object.id) {
+            unique.push(object);
+            continue;
+        }
+        match policy {
+            DuplicateIdPolicy::Reject => {
+                return Err(PackRequestValidationError::DuplicateObjectId { id: object.id });
+            }
+            DuplicateIdPolicy::Dedupe => warnings.push(format!(
+                "Duplicate object id {}: dropped a later occurrence",
+                object.id
+            )),
+            DuplicateIdPolicy::Warn => {
+                warnings.push(format!(
+                    "Duplicate object id {}: object ids should be unique",
+                    object.id
+                ));
+                unique.push(object);
+            }
+        }
+    }
+    Ok((unique, warnings))
+}
+
+/// Resolves the rotation rule of an object from its tags.
+///
+/// `no_rotate_tags` wins over `rotate_tags`; `None` leaves the decision to the global setting.
+fn resolve_tag_rotation(
+    tags: &[String],
+    rotate_tags: &[String],
+    no_rotate_tags: &[String],
+) -> Option<bool> {
+    let has_any = |rules: &[String]| tags.iter().any(|tag| rules.contains(tag));
+    if has_any(no_rotate_tags) {
+        Some(false)
+    } else if has_any(rotate_tags) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Records every gap constraint on both objects of its pair.
+///
+/// The ids may refer to new objects or to placements from the prior result; a constraint naming
+/// an unknown id is rejected, since it would silently have no effect.
+fn attach_gap_constraints(
+    constraints: &[GapConstraintRequest],
+    bundles: &[ObjectBundle],
+    objects: &mut [Box3D],
+    existing: &mut [Container],
+) -> Result<(), PackRequestValidationError> {
+    for (index, constraint) in constraints.iter().enumerate() {
+        let invalid =
+            |reason: String| PackRequestValidationError::InvalidGapConstraint { index, reason };
+        if !constraint.gap.is_finite() || constraint.gap < 0.0 {
+            return Err(invalid(format!(
+                "gap must be a non-negative number, got {}",
+                constraint.gap
+            )));
+        }
+        if constraint.a == constraint.b {
+            return Err(invalid(format!(
+                "object {} cannot keep a gap to itself",
+                constraint.a
+            )));
+        }
+        // Members are packed as their bundle's composite object.
+        let composite_of = |id: usize| {
+            bundles
+                .iter()
+                .find(|bundle| bundle.members.iter().any(|(member, _)| member.id == id))
+                .map_or(id, |bundle| bundle.id)
+        };
+        let (a, b) = (composite_of(constraint.a), composite_of(constraint.b));
+        if a == b {
+            return Err(invalid(format!(
+                "objects {} and {} are members of the same bundle",
+                constraint.a, constraint.b
+            )));
+        }
+
+        for (id, other) in [(a, b), (b, a)] {
+            let mut found = false;
+            let targets = objects.iter_mut().chain(
+                existing
+                    .iter_mut()
+                    .flat_map(|container| container.placed.iter_mut().map(|p| &mut p.object)),
+            );
+            for object in targets.filter(|object| object.id == id) {
+                object.min_gaps.push(ObjectGap {
+                    id: other,
+                    gap: constraint.gap,
+                });
+                found = true;
+            }
+            if !found {
+                return Err(invalid(format!("references unknown object id {id}")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the members of every bundle in `objects` by one composite object.
+///
+/// The composite takes the id of the first member, spans the bounding box of all members at
+/// their offsets, weighs their sum and may not rotate. It must stay accessible if any member
+/// must, may only use the container types every member allows, and keeps the category, color,
+/// group and tags the members have in common. Members of different groups are rejected, as
+/// groups never share a container.
+fn form_bundles(
+    bundles: &[BundleRequest],
+    objects: &mut Vec<Box3D>,
+) -> Result<Vec<ObjectBundle>, PackRequestValidationError> {
+    let mut resolved = Vec::with_capacity(bundles.len());
+    for (index, bundle) in bundles.iter().enumerate() {
+        let invalid = |reason: String| PackRequestValidationError::InvalidBundle { index, reason };
+        if bundle.member_ids.is_empty() {
+            return Err(invalid("needs at least one member".to_string()));
+        }
+        if bundle.member_ids.len() != bundle.offsets.len() {
+            return Err(invalid(format!(
+                "has {} member(s) but {} offset(s)",
+                bundle.member_ids.len(),
+                bundle.offsets.len()
+            )));
+        }
+        if let Some(offset) = bundle.offsets.iter().find(|(x, y, z)| {
+            [x, y, z]
+                .iter()
+                .any(|value| !value.is_finite() || **value < 0.0)
+        }) {
+            return Err(invalid(format!(
+                "offsets must be non-negative, got {:?}",
+                offset
+            )));
+        }
+
+        let mut members = Vec::with_capacity(bundle.member_ids.len());
+        for (&id, &offset) in bundle.member_ids.iter().zip(&bundle.offsets) {
+            let Some(position) = objects.iter().position(|object| object.id == id) else {
+                return Err(invalid(format!(
+                    "references unknown or already bundled object id {id}"
+                )));
+            };
+            members.push((objects.remove(position), offset));
+        }
+        if let Some(object) = objects
+            .iter()
+            .find(|object| bundle.member_ids.contains(&object.id))
+        {
+            return Err(invalid(format!(
+                "member id {} is not unique in the request",
+                object.id
+            )));
+        }
+        let shared = |field: fn(&Box3D) -> &Option<String>| {
+            let first = field(&members[0].0);
+            members
+                .iter()
+                .all(|(object, _)| field(object) == first)
+                .then(|| first.clone())
+                .flatten()
+        };
+        if shared(|object| &object.group_id).is_none()
+            && members.iter().any(|(object, _)| object.group_id.is_some())
+        {
+            return Err(invalid("members belong to different groups".to_string()));
+        }
+        let allowed_template_ids = members
+            .iter()
+            .filter_map(|(object, _)| object.allowed_template_ids.clone())
+            .reduce(|common, ids| common.into_iter().filter(|id| ids.contains(id)).collect());
+        if allowed_template_ids.as_ref().is_some_and(Vec::is_empty) {
+            return Err(invalid(
+                "members have no allowed container type in common".to_string(),
+            ));
+        }
+        let placed: Vec<PlacedBox> = members
+            .iter()
+            .map(|(object, offset)| PlacedBox::new(object.clone(), *offset))
+            .collect();
+        for (i, a) in placed.iter().enumerate() {
+            if let Some(b) = placed[i + 1..].iter().find(|b| intersects(a, b)) {
+                return Err(invalid(format!(
+                    "members {} and {} overlap",
+                    a.object.id, b.object.id
+                )));
+            }
+        }
+
+        let extent = |axis: fn(&PlacedBox) -> f64| placed.iter().map(axis).fold(0.0, f64::max);
+        let composite = Box3D {
+            id: bundle.member_ids[0],
+            dims: (
+                extent(|p| p.position.0 + p.object.dims.0),
+                extent(|p| p.position.1 + p.object.dims.1),
+                extent(|p| p.position.2 + p.object.dims.2),
+            ),
+            weight: members.iter().map(|(object, _)| object.weight).sum(),
+            category: shared(|object| &object.category),
+            color: shared(|object| &object.color),
+            group_id: shared(|object| &object.group_id),
+            tags: members[0]
+                .0
+                .tags
+                .iter()
+                .filter(|tag| members.iter().all(|(object, _)| object.tags.contains(tag)))
+                .cloned()
+                .collect(),
+            allow_rotation: Some(false),
+            allowed_template_ids,
+            must_be_accessible: members.iter().any(|(object, _)| object.must_be_accessible),
+            optional: members.iter().all(|(object, _)| object.optional),
+            // Every member has to stay within its own reach limit.
+            max_placement_height: members
+                .iter()
+                .filter_map(|(object, offset)| {
+                    object.max_placement_height.map(|max| max - offset.2)
+                })
+                .reduce(f64::min),
+            ..Default::default()
+        };
+        resolved.push(ObjectBundle {
+            id: composite.id,
+            members,
+        });
+        objects.push(composite);
+    }
+    Ok(resolved)
+}
+
+/// Reports every bundle as its members again, placed at the bundle position plus their offset.
+fn expand_bundles(response: &mut PackResponse, bundles: &[ObjectBundle]) {
+    if bundles.is_empty() {
+        return;
+    }
+    let bundle_of = |id: usize| bundles.iter().find(|bundle| bundle.id == id);
+
+    for container in &mut response.results {
+        container.placed = std::mem::take(&mut container.placed)
+            .into_iter()
+            .flat_map(|packed| match bundle_of(packed.id) {
+                Some(bundle) => bundle
+                    .placed_members(packed.pos)
+                    .map(|member| PackedObject::from(&member))
+                    .collect(),
+                None => vec![packed],
+            })
+            .collect();
+        container.refresh_envelope();
+    }
+    response.unplaced = std::mem::take(&mut response.unplaced)
+        .into_iter()
+        .flat_map(|entry| match bundle_of(entry.id) {
+            Some(bundle) => bundle
+                .members
+                .iter()
+                .map(|(member, _)| PackedUnplacedObject {
+                    id: member.id,
+                    weight: member.weight,
+                    dims: member.dims,
+                    reason_code: entry.reason_code.clone(),
+                    reason: entry.reason.clone(),
+                    category: member.category.clone(),
+                    color: member.color.clone(),
+                    rejections: entry.rejections,
+                })
+                .collect(),
+            None => vec![entry],
+        })
+        .collect();
+}
+
+/// Wraps `on_event` so that events about a bundle's composite object describe its members.
+///
+/// `ObjectPlaced` and `ObjectRejected` are repeated per member (placements at the bundle
+/// position plus the member's offset, with the running container weight), and snapshots list
+/// the members instead of the composite. All other events pass through unchanged.
+fn expand_bundle_events<'a>(
+    bundles: &'a [ObjectBundle],
+    mut on_event: impl FnMut(&PackEvent) + 'a,
+) -> impl FnMut(&PackEvent) + 'a {
+    let bundle_of = move |id: usize| bundles.iter().find(|bundle| bundle.id == id);
+    move |event| match event {
+        PackEvent::ObjectPlaced {
+            container_id,
+            id,
+            pos,
+            weight,
+            total_weight,
+            ..
+        } if bundle_of(*id).is_some() => {
+            let mut running_weight = total_weight - weight;
+            for member in bundle_of(*id).expect("checked above").placed_members(*pos) {
+                running_weight += member.object.weight;
+                on_event(&PackEvent::ObjectPlaced {
+                    container_id: *container_id,
+                    id: member.object.id,
+                    pos: member.position,
+                    weight: member.object.weight,
+                    dims: member.object.dims,
+                    total_weight: running_weight,
+                });
+            }
+        }
+        PackEvent::ObjectRejected {
+            id,
+            reason_code,
+            reason_text,
+            ..
+        } if bundle_of(*id).is_some() => {
+            for (member, _) in &bundle_of(*id).expect("checked above").members {
+                on_event(&PackEvent::ObjectRejected {
+                    id: member.id,
+                    weight: member.weight,
+                    dims: member.dims,
+                    reason_code: reason_code.clone(),
+                    reason_text: reason_text.clone(),
+                });
+            }
+        }
+        PackEvent::Snapshot {
+            container_id,
+            placed,
+        } if placed.iter().any(|object| bundle_of(object.id).is_some()) => {
+            on_event(&PackEvent::Snapshot {
+                container_id: *container_id,
+                placed: placed
+                    .iter()
+                    .flat_map(|object| match bundle_of(object.id) {
+                        Some(bundle) => bundle
+                            .placed_members(object.pos)
+                            .map(|member| SnapshotObject::from(&member))
+                            .collect(),
+                        None => vec![object.clone()],
+                    })
+                    .collect(),
+            });
+        }
+        _ => on_event(event),
+    }
+}
+
+/// Lowest ceiling of all container types and prior containers, including height profile steps.
+fn lowest_ceiling(containers: &[ContainerBlueprint], existing: &[Container]) -> f64 {
+    let lowest = |dims: (f64, f64, f64), profile: &[(f64, f64)]| {
+        profile
+            .iter()
+            .map(|&(_, height)| height)
+            .fold(dims.2, f64::min)
+    };
+    containers
+        .iter()
+        .map(|t| lowest(t.dims, &t.height_profile))
+        .chain(existing.iter().map(|c| lowest(c.dims, &c.height_profile)))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Adds every reserved block to each container type (and prior container) it fits into.
+///
+/// A block that fits nowhere is rejected, since it would silently have no effect.
+fn attach_reserved_space(
+    reserved: &[PlacedBox],
+    blueprints: &mut [ContainerBlueprint],
+    existing: &mut [Container],
+) -> Result<(), PackRequestValidationError> {
+    for (index, block) in reserved.iter().enumerate() {
+        let (position, dims) = (block.position, block.object.dims);
+        let mut attached = false;
+        for blueprint in blueprints.iter_mut() {
+            if blueprint.contains_region(position, dims) {
+                blueprint.reserved.push(block.clone());
+                attached = true;
+            }
+        }
+        for container in existing.iter_mut() {
+            if fits_inside(block, container, PackingConfig::DEFAULT_GENERAL_EPSILON) {
+                container.reserved.push(block.clone());
+                attached = true;
+            }
+        }
+        if !attached {
+            return Err(PackRequestValidationError::InvalidReservedSpace {
+                index,
+                reason: "does not lie within any container".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Runs validation and packing for a deserialized request, returning a structured error.
+///
+/// This is the single shared entry point used by both the HTTP `/pack` handler and the offline
+/// CLI, keeping request handling DRY across transports.
+pub fn run_pack(
+    request: PackRequest,
+    base_config: PackingConfig,
+    limits: RequestLimits,
+) -> Result<PackResponse, PackRequestValidationError> {
+    run_pack_with_events(request, base_config, limits, |_| {})
 }
 
-impl ValidatedPackRequest {
-    fn into_parts(self) -> (Vec<Box3D>, Vec<ContainerBlueprint>, Option<bool>) {
-        (self.objects, self.containers, self.allow_rotations)
+/// Like [`run_pack`], but reports every [`PackEvent`] of the run to `on_event`.
+pub fn run_pack_with_events(
+    request: PackRequest,
+    base_config: PackingConfig,
+    limits: RequestLimits,
+    on_event: impl FnMut(&PackEvent),
+) -> Result<PackResponse, PackRequestValidationError> {
+    let mut validated = request.into_validated(limits)?;
+    let packing_config = validated.packing_config(base_config);
+    let warnings = std::mem::take(&mut validated.warnings);
+    let strict = validated.strict;
+    let round_decimals = validated.round_decimals;
+    let include_corners = validated.include_corners;
+    let include_rotation = validated.include_rotation;
+    let bundles = std::mem::take(&mut validated.bundles);
+    let (existing, objects, container_blueprints) = validated.into_parts();
+    // Input dimensions per id; bundle members replace the composite that carries their id.
+    let members = bundles.iter().flat_map(|bundle| bundle.members.iter());
+    let original_dims: HashMap<usize, (f64, f64, f64)> = objects
+        .iter()
+        .map(|obj| (obj.id, obj.dims))
+        .chain(members.map(|(member, _)| (member.id, member.dims)))
+        .collect();
+    let has_containers = !existing.is_empty() || !container_blueprints.is_empty();
+
+    let packing_result = pack_objects_incremental_with_progress(
+        existing,
+        objects,
+        container_blueprints,
+        packing_config,
+        expand_bundle_events(&bundles, on_event),
+    );
+    if strict && !packing_result.is_complete() {
+        return Err(PackRequestValidationError::UnplacedInStrictMode {
+            unplaced_ids: packing_result
+                .unplaced
+                .iter()
+                .map(|entry| entry.object.id)
+                .collect(),
+        });
     }
+    let coarsest_grid_step = packing_result
+        .container_diagnostics
+        .iter()
+        .filter_map(|diagnostics| diagnostics.coarsened_grid_step)
+        .reduce(f64::max);
+    let mut response = PackResponse::from_packing_result_in_context(packing_result, has_containers);
+    response.warnings = warnings;
+    if let Some(step) = coarsest_grid_step {
+        response.warnings.push(format!(
+            "grid_step {} exceeds SORT_IT_NOW_PACKING_MAX_AXIS_POSITIONS ({}); a coarser step of up to {step} was used",
+            packing_config.grid_step, packing_config.max_axis_positions
+        ));
+    }
+    expand_bundles(&mut response, &bundles);
+    response.detect_rotation(&original_dims, packing_config.general_epsilon);
+    if include_rotation {
+        response.annotate_rotations(&original_dims, packing_config.general_epsilon);
+    }
+    let response = match round_decimals {
+        Some(decimals) => response.rounded(decimals),
+        None => response,
+    };
+    Ok(if include_corners {
+        response.with_corners()
+    } else {
+        response
+    })
 }
 
-/// Reasons a [`PackRequest`] can be rejected before packing begins.
+/// Reasons an exported result can be rejected by [`rediagnose`].
 #[derive(Debug)]
-pub enum PackRequestValidationError {
-    MissingContainers,
-    InvalidContainer(ValidationError),
-    InvalidObject(ValidationError),
-    TooManyContainers { count: usize, max: usize },
-    TooManyObjects { count: usize, max: usize },
+pub enum RediagnoseError {
+    TooManyContainers {
+        count: usize,
+        max: usize,
+    },
+    TooManyObjects {
+        count: usize,
+        max: usize,
+    },
+    InvalidContainer {
+        container_id: usize,
+        source: ValidationError,
+    },
+    InvalidObject {
+        container_id: usize,
+        source: ValidationError,
+    },
+    /// An object extends beyond the walls of its container.
+    OutOfBounds {
+        container_id: usize,
+        object_id: usize,
+    },
+    /// Two objects in the same container overlap.
+    Collision {
+        container_id: usize,
+        first_id: usize,
+        second_id: usize,
+    },
 }
 
-impl std::fmt::Display for PackRequestValidationError {
+impl std::fmt::Display for RediagnoseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            PackRequestValidationError::MissingContainers => {
-                write!(f, "At least one packaging type must be specified")
-            }
-            PackRequestValidationError::InvalidContainer(err) => {
-                write!(f, "Invalid container configuration: {err}")
-            }
-            PackRequestValidationError::InvalidObject(err) => write!(f, "{err}"),
-            PackRequestValidationError::TooManyContainers { count, max } => write!(
+            RediagnoseError::TooManyContainers { count, max } => write!(
                 f,
-                "Too many container types: {count} exceeds the configured limit of {max}"
+                "Too many containers: {count} exceeds the configured limit of {max}"
             ),
-            PackRequestValidationError::TooManyObjects { count, max } => write!(
+            RediagnoseError::TooManyObjects { count, max } => write!(
                 f,
                 "Too many objects: {count} exceeds the configured limit of {max}"
             ),
+            RediagnoseError::InvalidContainer {
+                container_id,
+                source,
+            } => write!(f, "Container {container_id}: {source}"),
+            RediagnoseError::InvalidObject {
+                container_id,
+                source,
+            } => write!(f, "Container {container_id}: {source}"),
+            RediagnoseError::OutOfBounds {
+                container_id,
+                object_id,
+            } => write!(
+                f,
+                "Container {container_id}: object {object_id} extends beyond the container bounds"
+            ),
+            RediagnoseError::Collision {
+                container_id,
+                first_id,
+                second_id,
+            } => write!(
+                f,
+                "Container {container_id}: objects {first_id} and {second_id} overlap"
+            ),
         }
     }
 }
 
-impl std::error::Error for PackRequestValidationError {}
+impl std::error::Error for RediagnoseError {}
 
-impl PackRequest {
-    fn into_validated(
-        self,
-        limits: RequestLimits,
-    ) -> Result<ValidatedPackRequest, PackRequestValidationError> {
-        if self.containers.is_empty() {
-            return Err(PackRequestValidationError::MissingContainers);
-        }
+/// Request for a single object/container fit check.
+#[derive(Deserialize, Clone, ToSchema)]
+#[schema(example = json!({
+    "container": { "dims": [120.0, 100.0, 80.0], "max_weight": 500.0 },
+    "object": { "id": 42, "dims": [30.0, 40.0, 20.0], "weight": 5.0 }
+}))]
+pub struct FitsRequest {
+    pub container: ContainerRequest,
+    pub object: Box3D,
+    #[serde(default)]
+    #[schema(nullable = true)]
+    pub allow_rotations: Option<bool>,
+}
 
-        if !limits.allows_containers(self.containers.len()) {
-            return Err(PackRequestValidationError::TooManyContainers {
-                count: self.containers.len(),
-                max: limits.max_containers(),
-            });
-        }
+/// Result of a fit check.
+///
+/// `position` and `dims` (the orientation used) are set when the object fits; otherwise
+/// `reason_code` uses the same codes as `unplaced[].reason_code` of a pack response.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct FitsResponse {
+    pub fits: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<[f64; 3]>)]
+    pub position: Option<(f64, f64, f64)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<[f64; 3]>)]
+    pub dims: Option<(f64, f64, f64)>,
+}
 
-        if !limits.allows_objects(self.objects.len()) {
-            return Err(PackRequestValidationError::TooManyObjects {
-                count: self.objects.len(),
-                max: limits.max_objects(),
-            });
-        }
+/// Converts an internal position into the reported one by adding the container `origin`.
+fn relative_to_origin(position: (f64, f64, f64), origin: (f64, f64, f64)) -> (f64, f64, f64) {
+    (
+        position.0 + origin.0,
+        position.1 + origin.1,
+        position.2 + origin.2,
+    )
+}
 
-        let containers = self
-            .containers
-            .into_iter()
-            .enumerate()
-            .map(|(idx, spec)| spec.into_blueprint(idx))
-            .collect::<Result<Vec<_>, ValidationError>>()
-            .map_err(PackRequestValidationError::InvalidContainer)?;
+/// Checks whether one object fits into an empty container of the given type.
+pub fn check_fit(
+    request: FitsRequest,
+    config: PackingConfig,
+) -> Result<FitsResponse, PackRequestValidationError> {
+    let template = request
+        .container
+        .into_blueprint(0, false)
+        .map_err(PackRequestValidationError::InvalidContainer)?;
+    let object = request
+        .object
+        .validated()
+        .map_err(PackRequestValidationError::InvalidObject)?;
+
+    let mut config = config;
+    if let Some(allow_rotations) = request.allow_rotations {
+        config.allow_item_rotation = allow_rotations;
+    }
 
-        let objects = self
-            .objects
-            .into_iter()
-            .map(|obj| Box3D::new(obj.id, obj.dims, obj.weight))
-            .collect::<Result<Vec<_>, ValidationError>>()
-            .map_err(PackRequestValidationError::InvalidObject)?;
+    Ok(match find_fit(&object, &template, config) {
+        Ok(placed) => FitsResponse {
+            fits: true,
+            reason_code: None,
+            position: Some(relative_to_origin(placed.position, template.origin)),
+            dims: Some(placed.object.dims),
+        },
+        Err(reason) => FitsResponse {
+            fits: false,
+            reason_code: Some(reason.code().to_string()),
+            position: None,
+            dims: None,
+        },
+    })
+}
 
-        Ok(ValidatedPackRequest {
-            containers,
-            objects,
-            allow_rotations: self.allow_rotations,
-        })
-    }
+/// Recomputed diagnostics for a previously exported result.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct RediagnoseResponse {
+    pub results: Vec<RediagnosedContainer>,
+    pub diagnostics_summary: PackingDiagnosticsSummary,
 }
 
-/// Runs validation and packing for a deserialized request, returning a structured error.
+/// Diagnostics of a single container, keyed by the id from the submitted result.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct RediagnosedContainer {
+    pub id: usize,
+    pub total_weight: f64,
+    pub diagnostics: ContainerDiagnostics,
+}
+
+/// Validates the placements of an exported [`PackResponse`] and recomputes its diagnostics.
 ///
-/// This is the single shared entry point used by both the HTTP `/pack` handler and the offline
-/// CLI, keeping request handling DRY across transports.
-pub fn run_pack(
-    request: PackRequest,
-    base_config: PackingConfig,
+/// Positions are taken verbatim (e.g. after manual edits): every object must lie inside its
+/// container and must not overlap another object. Diagnostics contained in the payload are
+/// ignored and recalculated from scratch.
+pub fn rediagnose(
+    result: PackResponse,
+    config: PackingConfig,
     limits: RequestLimits,
-) -> Result<PackResponse, PackRequestValidationError> {
-    let validated = request.into_validated(limits)?;
-    let (objects, container_blueprints, allow_rotations_override) = validated.into_parts();
+) -> Result<RediagnoseResponse, RediagnoseError> {
+    if !limits.allows_containers(result.results.len()) {
+        return Err(RediagnoseError::TooManyContainers {
+            count: result.results.len(),
+            max: limits.max_containers(),
+        });
+    }
+
+    let object_count = result.results.iter().map(|c| c.placed.len()).sum();
+    if !limits.allows_objects(object_count) {
+        return Err(RediagnoseError::TooManyObjects {
+            count: object_count,
+            max: limits.max_objects(),
+        });
+    }
 
-    let mut packing_config = base_config;
-    if let Some(allow_rotations) = allow_rotations_override {
-        packing_config.allow_item_rotation = allow_rotations;
+    let epsilon = config.general_epsilon;
+    let mut results = Vec::with_capacity(result.results.len());
+    for packed in result.results {
+        let container_id = packed.id;
+        let container = packed.restore(epsilon)?;
+
+        results.push(RediagnosedContainer {
+            id: container_id,
+            total_weight: container.total_weight(),
+            diagnostics: compute_container_diagnostics(&container, &config),
+        });
+    }
+
+    let diagnostics_summary = summarize_diagnostics(results.iter().map(|c| &c.diagnostics));
+    Ok(RediagnoseResponse {
+        results,
+        diagnostics_summary,
+    })
+}
+
+impl PackedContainer {
+    /// Recomputes [`Self::occupied_envelope`] from the current placements.
+    fn refresh_envelope(&mut self) {
+        let mut objects = self.placed.iter();
+        let Some(first) = objects.next() else {
+            self.occupied_envelope = Default::default();
+            return;
+        };
+        let far = |object: &PackedObject| {
+            (
+                object.pos.0 + object.dims.0,
+                object.pos.1 + object.dims.1,
+                object.pos.2 + object.dims.2,
+            )
+        };
+        let (mut min, mut max) = (first.pos, far(first));
+        for object in objects {
+            let end = far(object);
+            min = (
+                min.0.min(object.pos.0),
+                min.1.min(object.pos.1),
+                min.2.min(object.pos.2),
+            );
+            max = (max.0.max(end.0), max.1.max(end.1), max.2.max(end.2));
+        }
+        self.occupied_envelope = (min, max);
+    }
+
+    /// Rebuilds an optimizer [`Container`] from an exported container.
+    ///
+    /// Positions are kept verbatim, but every object must lie inside the container and must not
+    /// overlap any other object.
+    fn restore(self, epsilon: f64) -> Result<Container, RediagnoseError> {
+        let container_id = self.id;
+        let mut container = Container::new(self.dims, self.max_weight)
+            .and_then(|container| container.with_height_profile(self.height_profile))
+            .map_err(|source| RediagnoseError::InvalidContainer {
+                container_id,
+                source,
+            })?;
+        container.template_id = self.template_id;
+        container.label = self.label;
+        container.wall_thickness = self.wall_thickness;
+        container.origin = self.origin;
+        container.reserved_weight = self.reserved_weight;
+        container.floor_only_region = self.floor_only_region;
+        container.max_items = self.max_items;
+        container.cost = self.cost;
+        let origin = self.origin;
+
+        for obj in self.placed {
+            let object_id = obj.id;
+            let object = Box3D {
+                id: obj.id,
+                dims: obj.dims,
+                weight: obj.weight,
+                category: obj.category,
+                color: obj.color,
+                ..Default::default()
+            }
+            .validated()
+            .map_err(|source| RediagnoseError::InvalidObject {
+                container_id,
+                source,
+            })?;
+            let position = (
+                obj.pos.0 - origin.0,
+                obj.pos.1 - origin.1,
+                obj.pos.2 - origin.2,
+            );
+            let candidate = PlacedBox::new(object, position);
+            if !fits_inside(&candidate, &container, epsilon) {
+                return Err(RediagnoseError::OutOfBounds {
+                    container_id,
+                    object_id,
+                });
+            }
+            if let Some(other) = container
+                .placed
+                .iter()
+                .find(|other| overlaps(&candidate, other, epsilon))
+            {
+                return Err(RediagnoseError::Collision {
+                    container_id,
+                    first_id: other.object.id,
+                    second_id: object_id,
+                });
+            }
+            container.placed.push(candidate);
+        }
+
+        Ok(container)
     }
+}
+
+/// Checks that a placed object lies within the container walls and below its (possibly stepped)
+/// ceiling, allowing `epsilon` slack.
+fn fits_inside(b: &PlacedBox, cont: &Container, epsilon: f64) -> bool {
+    let (x, y, z) = b.position;
+    let (w, d, h) = b.object.dims;
+    let dims = cont.dims;
+    x >= -epsilon
+        && y >= -epsilon
+        && z >= -epsilon
+        && x + w <= dims.0 + epsilon
+        && y + d <= dims.1 + epsilon
+        && z + h <= cont.ceiling_between(x, x + w) + epsilon
+}
 
-    let packing_result = pack_objects_with_config(objects, container_blueprints, packing_config);
-    Ok(PackResponse::from_packing_result(packing_result))
+/// Checks whether two placed objects overlap by more than `epsilon` on every axis.
+///
+/// Unlike [`crate::geometry::intersects`], this tolerates the rounding noise of positions that went through a JSON
+/// export, so objects that merely touch are not reported as colliding.
+fn overlaps(a: &PlacedBox, b: &PlacedBox, epsilon: f64) -> bool {
+    let axis = |a_pos: f64, a_len: f64, b_pos: f64, b_len: f64| {
+        overlap_1d(a_pos, a_pos + a_len, b_pos, b_pos + b_len) > epsilon
+    };
+    axis(a.position.0, a.object.dims.0, b.position.0, b.object.dims.0)
+        && axis(a.position.1, a.object.dims.1, b.position.1, b.object.dims.1)
+        && axis(a.position.2, a.object.dims.2, b.position.2, b.object.dims.2)
 }
 
 /// Response structure with all packed containers.
 ///
 /// # Fields
 /// * `results` - Vector of containers with placed objects
-#[derive(Serialize, ToSchema)]
+///
+/// The response also deserializes, so an exported result can be sent back to `POST /rediagnose`.
+/// Derived fields (`is_complete`, diagnostics) may be omitted on input.
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct PackResponse {
+    #[serde(default)]
+    pub status: PackStatus,
     pub results: Vec<PackedContainer>,
+    #[serde(default)]
     pub unplaced: Vec<PackedUnplacedObject>,
+    #[serde(default)]
     pub is_complete: bool,
+    #[serde(default)]
     pub diagnostics_summary: PackingDiagnosticsSummary,
+    /// Non-fatal issues found while validating the request, e.g. dropped duplicate ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Phase durations in milliseconds; only present when the request set `profile`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<PhaseTimings>,
+    /// Page metadata; only present when the request was paginated via `page_size`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<PageInfo>,
+    /// `true` if any newly placed object was rotated away from its input dimensions.
+    #[serde(default)]
+    pub rotation_used: bool,
+    /// Sum of the `cost` of all containers in `results`; containers without a cost add 0.
+    #[serde(default)]
+    pub total_cost: f64,
+    /// Container that would take the unplaced objects; absent if everything required was packed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_container: Option<ContainerSpec>,
+    /// Every event of the run, as `/pack_stream` would send them; only present with
+    /// `POST /pack?events=true`.
+    ///
+    /// Container ids follow `results` and positions include each container's `origin`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Option<Vec<Object>>)]
+    pub events: Vec<PackEvent>,
+}
+
+/// Page metadata of a paginated `/pack` response.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, ToSchema)]
+pub struct PageInfo {
+    /// 1-based page number.
+    pub page: usize,
+    pub page_size: usize,
+    /// Number of placements across all containers before slicing.
+    pub total_placements: usize,
+    pub total_pages: usize,
+}
+
+/// Query parameters of the `/pack` endpoint.
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PackQuery {
+    /// Serializes all dimensions and positions as `array` (default) or named `object`s.
+    #[serde(default)]
+    pub dim_format: DimFormat,
+    /// 1-based page over the placements of all containers; only used with `page_size`.
+    #[serde(default)]
+    #[param(minimum = 1)]
+    pub page: Option<usize>,
+    /// Paginates the placements: each container then only lists its share of the page.
+    #[serde(default)]
+    #[param(minimum = 1)]
+    pub page_size: Option<usize>,
+    /// Set to `false` to bypass the result cache for this request.
+    #[serde(default)]
+    pub cache: Option<bool>,
+    /// Adds all progress events of the run as an `events` array next to the result.
+    #[serde(default)]
+    pub events: bool,
+}
+
+/// Overall outcome of a pack request, distinguishing the two kinds of empty results.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PackStatus {
+    /// Objects were processed; see `is_complete` and `unplaced` for the details.
+    #[default]
+    Ok,
+    /// Nothing to pack: the request contained no objects (and no prior placements).
+    NoObjects,
+    /// Nowhere to pack: neither container types nor prior containers were available.
+    NoContainers,
 }
 
 /// Single container with metadata and placed objects.
@@ -260,16 +2019,55 @@ pub struct PackResponse {
 /// * `id` - Container number (1-based)
 /// * `total_weight` - Total weight of all objects in the container
 /// * `placed` - List of placed objects with positions
-#[derive(Serialize, ToSchema)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct PackedContainer {
     pub id: usize,
     pub template_id: Option<usize>,
+    /// Index of the container type in the submitted `containers` (followed by `template_names`
+    /// in request order), regardless of how the optimizer ordered the types internally.
+    #[serde(default)]
+    pub template_submission_index: Option<usize>,
     pub label: Option<String>,
-    #[schema(value_type = [f64; 3], example = json!([120.0, 100.0, 80.0]))]
+    /// Usable inner dimensions that objects are packed into.
+    #[serde(with = "dim_format::dims")]
+    #[schema(value_type = DimensionsValue, example = json!([120.0, 100.0, 80.0]))]
     pub dims: (f64, f64, f64),
+    /// Outer dimensions including walls; equal to `dims` without a wall thickness.
+    #[serde(default, with = "dim_format::dims")]
+    #[schema(value_type = DimensionsValue, example = json!([124.0, 104.0, 84.0]))]
+    pub outer_dims: (f64, f64, f64),
+    #[serde(default)]
+    pub wall_thickness: f64,
+    /// Reference point already added to every `pos` of this container.
+    #[serde(default, with = "dim_format::position")]
+    #[schema(value_type = PositionValue, example = json!([0.0, 0.0, 0.0]))]
+    pub origin: (f64, f64, f64),
     pub max_weight: f64,
+    /// Weight carried without occupying space; already included in `total_weight`.
+    #[serde(default)]
+    pub reserved_weight: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<[f64; 2]>)]
+    pub height_profile: Vec<(f64, f64)>,
+    /// `[x, y, width, depth]` rectangle where objects rest on the floor, in packing coordinates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<[f64; 4]>)]
+    pub floor_only_region: Option<(f64, f64, f64, f64)>,
+    /// Maximum number of objects this container may hold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    /// Price of using this container, if its type has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    #[serde(default)]
     pub total_weight: f64,
+    /// Tight `[min, max]` corners around all placed objects, in the coordinates of `pos`;
+    /// both corners are `[0, 0, 0]` for an empty container.
+    #[serde(default, with = "dim_format::envelope")]
+    #[schema(value_type = [PositionValue; 2], example = json!([[0.0, 0.0, 0.0], [60.0, 40.0, 30.0]]))]
+    pub occupied_envelope: ((f64, f64, f64), (f64, f64, f64)),
     pub placed: Vec<PackedObject>,
+    #[serde(default)]
     pub diagnostics: ContainerDiagnostics,
 }
 
@@ -280,24 +2078,106 @@ pub struct PackedContainer {
 /// * `pos` - Position (x, y, z) in the container
 /// * `weight` - Weight in kg
 /// * `dims` - Dimensions (width, depth, height)
-#[derive(Serialize, ToSchema)]
+/// * `category` / `color` - Visualization metadata copied from the request object
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 pub struct PackedObject {
     pub id: usize,
-    #[schema(value_type = [f64; 3], example = json!([0.0, 0.0, 0.0]))]
+    #[serde(with = "dim_format::position")]
+    #[schema(value_type = PositionValue, example = json!([0.0, 0.0, 0.0]))]
     pub pos: (f64, f64, f64),
     pub weight: f64,
-    #[schema(value_type = [f64; 3], example = json!([30.0, 40.0, 20.0]))]
+    #[serde(with = "dim_format::dims")]
+    #[schema(value_type = DimensionsValue, example = json!([30.0, 40.0, 20.0]))]
     pub dims: (f64, f64, f64),
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// All eight corners in container space; only present when the request set `include_corners`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Vec<[f64; 3]>>)]
+    pub corners: Option<[[f64; 3]; 8]>,
+    /// Input axis now lying along x, y and z (`"xyz"` = unrotated); only with `include_rotation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "yxz")]
+    pub rotation: Option<String>,
 }
 
-#[derive(Serialize, ToSchema)]
+/// Converts a placement, keeping its position in packing coordinates (without `origin`).
+impl From<&PlacedBox> for PackedObject {
+    fn from(placed: &PlacedBox) -> Self {
+        Self {
+            id: placed.object.id,
+            pos: placed.position,
+            weight: placed.object.weight,
+            dims: placed.object.dims,
+            category: placed.object.category.clone(),
+            color: placed.object.color.clone(),
+            corners: None,
+            rotation: None,
+        }
+    }
+}
+
+impl PackedObject {
+    /// Axis permutations as `(code, permuted dims)`, identity first.
+    const ROTATIONS: [(&'static str, [usize; 3]); 6] = [
+        ("xyz", [0, 1, 2]),
+        ("xzy", [0, 2, 1]),
+        ("yxz", [1, 0, 2]),
+        ("yzx", [1, 2, 0]),
+        ("zxy", [2, 0, 1]),
+        ("zyx", [2, 1, 0]),
+    ];
+
+    /// Returns the rotation code turning `original` dimensions into the placed `dims`.
+    ///
+    /// Each letter names the input axis that ended up along x, y and z. Symmetric objects match
+    /// several permutations; the first one wins, so identical dimensions report `"xyz"`. Returns
+    /// `None` if no permutation matches within `epsilon`.
+    pub fn rotation_from(&self, original: (f64, f64, f64), epsilon: f64) -> Option<&'static str> {
+        let original = [original.0, original.1, original.2];
+        let placed = [self.dims.0, self.dims.1, self.dims.2];
+        Self::ROTATIONS
+            .iter()
+            .find(|(_, axes)| {
+                axes.iter()
+                    .zip(placed)
+                    .all(|(&axis, len)| (original[axis] - len).abs() <= epsilon)
+            })
+            .map(|(code, _)| *code)
+    }
+
+    /// Returns the eight corners of the box derived from `pos` and `dims`.
+    ///
+    /// Corner `i` adds the width if bit 0 of `i` is set, the depth for bit 1 and the height for
+    /// bit 2, so index 0 is `pos` and index 7 the opposite corner.
+    pub fn corner_points(&self) -> [[f64; 3]; 8] {
+        let (x, y, z) = self.pos;
+        let (w, d, h) = self.dims;
+        std::array::from_fn(|i| {
+            let offset = |bit: usize, len: f64| if i & bit != 0 { len } else { 0.0 };
+            [x + offset(1, w), y + offset(2, d), z + offset(4, h)]
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct PackedUnplacedObject {
     pub id: usize,
     pub weight: f64,
-    #[schema(value_type = [f64; 3], example = json!([35.0, 45.0, 25.0]))]
+    #[serde(with = "dim_format::dims")]
+    #[schema(value_type = DimensionsValue, example = json!([35.0, 45.0, 25.0]))]
     pub dims: (f64, f64, f64),
     pub reason_code: String,
     pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Failed checks of the rejected candidate positions (only with `track_rejections`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejections: Option<RejectionCounts>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -323,14 +2203,93 @@ fn error_response(
     (status, Json(ErrorResponse::new(error, details))).into_response()
 }
 
-fn json_deserialize_error(err: JsonRejection) -> Response {
+fn json_deserialize_error(err: serde_json::Error) -> Response {
+    let details = match err.classify() {
+        serde_json::error::Category::Data => {
+            format!("Failed to deserialize the JSON body into the target type: {err}")
+        }
+        _ => format!("Failed to parse the request body as JSON: {err}"),
+    };
     error_response(
         StatusCode::UNPROCESSABLE_ENTITY,
         "Invalid JSON data",
-        err.to_string(),
+        details,
+    )
+}
+
+fn too_many_requests(retry_after: std::time::Duration) -> Response {
+    // Round up so clients never retry before a token is actually available.
+    let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let mut response = error_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        "Too many requests",
+        format!("Rate limit exceeded; retry after {seconds} second(s)"),
+    );
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, header::HeaderValue::from(seconds));
+    response
+}
+
+/// Resolves the client IP from the first `X-Forwarded-For` entry, falling back to the peer
+/// address of the connection.
+fn client_ip(request: &Request) -> IpAddr {
+    let forwarded = request
+        .headers()
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .and_then(|first| first.trim().parse::<IpAddr>().ok());
+
+    forwarded
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip())
+        })
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+}
+
+async fn rate_limit_middleware(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(limiter) = &state.rate_limiter
+        && let Err(retry_after) = limiter.check(client_ip(&request))
+    {
+        return too_many_requests(retry_after);
+    }
+    next.run(request).await
+}
+
+fn unsupported_media_type(content_type: Option<&str>) -> Response {
+    let details = match content_type {
+        Some(value) => format!("Expected `Content-Type: application/json`, got `{value}`"),
+        None => "Expected `Content-Type: application/json`, but the header is missing".to_string(),
+    };
+    error_response(
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "Unsupported media type",
+        details,
     )
 }
 
+/// Returns `true` for `application/json`, `text/json`, and `+json` structured syntax suffixes
+/// (e.g. `application/vnd.api+json`), ignoring parameters such as `charset`.
+fn is_json_content_type(value: &str) -> bool {
+    let essence = value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json"
+        || essence == "text/json"
+        || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
 fn validation_error(details: impl Into<String>) -> Response {
     error_response(
         StatusCode::UNPROCESSABLE_ENTITY,
@@ -347,17 +2306,25 @@ fn container_config_error(details: impl Into<String>) -> Response {
     )
 }
 
-/// Extracts a [`PackRequest`] from the request body, mapping deserialization failures to a 422.
+/// Extracts a JSON payload from the request body.
+///
+/// The `Content-Type` header is checked before deserialization is attempted: non-JSON media
+/// types yield a `415`, deserialization failures a `422`.
 ///
 /// The error variant is boxed because an axum [`Response`] is comparatively large; boxing keeps
 /// the common `Ok` path cheap to move around (see `clippy::result_large_err`).
-fn parse_json_body(
-    payload: Result<Json<PackRequest>, JsonRejection>,
-) -> Result<PackRequest, Box<Response>> {
-    match payload {
-        Ok(Json(payload)) => Ok(payload),
-        Err(err) => Err(Box::new(json_deserialize_error(err))),
+fn parse_json_body<T: DeserializeOwned>(
+    headers: &HeaderMap,
+    body: &Bytes,
+) -> Result<T, Box<Response>> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap_or("<non-ASCII value>"));
+    if !content_type.is_some_and(is_json_content_type) {
+        return Err(Box::new(unsupported_media_type(content_type)));
     }
+
+    serde_json::from_slice(body).map_err(|err| Box::new(json_deserialize_error(err)))
 }
 
 /// Maps a structured validation error to the appropriate HTTP error response.
@@ -369,58 +2336,205 @@ fn pack_validation_response(err: PackRequestValidationError) -> Response {
         }
         PackRequestValidationError::InvalidObject(ref inner) => validation_error(inner.to_string()),
         PackRequestValidationError::TooManyContainers { .. }
-        | PackRequestValidationError::TooManyObjects { .. } => validation_error(err.to_string()),
+        | PackRequestValidationError::TooManyObjects { .. }
+        | PackRequestValidationError::ObjectBelowMinimumDimension { .. }
+        | PackRequestValidationError::InvalidReservedSpace { .. }
+        | PackRequestValidationError::InvalidGapConstraint { .. }
+        | PackRequestValidationError::InvalidBundle { .. }
+        | PackRequestValidationError::UnknownTemplate { .. }
+        | PackRequestValidationError::UnknownTemplateReference { .. }
+        | PackRequestValidationError::InvalidPriorResult(_)
+        | PackRequestValidationError::DuplicateObjectId { .. } => validation_error(err.to_string()),
+        PackRequestValidationError::UnplacedInStrictMode { .. } => error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Incomplete pack",
+            err.to_string(),
+        ),
     }
 }
 
 impl PackResponse {
+    /// Creates a PackResponse and derives its [`PackStatus`].
+    ///
+    /// `has_containers` states whether any container type or prior container was available; the
+    /// packing result alone cannot tell "no containers" apart from "nothing fits".
+    pub fn from_packing_result_in_context(result: PackingResult, has_containers: bool) -> Self {
+        let mut response = Self::from_packing_result(result);
+        if !has_containers && !response.unplaced.is_empty() {
+            response.status = PackStatus::NoContainers;
+        }
+        response
+    }
+
+    /// Highest supported `round_decimals`; f64 cannot represent more significant decimals.
+    pub const MAX_ROUND_DECIMALS: u32 = 15;
+
+    /// Rounds all positions and dimensions to `decimals` decimal places.
+    ///
+    /// Placed objects are rounded by their corners: the start and end coordinate of each axis
+    /// are rounded and the dimension is derived from them. Rounding is monotonic, so objects that
+    /// touched or were apart before still do not overlap in the reported data.
+    pub fn rounded(mut self, decimals: u32) -> Self {
+        let factor = 10f64.powi(decimals.min(Self::MAX_ROUND_DECIMALS) as i32);
+        let round = |value: f64| (value * factor).round() / factor;
+        let round_dims = |dims: (f64, f64, f64)| (round(dims.0), round(dims.1), round(dims.2));
+        let round_span = |start: f64, len: f64| {
+            let rounded_start = round(start);
+            (rounded_start, round(round(start + len) - rounded_start))
+        };
+
+        for container in &mut self.results {
+            container.dims = round_dims(container.dims);
+            container.outer_dims = round_dims(container.outer_dims);
+            for object in &mut container.placed {
+                let (x, w) = round_span(object.pos.0, object.dims.0);
+                let (y, d) = round_span(object.pos.1, object.dims.1);
+                let (z, h) = round_span(object.pos.2, object.dims.2);
+                object.pos = (x, y, z);
+                object.dims = (w, d, h);
+            }
+            container.refresh_envelope();
+        }
+        for object in &mut self.unplaced {
+            object.dims = round_dims(object.dims);
+        }
+        if let Some(suggestion) = &mut self.suggested_container {
+            suggestion.dims = round_dims(suggestion.dims);
+        }
+        self
+    }
+
+    /// Sets [`Self::rotation_used`] if a placed object's `dims` differ from its input dimensions.
+    ///
+    /// Objects missing from `original_dims` (e.g. prior placements) are not considered.
+    fn detect_rotation(&mut self, original_dims: &HashMap<usize, (f64, f64, f64)>, epsilon: f64) {
+        let differs = |a: f64, b: f64| (a - b).abs() > epsilon;
+        self.rotation_used = self
+            .results
+            .iter()
+            .flat_map(|container| &container.placed)
+            .any(|object| {
+                original_dims.get(&object.id).is_some_and(|dims| {
+                    differs(dims.0, object.dims.0)
+                        || differs(dims.1, object.dims.1)
+                        || differs(dims.2, object.dims.2)
+                })
+            });
+    }
+
+    /// Fills in [`PackedObject::rotation`] from the input dimensions of each placed object.
+    ///
+    /// Objects missing from `original_dims` (e.g. prior placements) keep `None`.
+    fn annotate_rotations(
+        &mut self,
+        original_dims: &HashMap<usize, (f64, f64, f64)>,
+        epsilon: f64,
+    ) {
+        for object in self.results.iter_mut().flat_map(|c| c.placed.iter_mut()) {
+            object.rotation = original_dims
+                .get(&object.id)
+                .and_then(|&dims| object.rotation_from(dims, epsilon))
+                .map(str::to_string);
+        }
+    }
+
+    /// Fills in [`PackedObject::corners`] for every placed object.
+    ///
+    /// Apply this after [`Self::rounded`] so the corners match the reported positions.
+    pub fn with_corners(mut self) -> Self {
+        for object in self.results.iter_mut().flat_map(|c| c.placed.iter_mut()) {
+            object.corners = Some(object.corner_points());
+        }
+        self
+    }
+
+    /// Serializes the response, writing dimensions and positions in the requested `format`.
+    ///
+    /// [`DimFormat::Object`] writes every dimension field (`dims`, `outer_dims`) as
+    /// [`Dimensions`] and every position field (`pos`, `origin`, `occupied_envelope` corners) as
+    /// a [`Position`]; all other fields are unchanged.
+    pub fn to_json(&self, format: DimFormat) -> serde_json::Value {
+        serde_json::to_value(DimFormatted::new(self, format))
+            .expect("PackResponse serializes to JSON")
+    }
+
     /// Creates a PackResponse from a PackingResult (DRY principle).
     pub fn from_packing_result(result: PackingResult) -> Self {
+        let suggested_container = result.suggested_container();
         let PackingResult {
             containers,
             unplaced,
             container_diagnostics,
             diagnostics_summary,
+            timings,
         } = result;
 
         let is_complete = unplaced.is_empty();
+        let status = if containers.is_empty() && unplaced.is_empty() {
+            PackStatus::NoObjects
+        } else {
+            PackStatus::Ok
+        };
         let unplaced_entries = unplaced;
+        let total_cost = containers.iter().filter_map(|cont| cont.cost).sum();
 
         Self {
+            status,
             results: containers
                 .into_iter()
                 .zip(container_diagnostics)
                 .enumerate()
                 .map(|(i, (cont, diagnostics))| {
+                    let outer_dims = cont.outer_dims();
+                    let total_weight = cont.total_weight();
                     let Container {
                         dims,
                         max_weight,
                         placed,
                         template_id,
                         label,
+                        height_profile,
+                        wall_thickness,
+                        origin,
+                        reserved_weight,
+                        floor_only_region,
+                        max_items,
+                        cost,
+                        ..
                     } = cont;
 
-                    let total_weight = placed.iter().map(|b| b.object.weight).sum();
                     let placed_objects = placed
-                        .into_iter()
+                        .iter()
                         .map(|p| PackedObject {
-                            id: p.object.id,
-                            pos: p.position,
-                            weight: p.object.weight,
-                            dims: p.object.dims,
+                            pos: relative_to_origin(p.position, origin),
+                            ..PackedObject::from(p)
                         })
                         .collect();
 
-                    PackedContainer {
+                    let mut packed = PackedContainer {
                         id: i + 1,
+                        // Blueprint ids are assigned from the submission order and survive
+                        // every internal re-sort of the templates.
+                        template_submission_index: template_id,
                         template_id,
                         label,
                         dims,
+                        outer_dims,
+                        wall_thickness,
+                        origin,
                         max_weight,
+                        reserved_weight,
+                        height_profile,
+                        floor_only_region,
+                        max_items,
+                        cost,
                         total_weight,
+                        occupied_envelope: Default::default(),
                         placed: placed_objects,
                         diagnostics,
-                    }
+                    };
+                    packed.refresh_envelope();
+                    packed
                 })
                 .collect(),
             unplaced: unplaced_entries
@@ -431,11 +2545,107 @@ impl PackResponse {
                     dims: entry.object.dims,
                     reason_code: entry.reason.code().to_string(),
                     reason: entry.reason.to_string(),
+                    category: entry.object.category,
+                    color: entry.object.color,
+                    rejections: entry.rejections,
                 })
                 .collect(),
             is_complete,
             diagnostics_summary,
+            warnings: Vec::new(),
+            timings,
+            pagination: None,
+            rotation_used: false,
+            total_cost,
+            suggested_container,
+            events: Vec::new(),
+        }
+    }
+
+    /// Stores the events of the run in [`Self::events`], numbered and positioned like `results`.
+    ///
+    /// Container ids announced in creation order are mapped to the final order given by
+    /// [`PackEvent::ContainersReordered`], which is dropped, and positions are shifted by the
+    /// container's `origin`.
+    pub fn attach_events(&mut self, events: Vec<PackEvent>) {
+        let creation_ids = events.iter().find_map(|event| match event {
+            PackEvent::ContainersReordered { creation_ids } => Some(creation_ids.clone()),
+            _ => None,
+        });
+        let final_id = |id: usize| {
+            creation_ids
+                .as_ref()
+                .and_then(|ids| ids.iter().position(|&c| c == id))
+                .map_or(id, |idx| idx + 1)
+        };
+        let origin = |id: usize| {
+            self.results
+                .iter()
+                .find(|container| container.id == id)
+                .map_or((0.0, 0.0, 0.0), |container| container.origin)
+        };
+        self.events = events
+            .into_iter()
+            .filter_map(|mut event| {
+                match &mut event {
+                    PackEvent::ContainersReordered { .. } => return None,
+                    PackEvent::ContainerStarted { id, .. } => *id = final_id(*id),
+                    PackEvent::ObjectPlaced {
+                        container_id, pos, ..
+                    } => {
+                        *container_id = final_id(*container_id);
+                        *pos = relative_to_origin(*pos, origin(*container_id));
+                    }
+                    PackEvent::ContainerDiagnostics { container_id, .. } => {
+                        *container_id = final_id(*container_id);
+                    }
+                    PackEvent::Snapshot {
+                        container_id,
+                        placed,
+                    } => {
+                        *container_id = final_id(*container_id);
+                        let origin = origin(*container_id);
+                        for object in placed {
+                            object.pos = relative_to_origin(object.pos, origin);
+                        }
+                    }
+                    PackEvent::Progress { .. }
+                    | PackEvent::ObjectRejected { .. }
+                    | PackEvent::Finished { .. } => {}
+                }
+                Some(event)
+            })
+            .collect();
+    }
+
+    /// Keeps only the placements on `page` (1-based) of the placement list flattened across
+    /// containers, in container order.
+    ///
+    /// Containers, their diagnostics and the unplaced list are kept in full, so containers
+    /// outside the page appear with an empty `placed` list.
+    pub fn paginate(&mut self, page: usize, page_size: usize) {
+        let page = page.max(1);
+        let page_size = page_size.max(1);
+        let total_placements: usize = self.results.iter().map(|c| c.placed.len()).sum();
+        let start = (page - 1).saturating_mul(page_size);
+        let end = start.saturating_add(page_size);
+
+        let mut offset = 0;
+        for container in &mut self.results {
+            let len = container.placed.len();
+            let from = start.clamp(offset, offset + len) - offset;
+            let to = end.clamp(offset, offset + len) - offset;
+            container.placed.truncate(to);
+            container.placed.drain(..from);
+            offset += len;
         }
+
+        self.pagination = Some(PageInfo {
+            page,
+            page_size,
+            total_placements,
+            total_pages: total_placements.div_ceil(page_size),
+        });
     }
 }
 
@@ -473,6 +2683,28 @@ impl VersionResponse {
     }
 }
 
+/// Container templates of the server-side library, usable via `template_names`.
+#[derive(Serialize, ToSchema)]
+pub struct TemplateListResponse {
+    /// Templates in load order (sorted by file name).
+    pub templates: Vec<ContainerRequest>,
+}
+
+/// Static web assets embedded into the binary.
+#[derive(Serialize, ToSchema)]
+pub struct AssetListResponse {
+    /// Embedded file paths, sorted alphabetically (e.g. `index.html`).
+    pub assets: Vec<String>,
+}
+
+impl AssetListResponse {
+    fn embedded() -> Self {
+        let mut assets: Vec<String> = WebAssets::iter().map(|path| path.into_owned()).collect();
+        assets.sort();
+        Self { assets }
+    }
+}
+
 /// The active server-side packing configuration and request guardrails.
 ///
 /// Lets clients introspect the defaults that apply when a request omits `allow_rotations`, and the
@@ -484,10 +2716,52 @@ pub struct ConfigResponse {
     pub height_epsilon: f64,
     pub general_epsilon: f64,
     pub balance_limit_ratio: f64,
+    #[schema(nullable = true)]
+    pub balance_limit_ratio_x: Option<f64>,
+    #[schema(nullable = true)]
+    pub balance_limit_ratio_y: Option<f64>,
     pub footprint_cluster_tolerance: f64,
+    pub cluster_footprints: bool,
     pub allow_item_rotation: bool,
+    pub max_axis_positions: usize,
+    pub strict_layering: bool,
+    pub primary_objective: PrimaryObjective,
+    pub mass_placement: MassPlacement,
+    pub load_direction: LoadDirection,
+    #[schema(nullable = true)]
+    pub max_overhang: Option<f64>,
+    pub restarts: usize,
+    pub template_merge_tolerance: f64,
+    pub relative_tolerance: bool,
+    #[schema(nullable = true)]
+    pub floor_min_coverage: Option<f64>,
+    pub container_order: ContainerOrder,
+    pub template_preference: TemplatePreference,
+    pub new_container_policy: NewContainerPolicy,
+    #[schema(nullable = true)]
+    pub max_object_volume_fraction: Option<f64>,
+    pub top_clearance: f64,
+    pub allow_equal_weight_stacking: bool,
+    pub strict_weight: bool,
+    #[schema(nullable = true)]
+    pub target_utilization: Option<f64>,
+    pub support_samples_per_axis: usize,
+    pub compact: bool,
+    pub settle: bool,
+    pub cluster_by_category: bool,
+    pub prefer_wall_contact: bool,
+    pub prefer_floor_fallback: bool,
+    pub prefer_column_stacking: bool,
+    pub track_rejections: bool,
+    pub diagnostics_interval: usize,
+    pub snapshot_every: Option<usize>,
+    pub progress_every: Option<usize>,
+    pub progress_interval_ms: Option<u64>,
     pub max_objects: usize,
     pub max_containers: usize,
+    pub min_object_dimension: f64,
+    pub min_container_volume: f64,
+    pub max_container_aspect_ratio: f64,
 }
 
 impl ConfigResponse {
@@ -498,10 +2772,46 @@ impl ConfigResponse {
             height_epsilon: config.height_epsilon,
             general_epsilon: config.general_epsilon,
             balance_limit_ratio: config.balance_limit_ratio,
+            balance_limit_ratio_x: config.balance_limit_ratio_x,
+            balance_limit_ratio_y: config.balance_limit_ratio_y,
             footprint_cluster_tolerance: config.footprint_cluster_tolerance,
+            cluster_footprints: config.cluster_footprints,
             allow_item_rotation: config.allow_item_rotation,
+            max_axis_positions: config.max_axis_positions,
+            strict_layering: config.strict_layering,
+            primary_objective: config.primary_objective,
+            mass_placement: config.mass_placement,
+            load_direction: config.load_direction,
+            max_overhang: config.max_overhang,
+            restarts: config.restarts,
+            template_merge_tolerance: config.template_merge_tolerance,
+            relative_tolerance: config.relative_tolerance,
+            floor_min_coverage: config.floor_min_coverage,
+            container_order: config.container_order,
+            template_preference: config.template_preference,
+            new_container_policy: config.new_container_policy,
+            max_object_volume_fraction: config.max_object_volume_fraction,
+            top_clearance: config.top_clearance,
+            allow_equal_weight_stacking: config.allow_equal_weight_stacking,
+            strict_weight: config.strict_weight,
+            target_utilization: config.target_utilization,
+            support_samples_per_axis: config.support_samples_per_axis,
+            compact: config.compact,
+            settle: config.settle,
+            cluster_by_category: config.cluster_by_category,
+            prefer_wall_contact: config.prefer_wall_contact,
+            prefer_floor_fallback: config.prefer_floor_fallback,
+            prefer_column_stacking: config.prefer_column_stacking,
+            track_rejections: config.track_rejections,
+            diagnostics_interval: config.diagnostics_interval,
+            snapshot_every: config.snapshot_every,
+            progress_every: config.progress_every,
+            progress_interval_ms: config.progress_interval_ms,
             max_objects: limits.max_objects(),
             max_containers: limits.max_containers(),
+            min_object_dimension: limits.min_object_dimension(),
+            min_container_volume: limits.min_container_volume(),
+            max_container_aspect_ratio: limits.max_container_aspect_ratio(),
         }
     }
 }
@@ -511,26 +2821,60 @@ impl ConfigResponse {
     paths(
         handle_pack,
         handle_pack_stream,
+        handle_pack_ws,
+        handle_rediagnose,
+        handle_fits,
         handle_health,
         handle_version,
-        handle_config
+        handle_config,
+        handle_templates,
+        handle_assets,
+        handle_example
     ),
     components(
         schemas(
             PackRequest,
             ContainerRequest,
             PackResponse,
+            PackStatus,
             PackedContainer,
             PackedObject,
             PackedUnplacedObject,
+            ReservedSpaceRequest,
+            GapConstraintRequest,
+            BundleRequest,
+            ObjectGap,
+            WeightUnit,
+            PrimaryObjective,
+            MassPlacement,
+            LoadDirection,
+            ContainerOrder,
+            TemplatePreference,
+            NewContainerPolicy,
+            PackingProfile,
+            PhaseTimings,
+            PageInfo,
+            DimFormat,
+            Dimensions,
+            Position,
+            DimensionsValue,
+            PositionValue,
+            RediagnoseResponse,
+            RediagnosedContainer,
+            FitsRequest,
+            FitsResponse,
             ErrorResponse,
             HealthResponse,
             VersionResponse,
+            AssetListResponse,
+            TemplateListResponse,
             ConfigResponse,
             Box3D,
             ContainerDiagnostics,
             SupportDiagnostics,
             PackingDiagnosticsSummary,
+            RejectionCounts,
+            ContainerSpec,
             PackagingFill,
             PackagingSummary
         )
@@ -542,11 +2886,54 @@ impl ConfigResponse {
 )]
 struct ApiDoc;
 
-/// Builds the fully configured Axum [`Router`] for the service.
+/// Builds the fully configured Axum [`Router`] for the service.
+///
+/// Exposed so that integration tests (and embedders) can exercise the complete routing and
+/// handler stack without binding a TCP socket.
+pub fn build_router(optimizer_config: OptimizerConfig, limits: RequestLimits) -> Router {
+    build_router_with_rate_limit(optimizer_config, limits, 0)
+}
+
+/// Builds the router with per-client rate limiting on the POST packing endpoints.
+///
+/// `requests_per_minute` is the token-bucket capacity per client IP; `0` disables the limiter.
+/// Clients are identified by the first `X-Forwarded-For` entry or the connection's peer address.
+pub fn build_router_with_rate_limit(
+    optimizer_config: OptimizerConfig,
+    limits: RequestLimits,
+    requests_per_minute: usize,
+) -> Router {
+    build_router_with_templates(
+        optimizer_config,
+        limits,
+        requests_per_minute,
+        TemplateLibrary::default(),
+    )
+}
+
+/// Builds the router with rate limiting and a server-side container template library.
 ///
-/// Exposed so that integration tests (and embedders) can exercise the complete routing and
-/// handler stack without binding a TCP socket.
-pub fn build_router(optimizer_config: OptimizerConfig, limits: RequestLimits) -> Router {
+/// Templates are listed by `GET /templates` and can be referenced in pack requests by name.
+pub fn build_router_with_templates(
+    optimizer_config: OptimizerConfig,
+    limits: RequestLimits,
+    requests_per_minute: usize,
+    templates: TemplateLibrary,
+) -> Router {
+    build_router_with_cache(optimizer_config, limits, requests_per_minute, templates, 0)
+}
+
+/// Builds the router with rate limiting, a template library, and a `/pack` result cache.
+///
+/// Up to `cache_size` responses are kept and served again for identical requests; `0` disables
+/// the cache.
+pub fn build_router_with_cache(
+    optimizer_config: OptimizerConfig,
+    limits: RequestLimits,
+    requests_per_minute: usize,
+    templates: TemplateLibrary,
+    cache_size: usize,
+) -> Router {
     let cors = CorsLayer::new()
         .allow_methods(Any)
         .allow_origin(Any)
@@ -555,18 +2942,32 @@ pub fn build_router(optimizer_config: OptimizerConfig, limits: RequestLimits) ->
     let state = ApiState {
         optimizer_config,
         limits,
+        rate_limiter: RateLimiter::per_minute(requests_per_minute).map(Arc::new),
+        templates: Arc::new(templates),
+        result_cache: ResultCache::with_capacity(cache_size).map(Arc::new),
     };
 
     Router::new()
         // API endpoints
         .route("/pack", post(handle_pack))
         .route("/pack_stream", post(handle_pack_stream))
+        .route("/pack_ws", get(handle_pack_ws))
+        .route("/rediagnose", post(handle_rediagnose))
+        .route("/fits", post(handle_fits))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
         // System endpoints
         .route("/health", get(handle_health))
         .route("/version", get(handle_version))
+        .route("/assets", get(handle_assets))
+        .route("/example", get(handle_example))
         .route("/config", get(handle_config))
+        .route("/templates", get(handle_templates))
         // API documentation
         .route("/docs/openapi.json", get(serve_openapi_json))
+        .route("/docs/schema/pack_request", get(serve_pack_request_schema))
         .route("/docs", get(serve_openapi_ui))
         // Web-UI (embedded)
         .route("/", get(serve_index))
@@ -580,7 +2981,25 @@ pub fn build_router(optimizer_config: OptimizerConfig, limits: RequestLimits) ->
 /// Configures CORS for cross-origin requests from the frontend.
 /// Blocks until the server is terminated.
 pub async fn start_api_server(config: ApiConfig, optimizer_config: OptimizerConfig) {
-    let app = build_router(optimizer_config, config.request_limits());
+    let templates = match config.templates_dir() {
+        Some(dir) => {
+            let library = TemplateLibrary::load_dir(dir);
+            println!(
+                "🗂️ Loaded {} container template(s) from {}",
+                library.len(),
+                dir.display()
+            );
+            library
+        }
+        None => TemplateLibrary::default(),
+    };
+    let app = build_router_with_cache(
+        optimizer_config,
+        config.request_limits(),
+        config.rate_limit_per_minute(),
+        templates,
+        config.result_cache_size(),
+    );
 
     let addr = config.socket_addr();
     let listener = match tokio::net::TcpListener::bind(addr).await {
@@ -602,15 +3021,33 @@ pub async fn start_api_server(config: ApiConfig, optimizer_config: OptimizerConf
     println!("📦 API Endpoints:");
     println!("   - POST /pack");
     println!("   - POST /pack_stream");
+    println!("   - POST /rediagnose");
+    println!("   - POST /fits");
     println!("   - GET /health");
     println!("   - GET /version");
     println!("   - GET /config");
+    println!("   - GET /templates");
+    println!("   - GET /example");
     println!("📑 Documentation:");
     println!("   - GET /docs");
     println!("   - GET /docs/openapi.json");
+    println!("   - GET /docs/schema/pack_request");
+    if config.rate_limit_per_minute() > 0 {
+        println!(
+            "🚦 Rate limit: {} POST requests per minute and client",
+            config.rate_limit_per_minute()
+        );
+    }
+    if config.result_cache_size() > 0 {
+        println!(
+            "🗃️ Result cache: up to {} responses",
+            config.result_cache_size()
+        );
+    }
     println!("🌐 Web-UI: http://{}:{}", display_host, config.port());
 
-    if let Err(err) = axum::serve(listener, app).await {
+    let service = app.into_make_service_with_connect_info::<SocketAddr>();
+    if let Err(err) = axum::serve(listener, service).await {
         eprintln!("❌ API server terminated with an error: {err}");
     }
 }
@@ -627,12 +3064,18 @@ pub async fn start_api_server(config: ApiConfig, optimizer_config: OptimizerConf
 #[utoipa::path(
     post,
     path = "/pack",
+    params(PackQuery),
     request_body = PackRequest,
     responses(
         (status = 200, description = "Successfully packed objects", body = PackResponse),
+        (
+            status = UNSUPPORTED_MEDIA_TYPE,
+            description = "Content-Type is not application/json",
+            body = ErrorResponse
+        ),
         (
             status = UNPROCESSABLE_ENTITY,
-            description = "Invalid request or container configuration",
+            description = "Invalid request, container configuration, or incomplete pack in strict mode",
             body = ErrorResponse
         )
     ),
@@ -640,9 +3083,11 @@ pub async fn start_api_server(config: ApiConfig, optimizer_config: OptimizerConf
 )]
 async fn handle_pack(
     State(state): State<ApiState>,
-    payload: Result<Json<PackRequest>, JsonRejection>,
+    Query(query): Query<PackQuery>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    let request = match parse_json_body(payload) {
+    let request = match parse_json_body::<PackRequest>(&headers, &body) {
         Ok(request) => request,
         Err(response) => return *response,
     };
@@ -653,23 +3098,149 @@ async fn handle_pack(
         request.containers.len()
     );
 
-    match run_pack(
+    if query.page == Some(0) || query.page_size == Some(0) {
+        return validation_error("page and page_size must be at least 1");
+    }
+
+    let cache = state
+        .result_cache
+        .as_ref()
+        .filter(|_| query.cache != Some(false));
+    let cache_key = cache.and_then(|_| {
+        ResultCache::key(
+            &body,
+            (query.dim_format, query.page, query.page_size, query.events),
+        )
+    });
+    if let (Some(cache), Some(key)) = (cache, cache_key.as_deref())
+        && let Some(cached) = cache.get(key)
+    {
+        return (StatusCode::OK, [(CACHE_HEADER, "hit")], Json(cached)).into_response();
+    }
+
+    let request = match request.with_library_templates(&state.templates) {
+        Ok(request) => request,
+        Err(err) => return pack_validation_response(err),
+    };
+
+    let mut events = Vec::new();
+    let on_event = |event: &PackEvent| {
+        if query.events {
+            events.push(event.clone());
+        }
+    };
+    match run_pack_with_events(
         request,
         state.optimizer_config.packing_config(),
         state.limits,
+        on_event,
     ) {
-        Ok(response) => {
+        Ok(mut response) => {
             println!(
                 "📦 Result: {} containers, {} unpacked objects",
                 response.results.len(),
                 response.unplaced.len()
             );
-            (StatusCode::OK, Json(response)).into_response()
+            if let Some(page_size) = query.page_size {
+                response.paginate(query.page.unwrap_or(1), page_size);
+            }
+            response.attach_events(events);
+            let body = response.to_json(query.dim_format);
+            if let (Some(cache), Some(key)) = (cache, cache_key) {
+                cache.insert(key, body.clone());
+                return (StatusCode::OK, [(CACHE_HEADER, "miss")], Json(body)).into_response();
+            }
+            (StatusCode::OK, Json(body)).into_response()
         }
         Err(err) => pack_validation_response(err),
     }
 }
 
+/// Handler for POST /rediagnose endpoint.
+///
+/// Accepts a previously exported pack result (optionally with edited positions), validates its
+/// placements, and returns freshly computed diagnostics.
+#[utoipa::path(
+    post,
+    path = "/rediagnose",
+    request_body = PackResponse,
+    responses(
+        (status = 200, description = "Recomputed diagnostics", body = RediagnoseResponse),
+        (
+            status = UNSUPPORTED_MEDIA_TYPE,
+            description = "Content-Type is not application/json",
+            body = ErrorResponse
+        ),
+        (
+            status = UNPROCESSABLE_ENTITY,
+            description = "Invalid payload, out-of-bounds or overlapping placements",
+            body = ErrorResponse
+        )
+    ),
+    tag = "packing"
+)]
+async fn handle_rediagnose(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let result = match parse_json_body::<PackResponse>(&headers, &body) {
+        Ok(result) => result,
+        Err(response) => return *response,
+    };
+
+    match rediagnose(
+        result,
+        state.optimizer_config.packing_config(),
+        state.limits,
+    ) {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(err) => error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Invalid placement",
+            err.to_string(),
+        ),
+    }
+}
+
+/// Handler for POST /fits endpoint.
+///
+/// Checks a single object against a single container type without running a full pack.
+#[utoipa::path(
+    post,
+    path = "/fits",
+    request_body = FitsRequest,
+    responses(
+        (status = 200, description = "Fit check result", body = FitsResponse),
+        (
+            status = UNSUPPORTED_MEDIA_TYPE,
+            description = "Content-Type is not application/json",
+            body = ErrorResponse
+        ),
+        (
+            status = UNPROCESSABLE_ENTITY,
+            description = "Invalid object or container configuration",
+            body = ErrorResponse
+        )
+    ),
+    tag = "packing"
+)]
+async fn handle_fits(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let request = match parse_json_body::<FitsRequest>(&headers, &body) {
+        Ok(request) => request,
+        Err(response) => return *response,
+    };
+
+    match check_fit(request, state.optimizer_config.packing_config()) {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(err) => pack_validation_response(err),
+    }
+}
+
 /// Handler for POST /pack_stream endpoint (SSE).
 ///
 /// Streams pack events in real-time as Server-Sent Events (text/event-stream).
@@ -685,6 +3256,11 @@ async fn handle_pack(
             content_type = "text/event-stream",
             body = String
         ),
+        (
+            status = UNSUPPORTED_MEDIA_TYPE,
+            description = "Content-Type is not application/json",
+            body = ErrorResponse
+        ),
         (
             status = UNPROCESSABLE_ENTITY,
             description = "Invalid request or container configuration",
@@ -695,46 +3271,161 @@ async fn handle_pack(
 )]
 async fn handle_pack_stream(
     State(state): State<ApiState>,
-    payload: Result<Json<PackRequest>, JsonRejection>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    let request = match parse_json_body(payload) {
+    let request = match parse_json_body::<PackRequest>(&headers, &body) {
         Ok(request) => request,
         Err(response) => return *response,
     };
 
-    let validated = match request.into_validated(state.limits) {
+    let validated = match request
+        .with_library_templates(&state.templates)
+        .and_then(|request| request.into_validated(state.limits))
+    {
         Ok(validated) => validated,
         Err(err) => return pack_validation_response(err),
     };
 
-    let (objects, container_blueprints, allow_rotations_override) = validated.into_parts();
+    let stream = ReceiverStream::new(spawn_pack_events(validated, &state))
+        .map(|msg| Ok::<_, std::convert::Infallible>(Event::default().data(msg)));
+    Sse::new(stream)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(std::time::Duration::from_secs(10))
+                .text("keep-alive"),
+        )
+        .into_response()
+}
+
+/// Message sent on `/pack_stream` and `/pack_ws` besides the pack events.
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum StreamNotice {
+    /// Non-fatal validation issue, sent before the first pack event.
+    Warning { message: String },
+}
 
-    let (tx, rx) = mpsc::channel::<String>(32);
+/// Packs a validated request on a blocking thread and forwards every event as JSON.
+///
+/// Shared by the SSE and WebSocket transports. The channel closes once packing has finished,
+/// i.e. right after the `Finished` event.
+fn spawn_pack_events(
+    mut validated: ValidatedPackRequest,
+    state: &ApiState,
+) -> mpsc::Receiver<String> {
+    let packing_config = validated.packing_config(state.optimizer_config.packing_config());
+    let warnings = std::mem::take(&mut validated.warnings);
+    let bundles = std::mem::take(&mut validated.bundles);
+    let (existing, objects, container_blueprints) = validated.into_parts();
 
-    let mut packing_config = state.optimizer_config.packing_config();
-    if let Some(allow_rotations) = allow_rotations_override {
-        packing_config.allow_item_rotation = allow_rotations;
-    }
+    let (tx, rx) = mpsc::channel::<String>(32);
 
     tokio::task::spawn_blocking(move || {
-        let _ = pack_objects_with_progress(objects, container_blueprints, packing_config, |evt| {
-            if let Ok(json) = serde_json::to_string(evt) {
-                // A send error means the receiver has closed the stream; remaining events
-                // are simply discarded on subsequent callback invocations.
+        for message in warnings {
+            if let Ok(json) = serde_json::to_string(&StreamNotice::Warning { message }) {
                 let _ = tx.blocking_send(json);
             }
-        });
+        }
+        let _ = pack_objects_incremental_with_progress(
+            existing,
+            objects,
+            container_blueprints,
+            packing_config,
+            expand_bundle_events(&bundles, |evt| {
+                if let Ok(json) = serde_json::to_string(evt) {
+                    // A send error means the receiver has closed the stream; remaining events
+                    // are simply discarded on subsequent callback invocations.
+                    let _ = tx.blocking_send(json);
+                }
+            }),
+        );
     });
 
-    let stream = ReceiverStream::new(rx)
-        .map(|msg| Ok::<_, std::convert::Infallible>(Event::default().data(msg)));
-    Sse::new(stream)
-        .keep_alive(
-            KeepAlive::new()
-                .interval(std::time::Duration::from_secs(10))
-                .text("keep-alive"),
+    rx
+}
+
+/// WebSocket handler: streams pack events for clients that cannot use SSE.
+///
+/// The first text message must contain the `PackRequest`. Afterwards every pack event is sent as
+/// a JSON text frame, and the socket is closed after `Finished`. Invalid requests are answered
+/// with a single `ErrorResponse` frame before closing.
+#[utoipa::path(
+    get,
+    path = "/pack_ws",
+    responses(
+        (
+            status = 101,
+            description = "WebSocket upgrade; send a PackRequest as the first text message to receive pack events"
         )
-        .into_response()
+    ),
+    tag = "packing"
+)]
+async fn handle_pack_ws(State(state): State<ApiState>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| stream_pack_over_websocket(socket, state))
+}
+
+async fn stream_pack_over_websocket(mut socket: WebSocket, state: ApiState) {
+    let request = loop {
+        match socket.recv().await {
+            Some(Ok(Message::Text(text))) => break serde_json::from_str::<PackRequest>(&text),
+            // Control frames before the request are handled by axum; wait for the payload.
+            Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+            Some(Ok(Message::Binary(_))) => {
+                send_ws_error(
+                    &mut socket,
+                    ErrorResponse::new(
+                        "Invalid JSON data",
+                        "Expected the PackRequest as a text message",
+                    ),
+                )
+                .await;
+                return;
+            }
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+        }
+    };
+
+    let validated = match request {
+        Ok(request) => request
+            .with_library_templates(&state.templates)
+            .and_then(|request| request.into_validated(state.limits)),
+        Err(err) => {
+            send_ws_error(
+                &mut socket,
+                ErrorResponse::new("Invalid JSON data", err.to_string()),
+            )
+            .await;
+            return;
+        }
+    };
+    let validated = match validated {
+        Ok(validated) => validated,
+        Err(err) => {
+            send_ws_error(
+                &mut socket,
+                ErrorResponse::new("Invalid input data", err.to_string()),
+            )
+            .await;
+            return;
+        }
+    };
+
+    let mut events = spawn_pack_events(validated, &state);
+    while let Some(event) = events.recv().await {
+        if socket.send(Message::Text(event.into())).await.is_err() {
+            // The client went away; dropping the receiver stops further events.
+            return;
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+async fn send_ws_error(socket: &mut WebSocket, error: ErrorResponse) {
+    if let Ok(json) = serde_json::to_string(&error) {
+        let _ = socket.send(Message::Text(json.into())).await;
+    }
+    let _ = socket.send(Message::Close(None)).await;
 }
 
 /// Serves the index.html main page
@@ -762,6 +3453,13 @@ async fn serve_openapi_json(State(_state): State<ApiState>) -> impl IntoResponse
     Json(openapi_doc())
 }
 
+async fn serve_pack_request_schema(State(_state): State<ApiState>) -> Response {
+    match standalone_json_schema("PackRequest") {
+        Some(schema) => Json(schema).into_response(),
+        None => (StatusCode::NOT_FOUND, "404 Not Found").into_response(),
+    }
+}
+
 async fn serve_openapi_ui(State(_state): State<ApiState>) -> impl IntoResponse {
     Html(SWAGGER_UI_HTML)
 }
@@ -792,6 +3490,33 @@ async fn handle_version() -> impl IntoResponse {
     (StatusCode::OK, Json(VersionResponse::current()))
 }
 
+/// Handler for GET /example.
+///
+/// Returns a valid example request body for `/pack` that clients can copy and adapt.
+#[utoipa::path(
+    get,
+    path = "/example",
+    responses((status = 200, description = "Example pack request", body = PackRequest)),
+    tag = "packing"
+)]
+async fn handle_example() -> impl IntoResponse {
+    (StatusCode::OK, Json(pack_request_example()))
+}
+
+/// Handler for GET /assets.
+///
+/// Lists the embedded web UI files so deployments can confirm the expected frontend was bundled.
+/// The files are served publicly anyway, so the list reveals nothing new.
+#[utoipa::path(
+    get,
+    path = "/assets",
+    responses((status = 200, description = "Embedded web UI assets", body = AssetListResponse)),
+    tag = "system"
+)]
+async fn handle_assets() -> impl IntoResponse {
+    (StatusCode::OK, Json(AssetListResponse::embedded()))
+}
+
 /// Handler for GET /config.
 ///
 /// Reports the active default packing configuration and the per-request guardrails so clients can
@@ -808,15 +3533,32 @@ async fn handle_config(State(state): State<ApiState>) -> impl IntoResponse {
     (StatusCode::OK, Json(response))
 }
 
+/// Handler for GET /templates.
+///
+/// Lists the container templates loaded from `SORT_IT_NOW_TEMPLATES_DIR`.
+#[utoipa::path(
+    get,
+    path = "/templates",
+    responses((status = 200, description = "Server-side container templates", body = TemplateListResponse)),
+    tag = "system"
+)]
+async fn handle_templates(State(state): State<ApiState>) -> impl IntoResponse {
+    let response = TemplateListResponse {
+        templates: state.templates.templates().to_vec(),
+    };
+    (StatusCode::OK, Json(response))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::optimizer::pack_objects_incremental;
 
     #[test]
     fn openapi_doc_lists_expected_paths() {
         let doc = openapi_doc();
         let paths = &doc.paths.paths;
-        for expected in ["/pack", "/pack_stream", "/health", "/version"] {
+        for expected in ["/pack", "/pack_stream", "/fits", "/health", "/version"] {
             assert!(
                 paths.contains_key(expected),
                 "OpenAPI documentation is missing the {expected} path"
@@ -864,6 +3606,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn openapi_doc_documents_both_dim_formats() {
+        let doc = serde_json::to_value(openapi_doc()).unwrap();
+        let schemas = &doc["components"]["schemas"];
+        assert_eq!(
+            schemas["PackedObject"]["properties"]["pos"]["$ref"],
+            "#/components/schemas/PositionValue"
+        );
+        assert_eq!(
+            schemas["PackedObject"]["properties"]["dims"]["$ref"],
+            "#/components/schemas/DimensionsValue"
+        );
+        for name in ["PositionValue", "DimensionsValue"] {
+            assert_eq!(
+                schemas[name]["oneOf"].as_array().map(Vec::len),
+                Some(2),
+                "{name} should offer the array and the object form"
+            );
+        }
+    }
+
+    #[test]
+    fn top_clearance_is_checked_against_the_lowest_profile_step() {
+        let request = |top_clearance: Option<f64>| -> PackRequest {
+            serde_json::from_value(serde_json::json!({
+                "containers": [{
+                    "dims": [20.0, 10.0, 30.0],
+                    "max_weight": 100.0,
+                    "height_profile": [[10.0, 12.0]]
+                }],
+                "objects": [{"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0}],
+                "top_clearance": top_clearance
+            }))
+            .unwrap()
+        };
+
+        let err = run_pack(
+            request(Some(15.0)),
+            PackingConfig::default(),
+            RequestLimits::default(),
+        )
+        .err()
+        .expect("clearance above the profile step");
+        assert!(
+            err.to_string().contains("lowest container ceiling 12"),
+            "{err}"
+        );
+
+        // A server-wide clearance is dropped with a warning instead of failing the request.
+        let base = PackingConfig::builder().top_clearance(15.0).build();
+        let response = run_pack(request(None), base, RequestLimits::default()).unwrap();
+        assert!(response.is_complete);
+        assert_eq!(response.warnings.len(), 1);
+        assert!(response.warnings[0].contains("SORT_IT_NOW_PACKING_TOP_CLEARANCE"));
+    }
+
+    #[test]
+    fn coarsened_grid_is_reported_as_a_warning() {
+        let request: PackRequest = serde_json::from_value(serde_json::json!({
+            "containers": [{"dims": [100.0, 10.0, 10.0], "max_weight": 100.0}],
+            "objects": [{"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0}]
+        }))
+        .unwrap();
+        let base = PackingConfig::builder()
+            .grid_step(1.0)
+            .max_axis_positions(12)
+            .build();
+
+        let response = run_pack(request, base, RequestLimits::default()).unwrap();
+        assert_eq!(response.warnings.len(), 1, "{:?}", response.warnings);
+        assert!(response.warnings[0].contains("SORT_IT_NOW_PACKING_MAX_AXIS_POSITIONS"));
+    }
+
+    #[test]
+    fn openapi_doc_documents_collected_events() {
+        let doc = serde_json::to_value(openapi_doc()).unwrap();
+        let events = &doc["components"]["schemas"]["PackResponse"]["properties"]["events"];
+        assert!(events.is_object(), "PackResponse should document `events`");
+    }
+
     #[test]
     fn pack_request_parses_allow_rotations_when_present_true() {
         let json = r#"{
@@ -907,6 +3729,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pack_status_distinguishes_empty_results() {
+        let templates = vec![ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 10.0).unwrap()];
+        let object = Box3D::new(1, (5.0, 5.0, 5.0), 1.0).unwrap();
+
+        let ok = PackResponse::from_packing_result_in_context(
+            pack_objects_incremental(
+                Vec::new(),
+                vec![object.clone()],
+                templates.clone(),
+                PackingConfig::default(),
+            ),
+            true,
+        );
+        assert_eq!(ok.status, PackStatus::Ok);
+
+        let no_objects = PackResponse::from_packing_result_in_context(
+            pack_objects_incremental(Vec::new(), Vec::new(), templates, PackingConfig::default()),
+            true,
+        );
+        assert_eq!(no_objects.status, PackStatus::NoObjects);
+        assert!(no_objects.is_complete);
+
+        let no_containers = PackResponse::from_packing_result_in_context(
+            pack_objects_incremental(
+                Vec::new(),
+                vec![object],
+                Vec::new(),
+                PackingConfig::default(),
+            ),
+            false,
+        );
+        assert_eq!(no_containers.status, PackStatus::NoContainers);
+        assert_eq!(no_containers.unplaced.len(), 1);
+    }
+
+    #[test]
+    fn rounding_keeps_adjacent_objects_apart() {
+        let object = |id: usize, x: f64, width: f64| PackedObject {
+            id,
+            pos: (x, 0.0, 0.0),
+            weight: 1.0,
+            dims: (width, 1.0, 1.0),
+            category: None,
+            color: None,
+            corners: None,
+            rotation: None,
+        };
+        // Rounded independently, 1.01 + 1.01 would end at 2.02 and overlap the neighbour at 2.01.
+        // Rounding the corners keeps both objects touching instead.
+        let response = PackResponse {
+            status: PackStatus::Ok,
+            results: vec![PackedContainer {
+                id: 1,
+                template_id: None,
+                template_submission_index: None,
+                label: None,
+                dims: (10.0, 10.0, 10.0),
+                outer_dims: (10.0, 10.0, 10.0),
+                wall_thickness: 0.0,
+                origin: (0.0, 0.0, 0.0),
+                max_weight: 10.0,
+                reserved_weight: 0.0,
+                height_profile: Vec::new(),
+                floor_only_region: None,
+                max_items: None,
+                cost: None,
+                total_weight: 2.0,
+                occupied_envelope: Default::default(),
+                placed: vec![object(1, 1.006, 1.006), object(2, 2.012, 1.0)],
+                diagnostics: ContainerDiagnostics::default(),
+            }],
+            unplaced: Vec::new(),
+            is_complete: true,
+            diagnostics_summary: PackingDiagnosticsSummary::default(),
+            warnings: Vec::new(),
+            timings: None,
+            pagination: None,
+            rotation_used: false,
+            total_cost: 0.0,
+            suggested_container: None,
+            events: Vec::new(),
+        }
+        .rounded(2);
+
+        let placed = &response.results[0].placed;
+        assert_eq!(placed[0].pos.0, 1.01);
+        assert_eq!(placed[0].dims.0, 1.0);
+        assert_eq!(placed[1].pos.0, 2.01);
+        assert!(placed[0].pos.0 + placed[0].dims.0 <= placed[1].pos.0 + 1e-12);
+    }
+
     #[test]
     fn pack_request_parses_allow_rotations_when_null() {
         let json = r#"{
@@ -928,13 +3842,16 @@ mod tests {
                 name: Some("Test".to_string()),
                 dims: (10.0, 10.0, 10.0),
                 max_weight: 100.0,
+                ..Default::default()
             }],
             objects: vec![Box3D {
                 id: 1,
                 dims: (5.0, 5.0, 5.0),
                 weight: 10.0,
+                ..Default::default()
             }],
             allow_rotations: Some(true),
+            ..Default::default()
         };
 
         let validated = request
@@ -947,6 +3864,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn packing_profile_sets_the_rotation_default_unless_overridden() {
+        let config =
+            |base: PackingConfig, profile: PackingProfile, allow_rotations: Option<bool>| {
+                PackRequest {
+                    containers: vec![ContainerRequest {
+                        dims: (10.0, 10.0, 10.0),
+                        max_weight: 100.0,
+                        ..Default::default()
+                    }],
+                    objects: vec![Box3D {
+                        id: 1,
+                        dims: (5.0, 5.0, 5.0),
+                        weight: 10.0,
+                        ..Default::default()
+                    }],
+                    packing_profile: Some(profile),
+                    allow_rotations,
+                    ..Default::default()
+                }
+                .into_validated(RequestLimits::default())
+                .expect("Should validate successfully")
+                .packing_config(base)
+            };
+        let rotating = PackingConfig::builder().allow_item_rotation(true).build();
+
+        assert!(
+            config(PackingConfig::default(), PackingProfile::Compact, None).allow_item_rotation
+        );
+        assert!(
+            !config(
+                PackingConfig::default(),
+                PackingProfile::Compact,
+                Some(false)
+            )
+            .allow_item_rotation
+        );
+        assert!(!config(rotating, PackingProfile::Conservative, None).allow_item_rotation);
+        assert!(config(rotating, PackingProfile::Conservative, Some(true)).allow_item_rotation);
+    }
+
     #[test]
     fn validation_rejects_too_many_objects() {
         let request = PackRequest {
@@ -954,20 +3912,24 @@ mod tests {
                 name: None,
                 dims: (10.0, 10.0, 10.0),
                 max_weight: 100.0,
+                ..Default::default()
             }],
             objects: vec![
                 Box3D {
                     id: 1,
                     dims: (5.0, 5.0, 5.0),
                     weight: 10.0,
+                    ..Default::default()
                 },
                 Box3D {
                     id: 2,
                     dims: (5.0, 5.0, 5.0),
                     weight: 10.0,
+                    ..Default::default()
                 },
             ],
             allow_rotations: None,
+            ..Default::default()
         };
 
         let limits = RequestLimits::with_limits(1, 10);
@@ -983,6 +3945,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn json_content_type_accepts_known_aliases() {
+        assert!(is_json_content_type("application/json"));
+        assert!(is_json_content_type("Application/JSON; charset=utf-8"));
+        assert!(is_json_content_type("text/json"));
+        assert!(is_json_content_type("application/vnd.api+json"));
+        assert!(!is_json_content_type("text/plain"));
+        assert!(!is_json_content_type("application/x-www-form-urlencoded"));
+    }
+
+    #[test]
+    fn validation_enforces_min_object_dimension() {
+        let request = |floor: Option<f64>| PackRequest {
+            containers: vec![ContainerRequest {
+                name: None,
+                dims: (10.0, 10.0, 10.0),
+                max_weight: 100.0,
+                ..Default::default()
+            }],
+            objects: vec![Box3D {
+                id: 7,
+                dims: (0.0001, 5.0, 5.0),
+                weight: 1.0,
+                ..Default::default()
+            }],
+            min_object_dimension: floor,
+            ..Default::default()
+        };
+
+        let err = request(Some(0.1))
+            .into_validated(RequestLimits::default())
+            .expect_err("an OCR-sized sliver should be rejected above the floor");
+        assert!(
+            matches!(
+                err,
+                PackRequestValidationError::ObjectBelowMinimumDimension { id: 7, .. }
+            ),
+            "unexpected error: {err}"
+        );
+
+        // The server-side floor applies when the request does not override it.
+        let limits = RequestLimits::default().with_min_object_dimension(0.1);
+        assert!(request(None).into_validated(limits).is_err());
+
+        assert!(request(Some(0.0)).into_validated(limits).is_ok());
+        assert!(
+            request(None)
+                .into_validated(RequestLimits::default())
+                .is_ok()
+        );
+    }
+
     #[test]
     fn config_response_reflects_packing_config_and_limits() {
         let config = PackingConfig::default();
@@ -1,7 +1,11 @@
 use std::env;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
 
-use crate::optimizer::PackingConfig;
+use crate::optimizer::{
+    ContainerOrder, LoadDirection, MassPlacement, NewContainerPolicy, PackingConfig,
+    PrimaryObjective, TemplatePreference,
+};
 
 /// Complete application configuration, loaded from environment variables or default values.
 #[derive(Clone, Debug)]
@@ -26,17 +30,26 @@ impl AppConfig {
 ///
 /// These guardrails protect the service from pathological or accidental oversized payloads. A
 /// limit of `0` disables the corresponding check (treated as unlimited).
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct RequestLimits {
     max_objects: usize,
     max_containers: usize,
+    min_object_dimension: f64,
+    min_container_volume: f64,
+    max_container_aspect_ratio: f64,
 }
 
 impl RequestLimits {
     pub const DEFAULT_MAX_OBJECTS: usize = 10_000;
     pub const DEFAULT_MAX_CONTAINERS: usize = 1_000;
+    pub const DEFAULT_MIN_OBJECT_DIMENSION: f64 = 0.0;
+    pub const DEFAULT_MIN_CONTAINER_VOLUME: f64 = 0.0;
+    pub const DEFAULT_MAX_CONTAINER_ASPECT_RATIO: f64 = 0.0;
     const MAX_OBJECTS_VAR: &'static str = "SORT_IT_NOW_MAX_OBJECTS";
     const MAX_CONTAINERS_VAR: &'static str = "SORT_IT_NOW_MAX_CONTAINERS";
+    const MIN_OBJECT_DIMENSION_VAR: &'static str = "SORT_IT_NOW_MIN_OBJECT_DIMENSION";
+    const MIN_CONTAINER_VOLUME_VAR: &'static str = "SORT_IT_NOW_MIN_CONTAINER_VOLUME";
+    const MAX_CONTAINER_ASPECT_RATIO_VAR: &'static str = "SORT_IT_NOW_MAX_CONTAINER_ASPECT_RATIO";
 
     fn from_env() -> Self {
         Self {
@@ -45,6 +58,27 @@ impl RequestLimits {
                 Self::MAX_CONTAINERS_VAR,
                 Self::DEFAULT_MAX_CONTAINERS,
             ),
+            min_object_dimension: load_f64_with_warning(
+                Self::MIN_OBJECT_DIMENSION_VAR,
+                Self::DEFAULT_MIN_OBJECT_DIMENSION,
+                |value| value.is_finite() && value >= 0.0,
+                "must be a non-negative number",
+                "Minimum object dimension enabled; smaller objects will be rejected",
+            ),
+            min_container_volume: load_f64_with_warning(
+                Self::MIN_CONTAINER_VOLUME_VAR,
+                Self::DEFAULT_MIN_CONTAINER_VOLUME,
+                |value| value.is_finite() && value >= 0.0,
+                "must be a non-negative number",
+                "Minimum container volume enabled; smaller containers will be rejected",
+            ),
+            max_container_aspect_ratio: load_f64_with_warning(
+                Self::MAX_CONTAINER_ASPECT_RATIO_VAR,
+                Self::DEFAULT_MAX_CONTAINER_ASPECT_RATIO,
+                |value| value.is_finite() && value >= 0.0,
+                "must be a non-negative number",
+                "Maximum container aspect ratio enabled; more elongated containers will be rejected",
+            ),
         }
     }
 
@@ -53,9 +87,68 @@ impl RequestLimits {
         Self {
             max_objects,
             max_containers,
+            ..Self::default()
         }
     }
 
+    /// Sets the smallest accepted object dimension (`0` disables the check).
+    ///
+    /// Negative or non-finite values are treated as `0`.
+    pub fn with_min_object_dimension(mut self, min: f64) -> Self {
+        self.min_object_dimension = if min.is_finite() && min > 0.0 {
+            min
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// Smallest accepted object dimension (`0` = disabled).
+    pub fn min_object_dimension(&self) -> f64 {
+        self.min_object_dimension
+    }
+
+    /// Sets the smallest accepted usable container volume (`0` disables the check).
+    ///
+    /// Negative or non-finite values are treated as `0`.
+    pub fn with_min_container_volume(mut self, min: f64) -> Self {
+        self.min_container_volume = if min.is_finite() && min > 0.0 {
+            min
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// Smallest accepted usable container volume (`0` = disabled).
+    pub fn min_container_volume(&self) -> f64 {
+        self.min_container_volume
+    }
+
+    /// Sets the largest accepted ratio of a container's longest to shortest dimension
+    /// (`0` disables the check).
+    ///
+    /// Negative or non-finite values are treated as `0`.
+    pub fn with_max_container_aspect_ratio(mut self, max: f64) -> Self {
+        self.max_container_aspect_ratio = if max.is_finite() && max > 0.0 {
+            max
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// Largest accepted container aspect ratio (`0` = disabled).
+    pub fn max_container_aspect_ratio(&self) -> f64 {
+        self.max_container_aspect_ratio
+    }
+
+    /// Returns `true` if every dimension reaches the configured minimum.
+    pub fn allows_object_dims(&self, dims: (f64, f64, f64)) -> bool {
+        let min = self.min_object_dimension;
+        min <= 0.0 || (dims.0 >= min && dims.1 >= min && dims.2 >= min)
+    }
+
     /// Maximum number of objects accepted in a request (`0` = unlimited).
     pub fn max_objects(&self) -> usize {
         self.max_objects
@@ -82,6 +175,9 @@ impl Default for RequestLimits {
         Self {
             max_objects: Self::DEFAULT_MAX_OBJECTS,
             max_containers: Self::DEFAULT_MAX_CONTAINERS,
+            min_object_dimension: Self::DEFAULT_MIN_OBJECT_DIMENSION,
+            min_container_volume: Self::DEFAULT_MIN_CONTAINER_VOLUME,
+            max_container_aspect_ratio: Self::DEFAULT_MAX_CONTAINER_ASPECT_RATIO,
         }
     }
 }
@@ -93,11 +189,16 @@ pub struct ApiConfig {
     display_host: String,
     port: u16,
     limits: RequestLimits,
+    rate_limit_per_minute: usize,
+    templates_dir: Option<PathBuf>,
+    result_cache_size: usize,
 }
 
 impl ApiConfig {
     const DEFAULT_HOST: &'static str = "0.0.0.0";
     const DEFAULT_PORT: u16 = 8080;
+    const DEFAULT_RATE_LIMIT_PER_MINUTE: usize = 0;
+    const DEFAULT_RESULT_CACHE_SIZE: usize = 0;
 
     fn from_env() -> Self {
         let host_value =
@@ -148,6 +249,15 @@ impl ApiConfig {
             display_host: effective_host,
             port,
             limits: RequestLimits::from_env(),
+            rate_limit_per_minute: load_usize_with_warning(
+                "SORT_IT_NOW_RATE_LIMIT_PER_MINUTE",
+                Self::DEFAULT_RATE_LIMIT_PER_MINUTE,
+            ),
+            templates_dir: env_string("SORT_IT_NOW_TEMPLATES_DIR").map(PathBuf::from),
+            result_cache_size: load_usize_with_warning(
+                "SORT_IT_NOW_RESULT_CACHE_SIZE",
+                Self::DEFAULT_RESULT_CACHE_SIZE,
+            ),
         }
     }
 
@@ -161,6 +271,21 @@ impl ApiConfig {
         self.limits
     }
 
+    /// Allowed POST requests per minute and client IP (`0` disables rate limiting).
+    pub fn rate_limit_per_minute(&self) -> usize {
+        self.rate_limit_per_minute
+    }
+
+    /// Directory scanned for `*.json` container templates at startup, if configured.
+    pub fn templates_dir(&self) -> Option<&Path> {
+        self.templates_dir.as_deref()
+    }
+
+    /// Number of `/pack` responses kept in the result cache (`0` disables caching).
+    pub fn result_cache_size(&self) -> usize {
+        self.result_cache_size
+    }
+
     /// Visible hostname for logging and hints.
     pub fn display_host(&self) -> &str {
         &self.display_host
@@ -237,8 +362,43 @@ impl OptimizerConfig {
     const HEIGHT_EPSILON_VAR: &'static str = "SORT_IT_NOW_PACKING_HEIGHT_EPSILON";
     const GENERAL_EPSILON_VAR: &'static str = "SORT_IT_NOW_PACKING_GENERAL_EPSILON";
     const BALANCE_RATIO_VAR: &'static str = "SORT_IT_NOW_PACKING_BALANCE_LIMIT_RATIO";
+    const BALANCE_RATIO_X_VAR: &'static str = "SORT_IT_NOW_PACKING_BALANCE_LIMIT_RATIO_X";
+    const BALANCE_RATIO_Y_VAR: &'static str = "SORT_IT_NOW_PACKING_BALANCE_LIMIT_RATIO_Y";
     const FOOTPRINT_TOLERANCE_VAR: &'static str = "SORT_IT_NOW_PACKING_FOOTPRINT_TOLERANCE";
     const ALLOW_ROTATION_VAR: &'static str = "SORT_IT_NOW_PACKING_ALLOW_ROTATIONS";
+    const MAX_AXIS_POSITIONS_VAR: &'static str = "SORT_IT_NOW_PACKING_MAX_AXIS_POSITIONS";
+    const STRICT_LAYERING_VAR: &'static str = "SORT_IT_NOW_PACKING_STRICT_LAYERING";
+    const PRIMARY_OBJECTIVE_VAR: &'static str = "SORT_IT_NOW_PACKING_PRIMARY_OBJECTIVE";
+    const MASS_PLACEMENT_VAR: &'static str = "SORT_IT_NOW_PACKING_MASS_PLACEMENT";
+    const LOAD_DIRECTION_VAR: &'static str = "SORT_IT_NOW_PACKING_LOAD_DIRECTION";
+    const RESTARTS_VAR: &'static str = "SORT_IT_NOW_PACKING_RESTARTS";
+    const TEMPLATE_MERGE_TOLERANCE_VAR: &'static str =
+        "SORT_IT_NOW_PACKING_TEMPLATE_MERGE_TOLERANCE";
+    const RELATIVE_TOLERANCE_VAR: &'static str = "SORT_IT_NOW_PACKING_RELATIVE_TOLERANCE";
+    const FLOOR_MIN_COVERAGE_VAR: &'static str = "SORT_IT_NOW_PACKING_FLOOR_MIN_COVERAGE";
+    const CONTAINER_ORDER_VAR: &'static str = "SORT_IT_NOW_PACKING_CONTAINER_ORDER";
+    const TEMPLATE_PREFERENCE_VAR: &'static str = "SORT_IT_NOW_PACKING_TEMPLATE_PREFERENCE";
+    const NEW_CONTAINER_POLICY_VAR: &'static str = "SORT_IT_NOW_PACKING_NEW_CONTAINER_POLICY";
+    const TOP_CLEARANCE_VAR: &'static str = "SORT_IT_NOW_PACKING_TOP_CLEARANCE";
+    const MAX_OBJECT_VOLUME_FRACTION_VAR: &'static str =
+        "SORT_IT_NOW_PACKING_MAX_OBJECT_VOLUME_FRACTION";
+    const ALLOW_EQUAL_WEIGHT_STACKING_VAR: &'static str =
+        "SORT_IT_NOW_PACKING_ALLOW_EQUAL_WEIGHT_STACKING";
+    const STRICT_WEIGHT_VAR: &'static str = "SORT_IT_NOW_PACKING_STRICT_WEIGHT";
+    const TARGET_UTILIZATION_VAR: &'static str = "SORT_IT_NOW_PACKING_TARGET_UTILIZATION";
+    const SUPPORT_SAMPLES_VAR: &'static str = "SORT_IT_NOW_PACKING_SUPPORT_SAMPLES";
+    const COMPACT_VAR: &'static str = "SORT_IT_NOW_PACKING_COMPACT";
+    const SETTLE_VAR: &'static str = "SORT_IT_NOW_PACKING_SETTLE";
+    const CLUSTER_BY_CATEGORY_VAR: &'static str = "SORT_IT_NOW_PACKING_CLUSTER_BY_CATEGORY";
+    const PREFER_WALL_CONTACT_VAR: &'static str = "SORT_IT_NOW_PACKING_PREFER_WALL_CONTACT";
+    const PREFER_FLOOR_FALLBACK_VAR: &'static str = "SORT_IT_NOW_PACKING_PREFER_FLOOR_FALLBACK";
+    const PREFER_COLUMN_STACKING_VAR: &'static str = "SORT_IT_NOW_PACKING_PREFER_COLUMN_STACKING";
+    const TRACK_REJECTIONS_VAR: &'static str = "SORT_IT_NOW_PACKING_TRACK_REJECTIONS";
+    const CLUSTER_FOOTPRINTS_VAR: &'static str = "SORT_IT_NOW_PACKING_CLUSTER_FOOTPRINTS";
+    const DIAGNOSTICS_INTERVAL_VAR: &'static str = "SORT_IT_NOW_PACKING_DIAGNOSTICS_INTERVAL";
+    const SNAPSHOT_EVERY_VAR: &'static str = "SORT_IT_NOW_PACKING_SNAPSHOT_EVERY";
+    const PROGRESS_EVERY_VAR: &'static str = "SORT_IT_NOW_PACKING_PROGRESS_EVERY";
+    const PROGRESS_INTERVAL_MS_VAR: &'static str = "SORT_IT_NOW_PACKING_PROGRESS_INTERVAL_MS";
 
     fn from_env() -> Self {
         let grid_step = load_f64_with_warning(
@@ -281,6 +441,22 @@ impl OptimizerConfig {
             "Warning: Adjusted balance limits may cause stacks to tip over",
         );
 
+        // 0 keeps the combined limit for that axis.
+        let balance_limit_ratio_x = load_f64_with_warning(
+            Self::BALANCE_RATIO_X_VAR,
+            0.0,
+            |value| (0.0..=1.0).contains(&value),
+            "must be between 0 and 1",
+            "Per-axis balance limits enabled; side-to-side offset is checked separately",
+        );
+        let balance_limit_ratio_y = load_f64_with_warning(
+            Self::BALANCE_RATIO_Y_VAR,
+            0.0,
+            |value| (0.0..=1.0).contains(&value),
+            "must be between 0 and 1",
+            "Per-axis balance limits enabled; front-to-back offset is checked separately",
+        );
+
         let footprint_cluster_tolerance = load_f64_with_warning(
             Self::FOOTPRINT_TOLERANCE_VAR,
             PackingConfig::DEFAULT_FOOTPRINT_CLUSTER_TOLERANCE,
@@ -294,14 +470,175 @@ impl OptimizerConfig {
             .and_then(|raw| parse_bool(&raw, Self::ALLOW_ROTATION_VAR))
             .unwrap_or(PackingConfig::DEFAULT_ALLOW_ITEM_ROTATION);
 
+        let max_axis_positions = load_usize_with_warning(
+            Self::MAX_AXIS_POSITIONS_VAR,
+            PackingConfig::DEFAULT_MAX_AXIS_POSITIONS,
+        );
+
+        let strict_layering = env_string(Self::STRICT_LAYERING_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::STRICT_LAYERING_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_STRICT_LAYERING);
+
+        let primary_objective = env_string(Self::PRIMARY_OBJECTIVE_VAR)
+            .and_then(|raw| parse_primary_objective(&raw, Self::PRIMARY_OBJECTIVE_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_PRIMARY_OBJECTIVE);
+        let mass_placement = env_string(Self::MASS_PLACEMENT_VAR)
+            .and_then(|raw| parse_mass_placement(&raw, Self::MASS_PLACEMENT_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_MASS_PLACEMENT);
+        let load_direction = env_string(Self::LOAD_DIRECTION_VAR)
+            .and_then(|raw| parse_load_direction(&raw, Self::LOAD_DIRECTION_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_LOAD_DIRECTION);
+
+        let restarts = load_usize_with_warning(Self::RESTARTS_VAR, PackingConfig::DEFAULT_RESTARTS);
+
+        let template_merge_tolerance = load_f64_with_warning(
+            Self::TEMPLATE_MERGE_TOLERANCE_VAR,
+            PackingConfig::DEFAULT_TEMPLATE_MERGE_TOLERANCE,
+            |value| (0.0..=0.5).contains(&value),
+            "must be between 0 and 0.5",
+            "Warning: Merged container types may differ slightly from the requested dimensions",
+        );
+
+        let relative_tolerance = env_string(Self::RELATIVE_TOLERANCE_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::RELATIVE_TOLERANCE_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_RELATIVE_TOLERANCE);
+
+        // 0 disables the check, since any floor layer trivially covers 0% of the base.
+        let floor_min_coverage = load_f64_with_warning(
+            Self::FLOOR_MIN_COVERAGE_VAR,
+            0.0,
+            |value| (0.0..=1.0).contains(&value),
+            "must be between 0 and 1",
+            "Floor coverage threshold enabled; sparse floor layers will be flagged in diagnostics",
+        );
+
+        let container_order = env_string(Self::CONTAINER_ORDER_VAR)
+            .and_then(|raw| parse_container_order(&raw, Self::CONTAINER_ORDER_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_CONTAINER_ORDER);
+
+        let template_preference = env_string(Self::TEMPLATE_PREFERENCE_VAR)
+            .and_then(|raw| parse_template_preference(&raw, Self::TEMPLATE_PREFERENCE_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_TEMPLATE_PREFERENCE);
+        let new_container_policy = env_string(Self::NEW_CONTAINER_POLICY_VAR)
+            .and_then(|raw| parse_new_container_policy(&raw, Self::NEW_CONTAINER_POLICY_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_NEW_CONTAINER_POLICY);
+
+        // 0 disables the check, like the other optional thresholds.
+        let max_object_volume_fraction = load_f64_with_warning(
+            Self::MAX_OBJECT_VOLUME_FRACTION_VAR,
+            0.0,
+            |value| value >= 0.0,
+            "must be non-negative",
+            "Objects above the configured share of the largest container volume will be rejected",
+        );
+
+        let top_clearance = load_f64_with_warning(
+            Self::TOP_CLEARANCE_VAR,
+            PackingConfig::DEFAULT_TOP_CLEARANCE,
+            |value| value >= 0.0,
+            "must be non-negative",
+            "Top clearance enabled; containers will not be filled up to their ceiling",
+        );
+
+        let allow_equal_weight_stacking = env_string(Self::ALLOW_EQUAL_WEIGHT_STACKING_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::ALLOW_EQUAL_WEIGHT_STACKING_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_ALLOW_EQUAL_WEIGHT_STACKING);
+        let strict_weight = env_string(Self::STRICT_WEIGHT_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::STRICT_WEIGHT_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_STRICT_WEIGHT);
+
+        // 0 disables the cap; a container cannot stop before receiving anything.
+        let target_utilization = load_f64_with_warning(
+            Self::TARGET_UTILIZATION_VAR,
+            0.0,
+            |value| (0.0..=1.0).contains(&value),
+            "must be between 0 and 1",
+            "Containers stop receiving objects once they reach the target utilization",
+        );
+
+        let support_samples_per_axis = load_usize_with_warning(
+            Self::SUPPORT_SAMPLES_VAR,
+            PackingConfig::DEFAULT_SUPPORT_SAMPLES_PER_AXIS,
+        );
+
+        let compact = env_string(Self::COMPACT_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::COMPACT_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_COMPACT);
+        let settle = env_string(Self::SETTLE_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::SETTLE_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_SETTLE);
+        let cluster_by_category = env_string(Self::CLUSTER_BY_CATEGORY_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::CLUSTER_BY_CATEGORY_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_CLUSTER_BY_CATEGORY);
+        let prefer_wall_contact = env_string(Self::PREFER_WALL_CONTACT_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::PREFER_WALL_CONTACT_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_PREFER_WALL_CONTACT);
+        let prefer_floor_fallback = env_string(Self::PREFER_FLOOR_FALLBACK_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::PREFER_FLOOR_FALLBACK_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_PREFER_FLOOR_FALLBACK);
+        let prefer_column_stacking = env_string(Self::PREFER_COLUMN_STACKING_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::PREFER_COLUMN_STACKING_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_PREFER_COLUMN_STACKING);
+        let track_rejections = env_string(Self::TRACK_REJECTIONS_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::TRACK_REJECTIONS_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_TRACK_REJECTIONS);
+        let cluster_footprints = env_string(Self::CLUSTER_FOOTPRINTS_VAR)
+            .and_then(|raw| parse_bool(&raw, Self::CLUSTER_FOOTPRINTS_VAR))
+            .unwrap_or(PackingConfig::DEFAULT_CLUSTER_FOOTPRINTS);
+        let diagnostics_interval = load_usize_with_warning(
+            Self::DIAGNOSTICS_INTERVAL_VAR,
+            PackingConfig::DEFAULT_DIAGNOSTICS_INTERVAL,
+        );
+        // 0 disables snapshots.
+        let snapshot_every = load_usize_with_warning(Self::SNAPSHOT_EVERY_VAR, 0);
+        // 0 disables the respective progress trigger.
+        let progress_every = load_usize_with_warning(Self::PROGRESS_EVERY_VAR, 0);
+        let progress_interval_ms = load_usize_with_warning(Self::PROGRESS_INTERVAL_MS_VAR, 0);
+
         let packing = PackingConfig::builder()
             .grid_step(grid_step)
             .support_ratio(support_ratio)
             .height_epsilon(height_epsilon)
             .general_epsilon(general_epsilon)
             .balance_limit_ratio(balance_limit_ratio)
+            .balance_limit_ratio_per_axis(
+                (balance_limit_ratio_x > 0.0).then_some(balance_limit_ratio_x),
+                (balance_limit_ratio_y > 0.0).then_some(balance_limit_ratio_y),
+            )
             .footprint_cluster_tolerance(footprint_cluster_tolerance)
+            .cluster_footprints(cluster_footprints)
             .allow_item_rotation(allow_item_rotation)
+            .max_axis_positions(max_axis_positions)
+            .strict_layering(strict_layering)
+            .primary_objective(primary_objective)
+            .mass_placement(mass_placement)
+            .load_direction(load_direction)
+            .restarts(restarts)
+            .template_merge_tolerance(template_merge_tolerance)
+            .relative_tolerance(relative_tolerance)
+            .floor_min_coverage((floor_min_coverage > 0.0).then_some(floor_min_coverage))
+            .container_order(container_order)
+            .template_preference(template_preference)
+            .new_container_policy(new_container_policy)
+            .top_clearance(top_clearance)
+            .max_object_volume_fraction(
+                (max_object_volume_fraction > 0.0).then_some(max_object_volume_fraction),
+            )
+            .allow_equal_weight_stacking(allow_equal_weight_stacking)
+            .strict_weight(strict_weight)
+            .target_utilization((target_utilization > 0.0).then_some(target_utilization))
+            .support_samples_per_axis(support_samples_per_axis)
+            .compact(compact)
+            .settle(settle)
+            .cluster_by_category(cluster_by_category)
+            .prefer_wall_contact(prefer_wall_contact)
+            .prefer_floor_fallback(prefer_floor_fallback)
+            .prefer_column_stacking(prefer_column_stacking)
+            .track_rejections(track_rejections)
+            .diagnostics_interval(diagnostics_interval)
+            .snapshot_every((snapshot_every > 0).then_some(snapshot_every))
+            .progress_every((progress_every > 0).then_some(progress_every))
+            .progress_interval_ms((progress_interval_ms > 0).then_some(progress_interval_ms as u64))
             .build();
 
         Self { packing }
@@ -356,6 +693,92 @@ fn parse_bool(raw: &str, var_name: &str) -> Option<bool> {
     }
 }
 
+fn parse_primary_objective(raw: &str, var_name: &str) -> Option<PrimaryObjective> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "compactness" => Some(PrimaryObjective::Compactness),
+        "balance" => Some(PrimaryObjective::Balance),
+        other => {
+            eprintln!(
+                "⚠️ Could not interpret {} ('{}') as objective (compactness | balance). Using default value.",
+                var_name, other
+            );
+            None
+        }
+    }
+}
+
+fn parse_load_direction(raw: &str, var_name: &str) -> Option<LoadDirection> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "front_to_back" => Some(LoadDirection::FrontToBack),
+        "back_to_front" => Some(LoadDirection::BackToFront),
+        other => {
+            eprintln!(
+                "⚠️ Could not interpret {} ('{}') as load direction (front_to_back | back_to_front). Using default value.",
+                var_name, other
+            );
+            None
+        }
+    }
+}
+
+fn parse_mass_placement(raw: &str, var_name: &str) -> Option<MassPlacement> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "balanced" => Some(MassPlacement::Balanced),
+        "centered" => Some(MassPlacement::Centered),
+        other => {
+            eprintln!(
+                "⚠️ Could not interpret {} ('{}') as mass placement (balanced | centered). Using default value.",
+                var_name, other
+            );
+            None
+        }
+    }
+}
+
+fn parse_new_container_policy(raw: &str, var_name: &str) -> Option<NewContainerPolicy> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "smallest_fitting" => Some(NewContainerPolicy::SmallestFitting),
+        "largest_available" => Some(NewContainerPolicy::LargestAvailable),
+        "best_fit_for_remaining" => Some(NewContainerPolicy::BestFitForRemaining),
+        other => {
+            eprintln!(
+                "⚠️ Could not interpret {} ('{}') as new container policy (smallest_fitting | largest_available | best_fit_for_remaining). Using default value.",
+                var_name, other
+            );
+            None
+        }
+    }
+}
+
+fn parse_template_preference(raw: &str, var_name: &str) -> Option<TemplatePreference> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "smallest_first" => Some(TemplatePreference::SmallestFirst),
+        "largest_first" => Some(TemplatePreference::LargestFirst),
+        other => {
+            eprintln!(
+                "⚠️ Could not interpret {} ('{}') as template preference (smallest_first | largest_first). Using default value.",
+                var_name, other
+            );
+            None
+        }
+    }
+}
+
+fn parse_container_order(raw: &str, var_name: &str) -> Option<ContainerOrder> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "creation" => Some(ContainerOrder::Creation),
+        "utilization" => Some(ContainerOrder::Utilization),
+        "weight" => Some(ContainerOrder::Weight),
+        other => {
+            eprintln!(
+                "⚠️ Could not interpret {} ('{}') as container order (creation | utilization | weight). Using default value.",
+                var_name, other
+            );
+            None
+        }
+    }
+}
+
 /// Loads a non-negative integer setting, falling back to `default` on parse failure.
 ///
 /// A value of `0` is accepted and meaningful (it disables the associated limit), so only
@@ -460,4 +883,74 @@ mod tests {
         assert_eq!(parse_bool("", "TEST_VAR"), None);
         assert_eq!(parse_bool("  ", "TEST_VAR"), None);
     }
+
+    #[test]
+    fn test_parse_primary_objective() {
+        assert_eq!(
+            parse_primary_objective(" Balance ", "TEST_VAR"),
+            Some(PrimaryObjective::Balance)
+        );
+        assert_eq!(
+            parse_primary_objective("compactness", "TEST_VAR"),
+            Some(PrimaryObjective::Compactness)
+        );
+        assert_eq!(parse_primary_objective("height", "TEST_VAR"), None);
+    }
+
+    #[test]
+    fn test_parse_container_order() {
+        assert_eq!(
+            parse_container_order(" Utilization ", "TEST_VAR"),
+            Some(ContainerOrder::Utilization)
+        );
+        assert_eq!(
+            parse_container_order("weight", "TEST_VAR"),
+            Some(ContainerOrder::Weight)
+        );
+        assert_eq!(parse_container_order("volume", "TEST_VAR"), None);
+    }
+
+    #[test]
+    fn test_parse_template_preference() {
+        assert_eq!(
+            parse_template_preference(" Largest_First ", "TEST_VAR"),
+            Some(TemplatePreference::LargestFirst)
+        );
+        assert_eq!(parse_template_preference("largest", "TEST_VAR"), None);
+    }
+
+    #[test]
+    fn test_parse_mass_placement() {
+        assert_eq!(
+            parse_mass_placement(" Centered ", "TEST_VAR"),
+            Some(MassPlacement::Centered)
+        );
+        assert_eq!(
+            parse_mass_placement("balanced", "TEST_VAR"),
+            Some(MassPlacement::Balanced)
+        );
+        assert_eq!(parse_mass_placement("center", "TEST_VAR"), None);
+    }
+
+    #[test]
+    fn test_parse_load_direction() {
+        assert_eq!(
+            parse_load_direction(" Back_To_Front ", "TEST_VAR"),
+            Some(LoadDirection::BackToFront)
+        );
+        assert_eq!(
+            parse_load_direction("front_to_back", "TEST_VAR"),
+            Some(LoadDirection::FrontToBack)
+        );
+        assert_eq!(parse_load_direction("rear", "TEST_VAR"), None);
+    }
+
+    #[test]
+    fn test_parse_new_container_policy() {
+        assert_eq!(
+            parse_new_container_policy("Best_Fit_For_Remaining", "TEST_VAR"),
+            Some(NewContainerPolicy::BestFitForRemaining)
+        );
+        assert_eq!(parse_new_container_policy("best_fit", "TEST_VAR"), None);
+    }
 }
@@ -0,0 +1,230 @@
+//! Representation of dimension and position tuples in serialized responses.
+//!
+//! Internally every dimension and position is an `(f64, f64, f64)` tuple, which serializes as
+//! a positional array. Clients may request named objects instead via [`DimFormat`]. Fields opt
+//! into the conversion through the serde helpers [`dims`], [`position`] and [`envelope`], and
+//! [`DimFormatted`] selects the format for one serialization. Without the wrapper every field
+//! keeps the array form, so existing serializations are unchanged.
+
+use std::cell::Cell;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::ToSchema;
+
+/// Representation of dimensions and positions in a `/pack` response.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DimFormat {
+    /// Positional arrays: `[w, d, h]` and `[x, y, z]`.
+    #[default]
+    Array,
+    /// Named objects: `{width, depth, height}` and `{x, y, z}`.
+    Object,
+}
+
+/// Named form of a `(width, depth, height)` tuple.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, ToSchema)]
+pub struct Dimensions {
+    pub width: f64,
+    pub depth: f64,
+    pub height: f64,
+}
+
+impl From<(f64, f64, f64)> for Dimensions {
+    fn from((width, depth, height): (f64, f64, f64)) -> Self {
+        Self {
+            width,
+            depth,
+            height,
+        }
+    }
+}
+
+/// Named form of an `(x, y, z)` position tuple.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, ToSchema)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl From<(f64, f64, f64)> for Position {
+    fn from((x, y, z): (f64, f64, f64)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Schema of a dimension field: `[w, d, h]` or, with [`DimFormat::Object`], [`Dimensions`].
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum DimensionsValue {
+    #[schema(value_type = [f64; 3])]
+    Array((f64, f64, f64)),
+    Object(Dimensions),
+}
+
+/// Schema of a position field: `[x, y, z]` or, with [`DimFormat::Object`], [`Position`].
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum PositionValue {
+    #[schema(value_type = [f64; 3])]
+    Array((f64, f64, f64)),
+    Object(Position),
+}
+
+thread_local! {
+    static ACTIVE_FORMAT: Cell<DimFormat> = const { Cell::new(DimFormat::Array) };
+}
+
+/// Serializes `value` with all opted-in dimension and position fields in `format`.
+pub struct DimFormatted<'a, T: ?Sized> {
+    value: &'a T,
+    format: DimFormat,
+}
+
+impl<'a, T: ?Sized> DimFormatted<'a, T> {
+    pub fn new(value: &'a T, format: DimFormat) -> Self {
+        Self { value, format }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for DimFormatted<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Restores the previous format even if serialization panics.
+        struct Restore(DimFormat);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                ACTIVE_FORMAT.with(|active| active.set(self.0));
+            }
+        }
+
+        let _restore = Restore(ACTIVE_FORMAT.with(|active| active.replace(self.format)));
+        self.value.serialize(serializer)
+    }
+}
+
+fn active_format() -> DimFormat {
+    ACTIVE_FORMAT.with(Cell::get)
+}
+
+/// Serde helpers for `(width, depth, height)` fields.
+pub mod dims {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &(f64, f64, f64),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match active_format() {
+            DimFormat::Array => value.serialize(serializer),
+            DimFormat::Object => Dimensions::from(*value).serialize(serializer),
+        }
+    }
+
+    /// Accepts both the array and the object form.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<(f64, f64, f64), D::Error> {
+        Ok(match DimensionsValue::deserialize(deserializer)? {
+            DimensionsValue::Array(value) => value,
+            DimensionsValue::Object(dims) => (dims.width, dims.depth, dims.height),
+        })
+    }
+}
+
+/// Serde helpers for `(x, y, z)` fields.
+pub mod position {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &(f64, f64, f64),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match active_format() {
+            DimFormat::Array => value.serialize(serializer),
+            DimFormat::Object => Position::from(*value).serialize(serializer),
+        }
+    }
+
+    /// Accepts both the array and the object form.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<(f64, f64, f64), D::Error> {
+        Ok(match PositionValue::deserialize(deserializer)? {
+            PositionValue::Array(value) => value,
+            PositionValue::Object(pos) => (pos.x, pos.y, pos.z),
+        })
+    }
+}
+
+/// Serde helpers for `[min, max]` corner pairs; each corner is a position.
+pub mod envelope {
+    use super::*;
+
+    type Corners = ((f64, f64, f64), (f64, f64, f64));
+
+    pub fn serialize<S: Serializer>(value: &Corners, serializer: S) -> Result<S::Ok, S::Error> {
+        match active_format() {
+            DimFormat::Array => value.serialize(serializer),
+            DimFormat::Object => {
+                (Position::from(value.0), Position::from(value.1)).serialize(serializer)
+            }
+        }
+    }
+
+    /// Accepts both the array and the object form of each corner.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Corners, D::Error> {
+        let corner = |value: PositionValue| match value {
+            PositionValue::Array(value) => value,
+            PositionValue::Object(pos) => (pos.x, pos.y, pos.z),
+        };
+        let (min, max) = <(PositionValue, PositionValue)>::deserialize(deserializer)?;
+        Ok((corner(min), corner(max)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize, Deserialize)]
+    struct Placed {
+        #[serde(with = "position")]
+        pos: (f64, f64, f64),
+        #[serde(with = "dims")]
+        dims: (f64, f64, f64),
+        /// Not opted in, so it stays an array in every format.
+        other: (f64, f64, f64),
+    }
+
+    #[test]
+    fn only_opted_in_fields_follow_the_requested_format() {
+        let placed = Placed {
+            pos: (1.0, 2.0, 3.0),
+            dims: (4.0, 5.0, 6.0),
+            other: (7.0, 8.0, 9.0),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&placed).unwrap(),
+            json!({"pos": [1.0, 2.0, 3.0], "dims": [4.0, 5.0, 6.0], "other": [7.0, 8.0, 9.0]})
+        );
+        let named = serde_json::to_value(DimFormatted::new(&placed, DimFormat::Object)).unwrap();
+        assert_eq!(
+            named,
+            json!({
+                "pos": {"x": 1.0, "y": 2.0, "z": 3.0},
+                "dims": {"width": 4.0, "depth": 5.0, "height": 6.0},
+                "other": [7.0, 8.0, 9.0]
+            })
+        );
+        // The format only applies inside the wrapper, and both forms read back.
+        assert_eq!(
+            serde_json::to_value(&placed).unwrap()["pos"],
+            json!([1.0, 2.0, 3.0])
+        );
+        let parsed: Placed = serde_json::from_value(named).unwrap();
+        assert_eq!((parsed.pos, parsed.dims), (placed.pos, placed.dims));
+    }
+}
@@ -0,0 +1,221 @@
+//! Export of packing results into formats for downstream automation.
+//!
+//! [`to_plan`] turns a [`PackResponse`] into a [`Plan`] for automated loaders (e.g. a robotic
+//! arm): every object gets absolute world coordinates, its dimensions, a global placement
+//! sequence, and — if the original objects are known — the orientation that was applied.
+//!
+//! Containers are laid out side by side along the X axis in response order, so container `n`
+//! starts where container `n - 1` ends. Objects keep the order in which the optimizer placed
+//! them, which is always bottom-up within a stack and therefore a valid approach order.
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::PackResponse;
+use crate::model::Box3D;
+
+/// Relative tolerance used to match placed dimensions against the original dimensions.
+const DIMENSION_MATCH_TOLERANCE: f64 = 1e-9;
+
+/// Complete placement plan for all containers of a packing result.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    pub containers: Vec<PlanContainer>,
+    /// All placement steps across all containers, ordered by `sequence`.
+    pub steps: Vec<PlanStep>,
+}
+
+/// A container and its position in the world frame.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlanContainer {
+    /// Container id from the response (1-based).
+    pub id: usize,
+    pub label: Option<String>,
+    pub dims: (f64, f64, f64),
+    /// World coordinates of the container origin (its lower-left-front corner).
+    pub world_offset: (f64, f64, f64),
+}
+
+/// A single object placement.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlanStep {
+    /// Global placement order, starting at 0.
+    pub sequence: usize,
+    pub object_id: usize,
+    pub container_id: usize,
+    /// Absolute position of the object's lower-left-front corner (container offset + position).
+    pub world_position: (f64, f64, f64),
+    /// Position relative to the container origin, as reported in the response.
+    pub local_position: (f64, f64, f64),
+    /// Dimensions as placed (after rotation).
+    pub dims: (f64, f64, f64),
+    pub weight: f64,
+    /// Applied orientation; `None` if the original object is unknown.
+    pub orientation: Option<PlanOrientation>,
+}
+
+/// Rotation applied to an object, expressed as an axis permutation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanOrientation {
+    /// For each placed axis (x, y, z), the index of the original axis it came from.
+    pub axis_order: [usize; 3],
+    /// `true` if the axis order differs from the original orientation.
+    pub rotated: bool,
+}
+
+/// Builds a placement plan without orientation information.
+///
+/// Use [`to_plan_with_originals`] when the original request objects are available.
+pub fn to_plan(response: &PackResponse) -> Plan {
+    to_plan_with_originals(response, &[])
+}
+
+/// Builds a placement plan and derives each object's orientation from `originals`.
+///
+/// Objects are matched by id; objects without an original keep `orientation: None`.
+pub fn to_plan_with_originals(response: &PackResponse, originals: &[Box3D]) -> Plan {
+    let mut containers = Vec::with_capacity(response.results.len());
+    let mut steps = Vec::new();
+    let mut next_x = 0.0;
+
+    for container in &response.results {
+        let world_offset = (next_x, 0.0, 0.0);
+        next_x += container.dims.0;
+        containers.push(PlanContainer {
+            id: container.id,
+            label: container.label.clone(),
+            dims: container.dims,
+            world_offset,
+        });
+
+        for object in &container.placed {
+            let orientation = originals
+                .iter()
+                .find(|original| original.id == object.id)
+                .and_then(|original| orientation_between(original.dims, object.dims));
+            steps.push(PlanStep {
+                sequence: steps.len(),
+                object_id: object.id,
+                container_id: container.id,
+                world_position: (
+                    world_offset.0 + object.pos.0,
+                    world_offset.1 + object.pos.1,
+                    world_offset.2 + object.pos.2,
+                ),
+                local_position: object.pos,
+                dims: object.dims,
+                weight: object.weight,
+                orientation,
+            });
+        }
+    }
+
+    Plan { containers, steps }
+}
+
+/// Finds the axis permutation that maps `original` onto `placed`.
+///
+/// The identity is preferred for cube-like objects whose dimensions match several permutations.
+fn orientation_between(
+    original: (f64, f64, f64),
+    placed: (f64, f64, f64),
+) -> Option<PlanOrientation> {
+    let original = [original.0, original.1, original.2];
+    let placed = [placed.0, placed.1, placed.2];
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [1, 0, 2],
+        [0, 2, 1],
+        [2, 1, 0],
+        [1, 2, 0],
+        [2, 0, 1],
+    ];
+
+    PERMUTATIONS
+        .into_iter()
+        .find(|order| {
+            order
+                .iter()
+                .zip(placed)
+                .all(|(&axis, value)| dims_match(original[axis], value))
+        })
+        .map(|axis_order| PlanOrientation {
+            axis_order,
+            rotated: axis_order != [0, 1, 2],
+        })
+}
+
+fn dims_match(a: f64, b: f64) -> bool {
+    (a - b).abs() <= DIMENSION_MATCH_TOLERANCE * a.abs().max(b.abs()).max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ContainerBlueprint;
+    use crate::optimizer::pack_objects;
+
+    #[test]
+    fn plan_uses_world_coordinates_and_global_sequence() {
+        let templates = vec![ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 10.0).unwrap()];
+        // Each object alone reaches the weight limit, so the pack needs two containers.
+        let originals = vec![
+            Box3D::new(1, (10.0, 10.0, 4.0), 10.0).unwrap(),
+            Box3D::new(2, (4.0, 10.0, 10.0), 10.0).unwrap(),
+        ];
+        let response =
+            PackResponse::from_packing_result(pack_objects(originals.clone(), templates));
+        assert_eq!(response.results.len(), 2);
+
+        let plan = to_plan_with_originals(&response, &originals);
+
+        assert_eq!(plan.containers[0].world_offset, (0.0, 0.0, 0.0));
+        assert_eq!(plan.containers[1].world_offset, (10.0, 0.0, 0.0));
+
+        assert_eq!(plan.steps.len(), 2);
+        for (expected_sequence, step) in plan.steps.iter().enumerate() {
+            assert_eq!(step.sequence, expected_sequence);
+            let offset = plan
+                .containers
+                .iter()
+                .find(|c| c.id == step.container_id)
+                .unwrap()
+                .world_offset;
+            assert_eq!(
+                step.world_position,
+                (
+                    offset.0 + step.local_position.0,
+                    offset.1 + step.local_position.1,
+                    offset.2 + step.local_position.2,
+                )
+            );
+            let orientation = step.orientation.expect("originals are known");
+            assert!(!orientation.rotated);
+        }
+        assert_eq!(plan.steps[0].container_id, 1);
+        assert_eq!(plan.steps[1].container_id, 2);
+        assert_eq!(
+            plan.steps[1].world_position.0,
+            10.0 + plan.steps[1].local_position.0
+        );
+
+        // Without originals the orientation stays unknown.
+        assert!(
+            to_plan(&response)
+                .steps
+                .iter()
+                .all(|s| s.orientation.is_none())
+        );
+    }
+
+    #[test]
+    fn orientation_reports_applied_axis_permutation() {
+        let orientation = orientation_between((30.0, 20.0, 10.0), (10.0, 20.0, 30.0)).unwrap();
+        assert_eq!(orientation.axis_order, [2, 1, 0]);
+        assert!(orientation.rotated);
+
+        let cube = orientation_between((5.0, 5.0, 5.0), (5.0, 5.0, 5.0)).unwrap();
+        assert!(!cube.rotated);
+
+        assert!(orientation_between((1.0, 2.0, 3.0), (1.0, 2.0, 4.0)).is_none());
+    }
+}
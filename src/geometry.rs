@@ -162,7 +162,6 @@ pub fn point_inside_bounds(point: &Vec3, bounds: &BoundingBox) -> bool {
 /// # Returns
 /// `true` if `upper` rests on `lower`
 #[inline]
-#[allow(dead_code)]
 pub fn rests_on(upper: &PlacedBox, lower: &PlacedBox, height_epsilon: f64) -> bool {
     let upper_bottom = upper.position.2;
     let lower_top = lower.position.2 + lower.object.dims.2;
@@ -189,6 +188,37 @@ pub fn rests_on(upper: &PlacedBox, lower: &PlacedBox, height_epsilon: f64) -> bo
     overlap_x > EPSILON_GENERAL && overlap_y > EPSILON_GENERAL
 }
 
+/// Checks if two boxes touch side by side.
+///
+/// The boxes touch if a face of one lies against a face of the other along X or Y
+/// (within tolerance) and their areas of contact overlap in the two remaining axes.
+///
+/// # Parameters
+/// * `a` - First placed object
+/// * `b` - Second placed object
+/// * `epsilon` - Tolerance for the face distance
+///
+/// # Returns
+/// `true` if the boxes share a side face
+#[inline]
+pub fn touches_sideways(a: &PlacedBox, b: &PlacedBox, epsilon: f64) -> bool {
+    let (ax, ay, az) = a.position;
+    let (aw, ad, ah) = a.object.dims;
+    let (bx, by, bz) = b.position;
+    let (bw, bd, bh) = b.object.dims;
+
+    let overlap_x = overlap_1d(ax, ax + aw, bx, bx + bw) > EPSILON_GENERAL;
+    let overlap_y = overlap_1d(ay, ay + ad, by, by + bd) > EPSILON_GENERAL;
+    let overlap_z = overlap_1d(az, az + ah, bz, bz + bh) > EPSILON_GENERAL;
+    let faces_touch = |a_start: f64, a_len: f64, b_start: f64, b_len: f64| {
+        (a_start + a_len - b_start).abs() <= epsilon || (b_start + b_len - a_start).abs() <= epsilon
+    };
+
+    overlap_z
+        && ((overlap_y && faces_touch(ax, aw, bx, bw))
+            || (overlap_x && faces_touch(ay, ad, by, bd)))
+}
+
 /// Calculates the support area between two boxes.
 ///
 /// Returns the area with which `upper` rests on `lower`.
@@ -273,6 +303,7 @@ mod tests {
                 id,
                 dims,
                 weight: 1.0,
+                ..Default::default()
             },
             position: pos,
         }
@@ -316,6 +347,20 @@ mod tests {
         assert!(!rests_on(&separate, &lower, 1e-3));
     }
 
+    #[test]
+    fn test_touches_sideways() {
+        let a = make_placed_box(1, (0.0, 0.0, 0.0), (10.0, 10.0, 10.0));
+        let beside = make_placed_box(2, (10.0, 5.0, 0.0), (10.0, 10.0, 10.0));
+        let behind = make_placed_box(3, (0.0, 10.0, 0.0), (10.0, 10.0, 10.0));
+        let diagonal = make_placed_box(4, (10.0, 10.0, 0.0), (10.0, 10.0, 10.0));
+        let on_top = make_placed_box(5, (0.0, 0.0, 10.0), (10.0, 10.0, 10.0));
+
+        assert!(touches_sideways(&a, &beside, 1e-6));
+        assert!(touches_sideways(&a, &behind, 1e-6));
+        assert!(!touches_sideways(&a, &diagonal, 1e-6));
+        assert!(!touches_sideways(&a, &on_top, 1e-6));
+    }
+
     #[test]
     fn test_center_of_mass_xy() {
         let points = vec![(0.0, 0.0, 10.0), (10.0, 0.0, 10.0)];
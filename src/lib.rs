@@ -14,14 +14,24 @@
 //! - [`optimizer`] — the heuristic packing engine and its diagnostics.
 //! - [`config`] — environment-driven configuration for the API, optimizer, and updater.
 //! - [`api`] — the Axum HTTP layer (router, request/response types, handlers).
+//! - [`dim_format`] — array or named-object serialization of dimensions and positions.
+//! - [`export`] — placement plans with world coordinates for automated loaders.
+//! - [`rate_limit`] — per-client token-bucket rate limiting for the API.
+//! - [`result_cache`] — the bounded cache of recent `/pack` responses.
+//! - [`templates`] — the server-side library of named container templates.
 //! - [`update`] — the background GitHub release updater.
 
 pub mod api;
 pub mod cli;
 pub mod config;
+pub mod dim_format;
+pub mod export;
 pub mod geometry;
 pub mod model;
 pub mod optimizer;
 pub mod packaging;
+pub mod rate_limit;
+pub mod result_cache;
+pub mod templates;
 pub mod types;
 pub mod update;
@@ -12,6 +12,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use utoipa::ToSchema;
 
+use crate::geometry::{intersects, overlap_1d};
+use crate::optimizer::PackingConfig;
 use crate::packaging::PackagingFill;
 use crate::types::{BoundingBox, Dimensional, EPSILON_GENERAL, Positioned, Vec3, Weighted};
 
@@ -24,7 +26,6 @@ use crate::types::{BoundingBox, Dimensional, EPSILON_GENERAL, Positioned, Vec3,
 pub enum ValidationError {
     InvalidDimension(String),
     InvalidWeight(String),
-    #[allow(dead_code)]
     InvalidConfiguration(String),
 }
 
@@ -42,6 +43,43 @@ impl std::fmt::Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+/// Reason why [`Container::try_place`] rejected an object.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlacementError {
+    /// The object would extend past the container walls or its ceiling.
+    OutOfBounds,
+    /// The object would exceed the remaining weight capacity.
+    OverWeight { weight: f64, remaining: f64 },
+    /// The object would intersect an already placed object or reserved space with this id.
+    Overlap { other: usize },
+    /// The object would be stacked above the floor inside the container's floor-only region.
+    FloorOnlyRegion,
+    /// The container already holds its maximum number of objects.
+    ItemLimit { max: usize },
+}
+
+impl std::fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementError::OutOfBounds => write!(f, "Object extends beyond the container"),
+            PlacementError::OverWeight { weight, remaining } => write!(
+                f,
+                "Object weight {} exceeds the remaining capacity of {}",
+                weight, remaining
+            ),
+            PlacementError::Overlap { other } => write!(f, "Object overlaps object {}", other),
+            PlacementError::FloorOnlyRegion => {
+                write!(f, "Object is stacked inside the floor-only region")
+            }
+            PlacementError::ItemLimit { max } => {
+                write!(f, "Container already holds its maximum of {} objects", max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlacementError {}
+
 /// Helper function to validate a single dimension (DRY principle).
 fn validate_dimension(value: f64, name: &str) -> Result<(), ValidationError> {
     if value <= 0.0 || value.is_nan() || value.is_infinite() {
@@ -73,6 +111,58 @@ fn validate_box_params(dims: (f64, f64, f64), weight: f64) -> Result<(), Validat
     Ok(())
 }
 
+/// Validates a stepped ceiling profile against the container dimensions.
+///
+/// Each step is `(x_threshold, max_height)`: from `x_threshold` onwards (until the next step) the
+/// usable height is limited to `max_height`. Thresholds must be strictly ascending and lie inside
+/// `0..container width`; heights must be positive and not exceed the container height.
+fn validate_height_profile(
+    profile: &[(f64, f64)],
+    dims: (f64, f64, f64),
+) -> Result<(), ValidationError> {
+    let mut previous: Option<f64> = None;
+    for &(threshold, height) in profile {
+        if !threshold.is_finite() || threshold < 0.0 || threshold >= dims.0 {
+            return Err(ValidationError::InvalidConfiguration(format!(
+                "Height profile threshold must lie within 0..{}, got: {}",
+                dims.0, threshold
+            )));
+        }
+        if previous.is_some_and(|prev| threshold <= prev) {
+            return Err(ValidationError::InvalidConfiguration(format!(
+                "Height profile thresholds must be strictly ascending, got {} after {}",
+                threshold,
+                previous.unwrap_or_default()
+            )));
+        }
+        if !height.is_finite() || height <= 0.0 || height > dims.2 {
+            return Err(ValidationError::InvalidConfiguration(format!(
+                "Height profile height must lie within (0, {}], got: {}",
+                dims.2, height
+            )));
+        }
+        previous = Some(threshold);
+    }
+    Ok(())
+}
+
+/// Returns the lowest ceiling over the X span `x_start..x_end` for a stepped height profile.
+fn ceiling_over(profile: &[(f64, f64)], full_height: f64, x_start: f64, x_end: f64) -> f64 {
+    let mut ceiling = match profile.first() {
+        Some(&(first_threshold, _)) if x_start + EPSILON_GENERAL >= first_threshold => {
+            f64::INFINITY
+        }
+        _ => full_height,
+    };
+    for (i, &(threshold, height)) in profile.iter().enumerate() {
+        let next_threshold = profile.get(i + 1).map_or(f64::INFINITY, |step| step.0);
+        if x_end > threshold + EPSILON_GENERAL && x_start < next_threshold - EPSILON_GENERAL {
+            ceiling = ceiling.min(height);
+        }
+    }
+    ceiling.min(full_height)
+}
+
 /// Validates container dimensions (DRY principle).
 fn validate_container_dims(dims: (f64, f64, f64)) -> Result<(), ValidationError> {
     validate_dimension(dims.0, "Container width")?;
@@ -89,12 +179,65 @@ fn validate_container_dims(dims: (f64, f64, f64)) -> Result<(), ValidationError>
 /// * `id` - Unique identification number of the object
 /// * `dims` - Dimensions (width, depth, height) in units
 /// * `weight` - Weight of the object in kg
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+/// * `category` - Optional category passed through to the result (ignored by the optimizer)
+/// * `color` - Optional hex color passed through to the result (ignored by the optimizer)
+/// * `tags` - Free-form labels that request-level rules (e.g. rotation tags) can match on
+/// * `allow_rotation` - Per-object rotation override; `None` falls back to the global setting
+/// * `allowed_template_ids` - Optional whitelist of container types (indices) the object may use
+/// * `must_be_accessible` - Keeps the corridor between the object and the door (`y = 0`) free
+/// * `min_gaps` - Minimum clearance to specific other objects in the same container
+/// * `position_hint` - Preferred position (lower-left-front corner) the placement is pulled toward
+/// * `optional` - Only packed into already open containers, never opening a new one
+/// * `max_placement_height` - Highest `z` the object may be placed at, e.g. the reach of a worker
+/// * `group_id` - Independent group; only used by the experimental parallel group packing of the
+///   library, the server packs all groups together
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
 pub struct Box3D {
     pub id: usize,
     #[schema(value_type = [f64; 3], example = json!([30.0, 40.0, 20.0]))]
     pub dims: (f64, f64, f64),
     pub weight: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "fragile")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "#ff8800")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(example = json!(["rigid"]))]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = true)]
+    pub allow_rotation: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = true, example = json!([0, 2]))]
+    pub allowed_template_ids: Option<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub must_be_accessible: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub min_gaps: Vec<ObjectGap>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<[f64; 3]>, nullable = true, example = json!([40.0, 0.0, 0.0]))]
+    pub position_hint: Option<(f64, f64, f64)>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = true, example = 150.0)]
+    pub max_placement_height: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Only read by the library's experimental `pack_groups_parallel`; the server packs all
+    /// groups together and only checks that bundle members share it.
+    #[schema(nullable = true, example = "order-17")]
+    pub group_id: Option<String>,
+}
+
+/// Minimum clearance an object keeps to the object with id `id`.
+///
+/// The clearance is met when the bounding boxes are at least `gap` apart along one axis.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ObjectGap {
+    pub id: usize,
+    pub gap: f64,
 }
 
 impl Box3D {
@@ -120,7 +263,70 @@ impl Box3D {
     /// ```
     pub fn new(id: usize, dims: (f64, f64, f64), weight: f64) -> Result<Self, ValidationError> {
         validate_box_params(dims, weight)?;
-        Ok(Self { id, dims, weight })
+        Ok(Self {
+            id,
+            dims,
+            weight,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a weightless virtual box that only reserves space.
+    ///
+    /// Only the dimensions are validated; the weight is always `0`.
+    pub fn reserved_space(id: usize, dims: (f64, f64, f64)) -> Result<Self, ValidationError> {
+        validate_dimension(dims.0, "Reserved width")?;
+        validate_dimension(dims.1, "Reserved depth")?;
+        validate_dimension(dims.2, "Reserved height")?;
+        Ok(Self {
+            id,
+            dims,
+            ..Default::default()
+        })
+    }
+
+    /// Returns `true` if the object may be placed into a container of type `template_id`.
+    ///
+    /// Without a whitelist every container is allowed; with one, containers without a template
+    /// id (e.g. restored from a prior result without metadata) are not.
+    pub fn allows_template(&self, template_id: Option<usize>) -> bool {
+        match &self.allowed_template_ids {
+            None => true,
+            Some(allowed) => template_id.is_some_and(|id| allowed.contains(&id)),
+        }
+    }
+
+    /// Returns the clearance this object requires to the object with id `other` (0 = none).
+    pub fn required_gap_to(&self, other: usize) -> f64 {
+        self.min_gaps
+            .iter()
+            .filter(|entry| entry.id == other)
+            .map(|entry| entry.gap)
+            .fold(0.0, f64::max)
+    }
+
+    /// Validates an already constructed object, e.g. one deserialized from a request.
+    ///
+    /// Unlike [`Box3D::new`], this keeps optional metadata such as `category` and `color`.
+    pub fn validated(self) -> Result<Self, ValidationError> {
+        validate_box_params(self.dims, self.weight)?;
+        if let Some(hint) = self.position_hint
+            && !(hint.0.is_finite() && hint.1.is_finite() && hint.2.is_finite())
+        {
+            return Err(ValidationError::InvalidDimension(format!(
+                "Position hint of object {} must be finite, got: {:?}",
+                self.id, hint
+            )));
+        }
+        if let Some(height) = self.max_placement_height
+            && !(height.is_finite() && height >= 0.0)
+        {
+            return Err(ValidationError::InvalidDimension(format!(
+                "Max placement height of object {} must be non-negative, got: {}",
+                self.id, height
+            )));
+        }
+        Ok(self)
     }
 
     /// Calculates the volume of the object.
@@ -182,11 +388,15 @@ impl PlacedBox {
     /// # Parameters
     /// * `object` - The Box3D object to place
     /// * `position` - Position (x, y, z) in the container
-    #[allow(dead_code)]
     pub fn new(object: Box3D, position: (f64, f64, f64)) -> Self {
         Self { object, position }
     }
 
+    /// Returns the volume occupied by the placed object.
+    pub fn volume(&self) -> f64 {
+        self.object.volume()
+    }
+
     /// Returns the top Z coordinate of the placed object.
     ///
     /// # Returns
@@ -266,6 +476,12 @@ impl Weighted for PlacedBox {
 /// * `dims` - Dimensions (width, depth, height) of the container
 /// * `max_weight` - Maximum total weight in kg
 /// * `placed` - List of already placed objects
+/// * `height_profile` - Optional stepped ceiling as `(x_threshold, max_height)` pairs
+/// * `reserved` - Weightless virtual boxes that block space but are never part of the load
+/// * `reserved_weight` - Fixed weight (e.g. dunnage) counted in `total_weight` without taking space
+/// * `floor_only_region` - Optional `(x, y, width, depth)` rectangle where nothing may be stacked
+/// * `max_items` - Optional limit on the number of placed objects, regardless of volume
+/// * `cost` - Optional price of using the container, summed into the shipment cost
 #[derive(Clone, Debug)]
 pub struct Container {
     pub dims: (f64, f64, f64),
@@ -273,6 +489,20 @@ pub struct Container {
     pub placed: Vec<PlacedBox>,
     pub template_id: Option<usize>,
     pub label: Option<String>,
+    pub height_profile: Vec<(f64, f64)>,
+    pub reserved: Vec<PlacedBox>,
+    /// Wall thickness already subtracted from `dims`; `0` if `dims` are inner dimensions.
+    pub wall_thickness: f64,
+    /// Offset added to reported positions; packing itself always starts at the origin.
+    pub origin: (f64, f64, f64),
+    /// Weight carried from the start without occupying space; counts toward `max_weight`.
+    pub reserved_weight: f64,
+    /// `(x, y, width, depth)` rectangle, e.g. near the door, where objects must rest on the floor.
+    pub floor_only_region: Option<(f64, f64, f64, f64)>,
+    /// Maximum number of placed objects, e.g. for a mail sack.
+    pub max_items: Option<usize>,
+    /// Price of using this container; `None` counts as free.
+    pub cost: Option<f64>,
 }
 
 impl Container {
@@ -298,15 +528,62 @@ impl Container {
             placed: Vec::new(),
             template_id: None,
             label: None,
+            height_profile: Vec::new(),
+            reserved: Vec::new(),
+            wall_thickness: 0.0,
+            origin: (0.0, 0.0, 0.0),
+            reserved_weight: 0.0,
+            floor_only_region: None,
+            max_items: None,
+            cost: None,
         })
     }
 
+    /// Applies a stepped ceiling profile after validating it.
+    pub fn with_height_profile(
+        mut self,
+        profile: Vec<(f64, f64)>,
+    ) -> Result<Self, ValidationError> {
+        validate_height_profile(&profile, self.dims)?;
+        self.height_profile = profile;
+        Ok(self)
+    }
+
+    /// Returns the usable height for an object spanning `x_start..x_end`.
+    ///
+    /// Without a height profile this is simply the container height.
+    pub fn ceiling_between(&self, x_start: f64, x_end: f64) -> f64 {
+        ceiling_over(&self.height_profile, self.dims.2, x_start, x_end)
+    }
+
+    /// Returns the outer dimensions, i.e. the usable interior plus the walls on both sides.
+    pub fn outer_dims(&self) -> (f64, f64, f64) {
+        outer_dims(self.dims, self.wall_thickness)
+    }
+
+    /// Returns `false` if an object at `position` with `dims` would be stacked above the floor
+    /// while its footprint overlaps the floor-only region by more than `epsilon`.
+    pub fn allows_stacking_at(
+        &self,
+        position: (f64, f64, f64),
+        dims: (f64, f64, f64),
+        epsilon: f64,
+    ) -> bool {
+        let Some((rx, ry, rw, rd)) = self.floor_only_region else {
+            return true;
+        };
+        let (x, y, z) = position;
+        z <= epsilon
+            || overlap_1d(x, x + dims.0, rx, rx + rw) <= epsilon
+            || overlap_1d(y, y + dims.1, ry, ry + rd) <= epsilon
+    }
+
     /// Calculates the total weight of all placed objects.
     ///
     /// # Returns
-    /// Sum of the weights of all objects
+    /// Sum of the weights of all objects plus the `reserved_weight`
     pub fn total_weight(&self) -> f64 {
-        self.placed.iter().map(|b| b.object.weight).sum()
+        self.reserved_weight + self.placed.iter().map(|b| b.object.weight).sum::<f64>()
     }
 
     /// Calculates the remaining available weight.
@@ -322,7 +599,7 @@ impl Container {
     /// # Returns
     /// Sum of the volumes of all placed objects
     pub fn used_volume(&self) -> f64 {
-        self.placed.iter().map(|b| b.object.volume()).sum()
+        self.placed.iter().map(PlacedBox::volume).sum()
     }
 
     /// Calculates the total volume of the container.
@@ -378,10 +655,26 @@ impl Container {
     /// # Returns
     /// `true` if the object theoretically fits, otherwise `false`
     pub fn can_fit(&self, b: &Box3D) -> bool {
-        self.remaining_weight() + EPSILON_GENERAL >= b.weight
-            && b.dims.0 <= self.dims.0 + EPSILON_GENERAL
-            && b.dims.1 <= self.dims.1 + EPSILON_GENERAL
-            && b.dims.2 <= self.dims.2 + EPSILON_GENERAL
+        self.can_fit_with(b, EPSILON_GENERAL, EPSILON_GENERAL)
+    }
+
+    /// Like [`Self::can_fit`], with explicit tolerances.
+    ///
+    /// # Parameters
+    /// * `b` - The object to check
+    /// * `dim_epsilon` - Slack on every dimension, e.g. `PackingConfig::general_epsilon`
+    /// * `weight_tolerance` - Slack on the weight limit, e.g. `PackingConfig::weight_tolerance()`
+    pub fn can_fit_with(&self, b: &Box3D, dim_epsilon: f64, weight_tolerance: f64) -> bool {
+        !self.is_at_item_limit()
+            && self.remaining_weight() + weight_tolerance >= b.weight
+            && b.dims.0 <= self.dims.0 + dim_epsilon
+            && b.dims.1 <= self.dims.1 + dim_epsilon
+            && b.dims.2 <= self.dims.2 + dim_epsilon
+    }
+
+    /// Returns `true` if the container already holds `max_items` objects.
+    pub fn is_at_item_limit(&self) -> bool {
+        self.max_items.is_some_and(|max| self.placed.len() >= max)
     }
 
     /// Converts the container dimensions to a Vec3.
@@ -398,6 +691,65 @@ impl Container {
         (self.dims.0 / 2.0, self.dims.1 / 2.0)
     }
 
+    /// Places `object` at `position` after checking the container invariants.
+    ///
+    /// The object must lie within the walls and below the ceiling minus `config.top_clearance`,
+    /// fit into the remaining weight capacity, and not intersect any placed object or reserved
+    /// space. Dimension tolerances use `config.general_epsilon`, the weight check
+    /// `config.weight_tolerance()`. Stability rules are not checked; the optimizer keeps its own
+    /// unchecked fast path.
+    ///
+    /// # Returns
+    /// `Ok(())` once the object was added, otherwise the first violated invariant
+    pub fn try_place(
+        &mut self,
+        object: Box3D,
+        position: (f64, f64, f64),
+        config: &PackingConfig,
+    ) -> Result<(), PlacementError> {
+        if let Some(max) = self.max_items.filter(|_| self.is_at_item_limit()) {
+            return Err(PlacementError::ItemLimit { max });
+        }
+        let eps = config.general_epsilon;
+        let (x, y, z) = position;
+        let (w, d, h) = object.dims;
+        let within_walls = x >= -eps
+            && y >= -eps
+            && z >= -eps
+            && x + w <= self.dims.0 + eps
+            && y + d <= self.dims.1 + eps
+            && z + h <= self.ceiling_between(x, x + w) - config.top_clearance + eps;
+        if !within_walls {
+            return Err(PlacementError::OutOfBounds);
+        }
+        if !self.allows_stacking_at(position, object.dims, eps) {
+            return Err(PlacementError::FloorOnlyRegion);
+        }
+
+        let remaining = self.remaining_weight();
+        if object.weight > remaining + config.weight_tolerance() {
+            return Err(PlacementError::OverWeight {
+                weight: object.weight,
+                remaining,
+            });
+        }
+
+        let candidate = PlacedBox::new(object, position);
+        if let Some(other) = self
+            .placed
+            .iter()
+            .chain(&self.reserved)
+            .find(|p| intersects(p, &candidate))
+        {
+            return Err(PlacementError::Overlap {
+                other: other.object.id,
+            });
+        }
+
+        self.placed.push(candidate);
+        Ok(())
+    }
+
     /// Creates a new empty container with the same properties.
     ///
     /// # Returns
@@ -410,6 +762,14 @@ impl Container {
             placed: Vec::new(),
             template_id: self.template_id,
             label: self.label.clone(),
+            height_profile: self.height_profile.clone(),
+            reserved: self.reserved.clone(),
+            wall_thickness: self.wall_thickness,
+            origin: self.origin,
+            reserved_weight: self.reserved_weight,
+            floor_only_region: self.floor_only_region,
+            max_items: self.max_items,
+            cost: self.cost,
         }
     }
 
@@ -429,6 +789,22 @@ pub struct ContainerBlueprint {
     pub label: Option<String>,
     pub dims: (f64, f64, f64),
     pub max_weight: f64,
+    pub height_profile: Vec<(f64, f64)>,
+    pub reserved: Vec<PlacedBox>,
+    /// Wall thickness already subtracted from `dims`; `0` if `dims` are inner dimensions.
+    pub wall_thickness: f64,
+    /// Offset added to reported positions; packing itself always starts at the origin.
+    pub origin: (f64, f64, f64),
+    /// Weight carried from the start without occupying space; counts toward `max_weight`.
+    pub reserved_weight: f64,
+    /// Position in a fixed fill order; ranked templates are exhausted in ascending order.
+    pub fill_rank: Option<usize>,
+    /// `(x, y, width, depth)` rectangle where objects must rest on the floor.
+    pub floor_only_region: Option<(f64, f64, f64, f64)>,
+    /// Maximum number of objects per container of this type.
+    pub max_items: Option<usize>,
+    /// Price of using one container of this type.
+    pub cost: Option<f64>,
 }
 
 impl ContainerBlueprint {
@@ -449,9 +825,178 @@ impl ContainerBlueprint {
             label,
             dims,
             max_weight,
+            height_profile: Vec::new(),
+            reserved: Vec::new(),
+            wall_thickness: 0.0,
+            origin: (0.0, 0.0, 0.0),
+            reserved_weight: 0.0,
+            fill_rank: None,
+            floor_only_region: None,
+            max_items: None,
+            cost: None,
         })
     }
 
+    /// Treats `dims` as outer dimensions and shrinks them to the usable interior.
+    ///
+    /// Every axis loses `2 × thickness`. Apply this before [`Self::with_height_profile`], since
+    /// the profile is validated against the interior.
+    pub fn with_wall_thickness(mut self, thickness: f64) -> Result<Self, ValidationError> {
+        if !thickness.is_finite() || thickness < 0.0 {
+            return Err(ValidationError::InvalidDimension(format!(
+                "Wall thickness must be non-negative, got: {}",
+                thickness
+            )));
+        }
+        let (w, d, h) = self.dims;
+        let inner = (
+            w - 2.0 * thickness,
+            d - 2.0 * thickness,
+            h - 2.0 * thickness,
+        );
+        if inner.0 <= 0.0 || inner.1 <= 0.0 || inner.2 <= 0.0 {
+            return Err(ValidationError::InvalidDimension(format!(
+                "Wall thickness {} leaves no interior in a {}x{}x{} container",
+                thickness, w, d, h
+            )));
+        }
+        self.dims = inner;
+        self.wall_thickness = thickness;
+        Ok(self)
+    }
+
+    /// Rejects templates whose usable volume is below `min_volume` (`0` disables the check).
+    ///
+    /// Catches degenerate containers such as a mistyped `0.1` height that still pass the
+    /// per-dimension validation. Apply this after [`Self::with_wall_thickness`].
+    pub fn with_min_volume(self, min_volume: f64) -> Result<Self, ValidationError> {
+        let volume = self.volume();
+        if min_volume > 0.0 && volume < min_volume {
+            let (w, d, h) = self.dims;
+            return Err(ValidationError::InvalidConfiguration(format!(
+                "Container volume {} ({}x{}x{}) is below the minimum of {}",
+                volume, w, d, h, min_volume
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Rejects templates whose longest dimension exceeds `max_ratio` times the shortest one
+    /// (`0` disables the check).
+    ///
+    /// A spec such as `1000x1x1` is almost certainly a data error. Apply this after
+    /// [`Self::with_wall_thickness`].
+    pub fn with_max_aspect_ratio(self, max_ratio: f64) -> Result<Self, ValidationError> {
+        let (w, d, h) = self.dims;
+        let ratio = w.max(d).max(h) / w.min(d).min(h);
+        if max_ratio > 0.0 && ratio > max_ratio {
+            return Err(ValidationError::InvalidConfiguration(format!(
+                "Container aspect ratio {} ({}x{}x{}) exceeds the maximum of {}",
+                ratio, w, d, h, max_ratio
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Reserves part of the weight capacity, e.g. for dunnage, without occupying space.
+    ///
+    /// The reserved weight must be non-negative and below `max_weight`.
+    pub fn with_reserved_weight(mut self, weight: f64) -> Result<Self, ValidationError> {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(ValidationError::InvalidWeight(format!(
+                "Reserved weight must be non-negative, got: {}",
+                weight
+            )));
+        }
+        if weight >= self.max_weight {
+            return Err(ValidationError::InvalidWeight(format!(
+                "Reserved weight {} must be below the maximum weight of {}",
+                weight, self.max_weight
+            )));
+        }
+        self.reserved_weight = weight;
+        Ok(self)
+    }
+
+    /// Returns the weight capacity left for objects after the reserved weight.
+    pub fn available_weight(&self) -> f64 {
+        self.max_weight - self.reserved_weight
+    }
+
+    /// Forbids stacking inside the `(x, y, width, depth)` rectangle, e.g. near the door.
+    ///
+    /// Objects whose footprint overlaps the region must rest on the floor. The rectangle must be
+    /// finite with a positive width and depth.
+    pub fn with_floor_only_region(
+        mut self,
+        region: (f64, f64, f64, f64),
+    ) -> Result<Self, ValidationError> {
+        let (x, y, width, depth) = region;
+        if !(x.is_finite() && y.is_finite()) {
+            return Err(ValidationError::InvalidDimension(format!(
+                "Floor-only region must be finite, got: {:?}",
+                region
+            )));
+        }
+        validate_dimension(width, "Floor-only region width")?;
+        validate_dimension(depth, "Floor-only region depth")?;
+        self.floor_only_region = Some(region);
+        Ok(self)
+    }
+
+    /// Limits every container of this type to `max` placed objects, regardless of volume.
+    ///
+    /// The limit must be at least 1.
+    pub fn with_max_items(mut self, max: usize) -> Result<Self, ValidationError> {
+        if max == 0 {
+            return Err(ValidationError::InvalidConfiguration(
+                "Container max_items must be at least 1".to_string(),
+            ));
+        }
+        self.max_items = Some(max);
+        Ok(self)
+    }
+
+    /// Sets the price of using one container of this type.
+    ///
+    /// The cost must be finite and non-negative.
+    pub fn with_cost(mut self, cost: f64) -> Result<Self, ValidationError> {
+        if !cost.is_finite() || cost < 0.0 {
+            return Err(ValidationError::InvalidConfiguration(format!(
+                "Container cost must be a finite, non-negative number, got: {}",
+                cost
+            )));
+        }
+        self.cost = Some(cost);
+        Ok(self)
+    }
+
+    /// Sets the reference point that reported positions are measured from.
+    ///
+    /// This is purely an output transform; see [`Container::origin`].
+    pub fn with_origin(mut self, origin: (f64, f64, f64)) -> Result<Self, ValidationError> {
+        if !(origin.0.is_finite() && origin.1.is_finite() && origin.2.is_finite()) {
+            return Err(ValidationError::InvalidDimension(format!(
+                "Origin offset must be finite, got: {:?}",
+                origin
+            )));
+        }
+        self.origin = origin;
+        Ok(self)
+    }
+
+    /// Applies a stepped ceiling profile (e.g. a vehicle roof that drops toward the rear).
+    ///
+    /// Steps are `(x_threshold, max_height)` pairs; see [`Container::ceiling_between`].
+    pub fn with_height_profile(
+        mut self,
+        profile: Vec<(f64, f64)>,
+    ) -> Result<Self, ValidationError> {
+        validate_height_profile(&profile, self.dims)?;
+        self.height_profile = profile;
+        Ok(self)
+    }
+
     /// Instantiates an empty container based on this template.
     pub fn instantiate(&self) -> Container {
         Container {
@@ -460,17 +1005,54 @@ impl ContainerBlueprint {
             placed: Vec::new(),
             template_id: Some(self.id),
             label: self.label.clone(),
+            height_profile: self.height_profile.clone(),
+            reserved: self.reserved.clone(),
+            wall_thickness: self.wall_thickness,
+            origin: self.origin,
+            reserved_weight: self.reserved_weight,
+            floor_only_region: self.floor_only_region,
+            max_items: self.max_items,
+            cost: self.cost,
         }
     }
 
+    /// Instantiates the `ordinal`-th (1-based) container of this template.
+    ///
+    /// Expands the label placeholders `{n}` (the ordinal) and `{template}` (the template id), so
+    /// a label like `"Crate A #{n}"` yields `"Crate A #3"` for the third container.
+    pub fn instantiate_nth(&self, ordinal: usize) -> Container {
+        let mut container = self.instantiate();
+        container.label = self.label.as_deref().map(|label| {
+            label
+                .replace("{n}", &ordinal.to_string())
+                .replace("{template}", &self.id.to_string())
+        });
+        container
+    }
+
+    /// Returns `true` if a reserved block at `position` lies completely inside this template.
+    pub fn contains_region(&self, position: (f64, f64, f64), dims: (f64, f64, f64)) -> bool {
+        position.0 >= -EPSILON_GENERAL
+            && position.1 >= -EPSILON_GENERAL
+            && position.2 >= -EPSILON_GENERAL
+            && position.0 + dims.0 <= self.dims.0 + EPSILON_GENERAL
+            && position.1 + dims.1 <= self.dims.1 + EPSILON_GENERAL
+            && position.2 + dims.2 <= self.dims.2 + EPSILON_GENERAL
+    }
+
     /// Checks if the object can basically fit based on dimensions and weight.
     ///
     /// Uses the global tolerance constant (DRY principle).
     pub fn can_fit(&self, object: &Box3D) -> bool {
-        object.weight <= self.max_weight + EPSILON_GENERAL
-            && object.dims.0 <= self.dims.0 + EPSILON_GENERAL
-            && object.dims.1 <= self.dims.1 + EPSILON_GENERAL
-            && object.dims.2 <= self.dims.2 + EPSILON_GENERAL
+        self.can_fit_with(object, EPSILON_GENERAL, EPSILON_GENERAL)
+    }
+
+    /// Like [`Self::can_fit`], with the same explicit tolerances as [`Container::can_fit_with`].
+    pub fn can_fit_with(&self, object: &Box3D, dim_epsilon: f64, weight_tolerance: f64) -> bool {
+        object.weight <= self.available_weight() + weight_tolerance
+            && object.dims.0 <= self.dims.0 + dim_epsilon
+            && object.dims.1 <= self.dims.1 + dim_epsilon
+            && object.dims.2 <= self.dims.2 + dim_epsilon
     }
 
     /// Returns the volume of the template.
@@ -487,6 +1069,12 @@ impl ContainerBlueprint {
     }
 }
 
+/// Adds the walls on both sides of every axis back onto inner dimensions.
+fn outer_dims(inner: (f64, f64, f64), wall_thickness: f64) -> (f64, f64, f64) {
+    let walls = 2.0 * wall_thickness;
+    (inner.0 + walls, inner.1 + walls, inner.2 + walls)
+}
+
 /// Implementation of the Dimensional trait for Container.
 impl Dimensional for Container {
     fn dimensions(&self) -> Vec3 {
@@ -513,11 +1101,109 @@ mod tests {
                 id,
                 dims,
                 weight: 1.0,
+                ..Default::default()
             },
             position: pos,
         });
     }
 
+    #[test]
+    fn try_place_adds_a_valid_object() {
+        let mut container = Container::new((10.0, 10.0, 10.0), 10.0).unwrap();
+        let config = PackingConfig::default();
+        let object = Box3D::new(1, (5.0, 5.0, 5.0), 4.0).unwrap();
+
+        assert_eq!(
+            container.try_place(object.clone(), (0.0, 0.0, 0.0), &config),
+            Ok(())
+        );
+        // Touching faces do not count as an overlap.
+        assert_eq!(
+            container.try_place(Box3D { id: 2, ..object }, (5.0, 0.0, 0.0), &config),
+            Ok(())
+        );
+        assert_eq!(container.placed.len(), 2);
+    }
+
+    #[test]
+    fn try_place_rejects_overlap_weight_and_bounds_violations() {
+        let mut container = Container::new((10.0, 10.0, 10.0), 10.0).unwrap();
+        let config = PackingConfig::default();
+        place(&mut container, 1, (5.0, 5.0, 5.0), (0.0, 0.0, 0.0));
+
+        let object = Box3D::new(2, (5.0, 5.0, 5.0), 1.0).unwrap();
+        assert_eq!(
+            container.try_place(object.clone(), (2.0, 2.0, 0.0), &config),
+            Err(PlacementError::Overlap { other: 1 })
+        );
+        assert_eq!(
+            container.try_place(object.clone(), (6.0, 0.0, 0.0), &config),
+            Err(PlacementError::OutOfBounds)
+        );
+        assert_eq!(
+            container.try_place(object.clone(), (-1.0, 0.0, 0.0), &config),
+            Err(PlacementError::OutOfBounds)
+        );
+
+        let heavy = Box3D {
+            weight: 9.5,
+            ..object
+        };
+        assert_eq!(
+            container.try_place(heavy, (5.0, 0.0, 0.0), &config),
+            Err(PlacementError::OverWeight {
+                weight: 9.5,
+                remaining: 9.0
+            })
+        );
+        assert_eq!(container.placed.len(), 1);
+    }
+
+    #[test]
+    fn try_place_keeps_the_top_clearance_free() {
+        let mut container = Container::new((10.0, 10.0, 10.0), 10.0).unwrap();
+        let config = PackingConfig::builder().top_clearance(3.0).build();
+        let object = Box3D::new(1, (5.0, 5.0, 8.0), 1.0).unwrap();
+
+        assert_eq!(
+            container.try_place(object.clone(), (0.0, 0.0, 0.0), &config),
+            Err(PlacementError::OutOfBounds)
+        );
+        let flat = Box3D {
+            dims: (5.0, 5.0, 7.0),
+            ..object
+        };
+        assert_eq!(container.try_place(flat, (0.0, 0.0, 0.0), &config), Ok(()));
+    }
+
+    #[test]
+    fn strict_weight_removes_the_slack_from_every_weight_check() {
+        let blueprint = ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 10.0).unwrap();
+        let mut container = blueprint.instantiate();
+        let lenient = PackingConfig::default();
+        let strict = PackingConfig::builder().strict_weight(true).build();
+        let object = Box3D::new(1, (5.0, 5.0, 5.0), 10.0 + lenient.general_epsilon / 2.0).unwrap();
+
+        let fits = |config: &PackingConfig| {
+            let (eps, tolerance) = (config.general_epsilon, config.weight_tolerance());
+            (
+                blueprint.can_fit_with(&object, eps, tolerance),
+                container.can_fit_with(&object, eps, tolerance),
+            )
+        };
+        assert_eq!(fits(&lenient), (true, true));
+        assert_eq!(fits(&strict), (false, false));
+        assert!(blueprint.can_fit(&object) && container.can_fit(&object));
+        assert!(matches!(
+            container.try_place(object.clone(), (0.0, 0.0, 0.0), &strict),
+            Err(PlacementError::OverWeight { .. })
+        ));
+        assert_eq!(
+            container.try_place(object, (0.0, 0.0, 0.0), &lenient),
+            Ok(())
+        );
+    }
+
     #[test]
     fn free_volume_is_container_volume_minus_used_volume() {
         let mut container = Container::new((10.0, 10.0, 10.0), 100.0).unwrap();
@@ -555,4 +1241,115 @@ mod tests {
         // Void percentage and utilization percentage must always add up to 100%.
         assert!((fill.void_volume_percent + container.utilization_percent() - 100.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn height_profile_lowers_ceiling_toward_the_rear() {
+        let container = Container::new((100.0, 50.0, 50.0), 100.0)
+            .unwrap()
+            .with_height_profile(vec![(50.0, 30.0), (80.0, 20.0)])
+            .unwrap();
+
+        assert!((container.ceiling_between(0.0, 40.0) - 50.0).abs() < EPS);
+        assert!((container.ceiling_between(0.0, 50.0) - 50.0).abs() < EPS);
+        assert!((container.ceiling_between(40.0, 60.0) - 30.0).abs() < EPS);
+        assert!((container.ceiling_between(60.0, 90.0) - 20.0).abs() < EPS);
+        assert!((container.ceiling_between(85.0, 100.0) - 20.0).abs() < EPS);
+    }
+
+    #[test]
+    fn height_profile_rejects_unsorted_or_out_of_bounds_steps() {
+        let blueprint = || ContainerBlueprint::new(0, None, (100.0, 50.0, 50.0), 100.0).unwrap();
+
+        assert!(
+            blueprint()
+                .with_height_profile(vec![(60.0, 30.0), (40.0, 20.0)])
+                .is_err()
+        );
+        assert!(
+            blueprint()
+                .with_height_profile(vec![(120.0, 30.0)])
+                .is_err()
+        );
+        assert!(blueprint().with_height_profile(vec![(60.0, 80.0)]).is_err());
+        assert!(blueprint().with_height_profile(vec![(60.0, 0.0)]).is_err());
+        assert!(
+            blueprint()
+                .with_height_profile(vec![(0.0, 40.0), (60.0, 30.0)])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn instantiate_nth_expands_label_placeholders() {
+        let blueprint = ContainerBlueprint::new(
+            4,
+            Some("Crate A #{n} (type {template})".to_string()),
+            (10.0, 10.0, 10.0),
+            10.0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            blueprint.instantiate_nth(3).label.as_deref(),
+            Some("Crate A #3 (type 4)")
+        );
+        // Plain labels and unlabeled templates are left untouched.
+        let plain =
+            ContainerBlueprint::new(0, Some("Crate".to_string()), (1.0, 1.0, 1.0), 1.0).unwrap();
+        assert_eq!(plain.instantiate_nth(2).label.as_deref(), Some("Crate"));
+        let unlabeled = ContainerBlueprint::new(0, None, (1.0, 1.0, 1.0), 1.0).unwrap();
+        assert_eq!(unlabeled.instantiate_nth(2).label, None);
+    }
+
+    #[test]
+    fn wall_thickness_shrinks_interior_and_keeps_outer_dims() {
+        let blueprint = ContainerBlueprint::new(0, None, (20.0, 12.0, 10.0), 10.0)
+            .unwrap()
+            .with_wall_thickness(1.0)
+            .unwrap();
+        assert_eq!(blueprint.dims, (18.0, 10.0, 8.0));
+        assert_eq!(blueprint.instantiate().outer_dims(), (20.0, 12.0, 10.0));
+
+        let too_thick = ContainerBlueprint::new(0, None, (20.0, 12.0, 10.0), 10.0)
+            .unwrap()
+            .with_wall_thickness(5.0);
+        assert!(matches!(
+            too_thick,
+            Err(ValidationError::InvalidDimension(_))
+        ));
+        assert!(
+            ContainerBlueprint::new(0, None, (1.0, 1.0, 1.0), 1.0)
+                .unwrap()
+                .with_wall_thickness(-0.1)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn container_below_min_volume_is_rejected() {
+        let flat = || ContainerBlueprint::new(0, None, (10.0, 10.0, 0.1), 10.0).unwrap();
+
+        match flat().with_min_volume(100.0) {
+            Err(ValidationError::InvalidConfiguration(msg)) => {
+                assert!(msg.contains("Container volume 10"), "{msg}")
+            }
+            other => panic!("expected InvalidConfiguration, got {other:?}"),
+        }
+        assert!(flat().with_min_volume(5.0).is_ok());
+        assert!(flat().with_min_volume(0.0).is_ok());
+    }
+
+    #[test]
+    fn elongated_container_is_rejected_above_max_aspect_ratio() {
+        let rod = || ContainerBlueprint::new(0, None, (1000.0, 1.0, 1.0), 10.0).unwrap();
+
+        match rod().with_max_aspect_ratio(100.0) {
+            Err(ValidationError::InvalidConfiguration(msg)) => {
+                assert!(msg.contains("aspect ratio 1000"), "{msg}")
+            }
+            other => panic!("expected InvalidConfiguration, got {other:?}"),
+        }
+        assert!(rod().with_max_aspect_ratio(0.0).is_ok());
+        assert!(rod().with_max_aspect_ratio(1000.0).is_ok());
+    }
 }
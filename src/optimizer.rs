@@ -24,14 +24,15 @@
 //!    - Iterate over all Z-layers (floor + tops of placed objects)
 //!    - Grid search on X/Y axis with configurable step size
 //!    - Evaluation by `PlacementScore { z, instability, support_ratio,
-//!      support_centroid_offset_ratio, support_contact_count, y, x,
-//!      balance_shift, balance }`
+//!      support_centroid_offset_ratio, support_contact_count,
+//!      category_contacts, y, x, balance_shift, balance }`
 //!
 //! 5. **Stability Checks**: Each candidate position must pass:
 //!    - No collision with existing objects
 //!    - Minimum support (`support_ratio`) satisfied
 //!    - Weight hierarchy maintained (heavy under light)
 //!    - Center of gravity supported
+//!    - Overhang beyond supports within `max_overhang` (if set)
 //!    - Balance within limits
 //!
 //! 6. **Multi-Container**: When space is insufficient, a new container is created
@@ -57,13 +58,103 @@
 //! ```
 
 use std::cmp::Ordering;
+use std::time::Instant;
 
-use crate::geometry::{intersects, overlap_1d, point_inside};
-use crate::model::{Box3D, Container, ContainerBlueprint, PlacedBox};
+use crate::geometry::{intersects, overlap_1d, point_inside, rests_on, touches_sideways};
+use crate::model::{Box3D, Container, ContainerBlueprint, PlacedBox, ValidationError};
 use crate::packaging::{PackagingAccumulator, PackagingFill, PackagingSummary};
-use crate::types::Dimensional;
+use crate::types::{Dimensional, Weighted};
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// Top-level goal that decides between otherwise valid placements.
+///
+/// `Compactness` keeps stacks as low as possible and only uses balance as a late tie-breaker.
+/// `Balance` compares the resulting center-of-gravity offset first, accepting a higher
+/// placement when it keeps the container better centered.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PrimaryObjective {
+    #[default]
+    Compactness,
+    Balance,
+}
+
+/// Where heavy objects should end up in the container footprint.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MassPlacement {
+    /// Only the overall center of gravity matters (see [`PrimaryObjective`]).
+    #[default]
+    Balanced,
+    /// Pulls objects toward the XY center, weighted by mass, e.g. for forklift handling.
+    Centered,
+}
+
+/// Side of the container from which objects accumulate along y.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadDirection {
+    /// Prefers low y, so the load grows from the front (`y = 0`) to the back.
+    #[default]
+    FrontToBack,
+    /// Prefers high y, e.g. for containers loaded from the rear.
+    BackToFront,
+}
+
+/// Order in which the containers of a packing result are listed.
+///
+/// The response numbers containers by their final position, so sorting also renumbers them.
+/// Sorting is stable: containers that compare equal keep their creation order.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerOrder {
+    /// Order in which the containers were opened.
+    #[default]
+    Creation,
+    /// Highest volume utilization first.
+    Utilization,
+    /// Heaviest total load first.
+    Weight,
+}
+
+/// Order in which container templates are tried when a new container must be opened.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplatePreference {
+    /// Smallest volume first, keeping small orders in small containers.
+    #[default]
+    SmallestFirst,
+    /// Largest volume first, consolidating bulk orders into fewer containers.
+    LargestFirst,
+}
+
+/// Policy for picking the container template when a new container must be opened.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NewContainerPolicy {
+    /// First template in [`TemplatePreference`] order that fits the current object.
+    #[default]
+    SmallestFitting,
+    /// Largest template that fits the current object, regardless of the template preference.
+    LargestAvailable,
+    /// Smallest template whose volume covers all objects still to be placed (largest otherwise).
+    BestFitForRemaining,
+}
+
+/// Named preset that adjusts several packing settings at once.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PackingProfile {
+    /// Keeps the configured settings.
+    #[default]
+    Default,
+    /// Dense layouts: compactness first, followed by the settling and compaction passes.
+    Compact,
+    /// Maximum stability margins at the cost of density; see [`PackingConfig::conservative`].
+    Conservative,
+}
+
 /// Configuration for the packing algorithm.
 ///
 /// Contains all tolerances and limits for controlling the optimization behavior.
@@ -79,10 +170,80 @@ pub struct PackingConfig {
     pub general_epsilon: f64,
     /// Maximum allowed deviation of center of gravity from center point (as ratio of diagonal)
     pub balance_limit_ratio: f64,
+    /// Separate limit for the side-to-side (x) offset as ratio of the half width (`None` = combined)
+    pub balance_limit_ratio_x: Option<f64>,
+    /// Separate limit for the front-to-back (y) offset as ratio of the half depth (`None` = combined)
+    pub balance_limit_ratio_y: Option<f64>,
     /// Relative tolerance for pre-grouping by footprint to reduce backtracking
     pub footprint_cluster_tolerance: f64,
+    /// Applies the footprint pre-grouping; `false` keeps the plain weight/volume order
+    pub cluster_footprints: bool,
     /// Allows rotating objects to test alternative orientations
     pub allow_item_rotation: bool,
+    /// Pre-opens this many containers and spreads objects across them (emptiest first)
+    pub target_container_count: Option<usize>,
+    /// Upper bound for grid positions generated per axis (0 = unlimited)
+    pub max_axis_positions: usize,
+    /// Requires the average weight per Z-layer to be non-increasing from the floor upwards
+    pub strict_layering: bool,
+    /// Decides whether low placements (compactness) or centered loads (balance) win first
+    pub primary_objective: PrimaryObjective,
+    /// Whether heavy objects are pulled toward the XY center of the container
+    pub mass_placement: MassPlacement,
+    /// Side from which objects accumulate along y
+    pub load_direction: LoadDirection,
+    /// Longest allowed unsupported protrusion of a stacked box's base beyond its supports
+    pub max_overhang: Option<f64>,
+    /// Additional randomized packing runs; the best of all runs is returned (0 = single run)
+    pub restarts: usize,
+    /// Relative tolerance for collapsing near-identical container templates (0 = disabled)
+    pub template_merge_tolerance: f64,
+    /// Interprets `general_epsilon` and `height_epsilon` relative to the median object dimension
+    pub relative_tolerance: bool,
+    /// Minimum share of the container base the floor layer should cover (diagnostics only)
+    pub floor_min_coverage: Option<f64>,
+    /// Order in which the result lists its containers
+    pub container_order: ContainerOrder,
+    /// Order in which container templates are tried when opening a new container
+    pub template_preference: TemplatePreference,
+    /// Which template a new container is opened from
+    pub new_container_policy: NewContainerPolicy,
+    /// Rejects objects larger than this share of the largest template's volume up front
+    pub max_object_volume_fraction: Option<f64>,
+    /// Headroom kept free below every container ceiling, e.g. for lid closure (0 = none)
+    pub top_clearance: f64,
+    /// Allows boxes of equal weight (within `general_epsilon`) to stack on each other
+    pub allow_equal_weight_stacking: bool,
+    /// Rejects any object exceeding a weight limit, dropping the `general_epsilon` slack
+    pub strict_weight: bool,
+    /// Volume utilization (0.0 to 1.0) at which a container stops receiving further objects
+    pub target_utilization: Option<f64>,
+    /// Measures support on a sample grid with this many points per axis (0 = exact overlaps)
+    pub support_samples_per_axis: usize,
+    /// Records phase durations in [`PackingResult::timings`]
+    pub profile: bool,
+    /// Slides packed objects toward the loading corner after packing to close grid gaps
+    pub compact: bool,
+    /// Lowers floating objects onto the highest surface beneath them after packing
+    pub settle: bool,
+    /// Prefers positions beside already placed objects of the same `category`
+    pub cluster_by_category: bool,
+    /// Prefers positions touching the container walls among otherwise equal positions
+    pub prefer_wall_contact: bool,
+    /// Falls back to a floor position before an elevated one when no position is within limits
+    pub prefer_floor_fallback: bool,
+    /// Tries the top of a placed object with the same footprint before the general search
+    pub prefer_column_stacking: bool,
+    /// Counts the failed check of every rejected candidate position for unplaced objects
+    pub track_rejections: bool,
+    /// Recomputes live container diagnostics only every this many placements (1 = every one)
+    pub diagnostics_interval: usize,
+    /// Emits a full [`PackEvent::Snapshot`] of every container each this many placements
+    pub snapshot_every: Option<usize>,
+    /// Emits a [`PackEvent::Progress`] every this many processed objects
+    pub progress_every: Option<usize>,
+    /// Emits a [`PackEvent::Progress`] once this many milliseconds passed since the last one
+    pub progress_interval_ms: Option<u64>,
 }
 
 impl PackingConfig {
@@ -91,14 +252,97 @@ impl PackingConfig {
     pub const DEFAULT_HEIGHT_EPSILON: f64 = 1e-3;
     pub const DEFAULT_GENERAL_EPSILON: f64 = 1e-6;
     pub const DEFAULT_BALANCE_LIMIT_RATIO: f64 = 0.45;
+    pub const DEFAULT_BALANCE_LIMIT_RATIO_X: Option<f64> = None;
+    pub const DEFAULT_BALANCE_LIMIT_RATIO_Y: Option<f64> = None;
     pub const DEFAULT_FOOTPRINT_CLUSTER_TOLERANCE: f64 = 0.15;
     pub const DEFAULT_ALLOW_ITEM_ROTATION: bool = false;
+    pub const DEFAULT_TARGET_CONTAINER_COUNT: Option<usize> = None;
+    pub const DEFAULT_MAX_AXIS_POSITIONS: usize = 10_000;
+    pub const DEFAULT_STRICT_LAYERING: bool = false;
+    pub const DEFAULT_PRIMARY_OBJECTIVE: PrimaryObjective = PrimaryObjective::Compactness;
+    pub const DEFAULT_MASS_PLACEMENT: MassPlacement = MassPlacement::Balanced;
+    pub const DEFAULT_LOAD_DIRECTION: LoadDirection = LoadDirection::FrontToBack;
+    pub const DEFAULT_MAX_OVERHANG: Option<f64> = None;
+    pub const DEFAULT_RESTARTS: usize = 0;
+    /// Upper bound for `restarts`, since every restart repeats the complete packing run.
+    pub const MAX_RESTARTS: usize = 32;
+    pub const DEFAULT_TEMPLATE_MERGE_TOLERANCE: f64 = 0.0;
+    pub const DEFAULT_RELATIVE_TOLERANCE: bool = false;
+    pub const DEFAULT_FLOOR_MIN_COVERAGE: Option<f64> = None;
+    pub const DEFAULT_CONTAINER_ORDER: ContainerOrder = ContainerOrder::Creation;
+    pub const DEFAULT_TEMPLATE_PREFERENCE: TemplatePreference = TemplatePreference::SmallestFirst;
+    pub const DEFAULT_NEW_CONTAINER_POLICY: NewContainerPolicy =
+        NewContainerPolicy::SmallestFitting;
+    pub const DEFAULT_MAX_OBJECT_VOLUME_FRACTION: Option<f64> = None;
+    pub const DEFAULT_TOP_CLEARANCE: f64 = 0.0;
+    pub const DEFAULT_ALLOW_EQUAL_WEIGHT_STACKING: bool = true;
+    pub const DEFAULT_STRICT_WEIGHT: bool = false;
+    pub const DEFAULT_TARGET_UTILIZATION: Option<f64> = None;
+    pub const DEFAULT_SUPPORT_SAMPLES_PER_AXIS: usize = 0;
+    /// Upper bound for `support_samples_per_axis`; every candidate position checks n² points.
+    pub const MAX_SUPPORT_SAMPLES_PER_AXIS: usize = 256;
+    pub const DEFAULT_PROFILE: bool = false;
+    pub const DEFAULT_COMPACT: bool = false;
+    pub const DEFAULT_SETTLE: bool = false;
+    pub const DEFAULT_CLUSTER_BY_CATEGORY: bool = false;
+    pub const DEFAULT_CLUSTER_FOOTPRINTS: bool = true;
+    pub const DEFAULT_PREFER_WALL_CONTACT: bool = false;
+    pub const DEFAULT_PREFER_FLOOR_FALLBACK: bool = false;
+    pub const DEFAULT_PREFER_COLUMN_STACKING: bool = false;
+    pub const DEFAULT_TRACK_REJECTIONS: bool = false;
+    pub const DEFAULT_DIAGNOSTICS_INTERVAL: usize = 1;
+    pub const DEFAULT_SNAPSHOT_EVERY: Option<usize> = None;
+    pub const DEFAULT_PROGRESS_EVERY: Option<usize> = None;
+    pub const DEFAULT_PROGRESS_INTERVAL_MS: Option<u64> = None;
+    /// Upper bound for compaction passes per container; each pass visits every object once.
+    pub const MAX_COMPACTION_PASSES: usize = 8;
+
+    /// Support ratio required by [`PackingProfile::Conservative`].
+    pub const CONSERVATIVE_SUPPORT_RATIO: f64 = 0.9;
+    /// Balance limit applied by [`PackingProfile::Conservative`].
+    pub const CONSERVATIVE_BALANCE_LIMIT_RATIO: f64 = 0.15;
 
     /// Creates a builder for custom configuration.
     pub fn builder() -> PackingConfigBuilder {
         PackingConfigBuilder::default()
     }
 
+    /// Preset for safety-critical loads that maximizes stability margins.
+    ///
+    /// Requires 90% support, forbids any overhang beyond the supports, tightens the balance
+    /// limit, keeps every object upright and compares balance before height when scoring
+    /// positions.
+    pub fn conservative() -> Self {
+        Self::builder().conservative().build()
+    }
+
+    /// Applies the settings of `profile` on top of this configuration.
+    pub fn with_profile(self, profile: PackingProfile) -> Self {
+        let builder = PackingConfigBuilder { config: self };
+        match profile {
+            PackingProfile::Default => builder,
+            PackingProfile::Compact => builder
+                .primary_objective(PrimaryObjective::Compactness)
+                .settle(true)
+                .compact(true)
+                .allow_item_rotation(true),
+            PackingProfile::Conservative => builder.conservative(),
+        }
+        .build()
+    }
+
+    /// Slack by which an object may exceed a weight limit: `general_epsilon`, or `0` with
+    /// [`Self::strict_weight`].
+    ///
+    /// An object weighing exactly the remaining capacity always fits.
+    pub fn weight_tolerance(&self) -> f64 {
+        if self.strict_weight {
+            0.0
+        } else {
+            self.general_epsilon
+        }
+    }
+
     /// Normalizes numerically invalid runtime inputs for the packing pipeline.
     ///
     /// `PackingConfig` remains publicly constructible, so packing re-sanitizes the active
@@ -112,10 +356,69 @@ impl PackingConfig {
             sanitize_nonnegative_finite(self.general_epsilon, Self::DEFAULT_GENERAL_EPSILON);
         self.balance_limit_ratio =
             sanitize_ratio(self.balance_limit_ratio, Self::DEFAULT_BALANCE_LIMIT_RATIO);
+        self.balance_limit_ratio_x = self
+            .balance_limit_ratio_x
+            .filter(|ratio| ratio.is_finite() && (0.0..=1.0).contains(ratio));
+        self.balance_limit_ratio_y = self
+            .balance_limit_ratio_y
+            .filter(|ratio| ratio.is_finite() && (0.0..=1.0).contains(ratio));
         self.footprint_cluster_tolerance = sanitize_nonnegative_finite(
             self.footprint_cluster_tolerance,
             Self::DEFAULT_FOOTPRINT_CLUSTER_TOLERANCE,
         );
+        self.target_container_count = self.target_container_count.filter(|&count| count > 0);
+        self.max_overhang = self
+            .max_overhang
+            .filter(|overhang| overhang.is_finite() && *overhang >= 0.0);
+        self.restarts = self.restarts.min(Self::MAX_RESTARTS);
+        self.template_merge_tolerance = sanitize_nonnegative_finite(
+            self.template_merge_tolerance,
+            Self::DEFAULT_TEMPLATE_MERGE_TOLERANCE,
+        );
+        self.floor_min_coverage = self
+            .floor_min_coverage
+            .filter(|coverage| coverage.is_finite() && (0.0..=1.0).contains(coverage));
+        self.max_object_volume_fraction = self
+            .max_object_volume_fraction
+            .filter(|fraction| fraction.is_finite() && *fraction > 0.0);
+        self.top_clearance =
+            sanitize_nonnegative_finite(self.top_clearance, Self::DEFAULT_TOP_CLEARANCE);
+        self.target_utilization = self
+            .target_utilization
+            .filter(|target| target.is_finite() && *target > 0.0 && *target <= 1.0);
+        self.support_samples_per_axis = self
+            .support_samples_per_axis
+            .min(Self::MAX_SUPPORT_SAMPLES_PER_AXIS);
+        self.diagnostics_interval = self.diagnostics_interval.max(1);
+        self.snapshot_every = self.snapshot_every.filter(|every| *every > 0);
+        self.progress_every = self.progress_every.filter(|every| *every > 0);
+        self.progress_interval_ms = self.progress_interval_ms.filter(|ms| *ms > 0);
+        self
+    }
+
+    /// Resolves relative tolerances into absolute ones for the given objects.
+    ///
+    /// With `relative_tolerance` enabled, both epsilons are multiplied by the median object
+    /// dimension, so the same configuration behaves identically whether the request uses
+    /// millimeters or meters. Without objects (or with the option disabled) this is a no-op.
+    fn scaled_for(mut self, objects: &[Box3D]) -> Self {
+        if !self.relative_tolerance {
+            return self;
+        }
+
+        let mut dims: Vec<f64> = objects
+            .iter()
+            .flat_map(|o| [o.dims.0, o.dims.1, o.dims.2])
+            .filter(|d| d.is_finite() && *d > 0.0)
+            .collect();
+        if dims.is_empty() {
+            return self;
+        }
+        dims.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let median = dims[dims.len() / 2];
+
+        self.general_epsilon *= median;
+        self.height_epsilon *= median;
         self
     }
 }
@@ -128,8 +431,43 @@ impl Default for PackingConfig {
             height_epsilon: Self::DEFAULT_HEIGHT_EPSILON,
             general_epsilon: Self::DEFAULT_GENERAL_EPSILON,
             balance_limit_ratio: Self::DEFAULT_BALANCE_LIMIT_RATIO,
+            balance_limit_ratio_x: Self::DEFAULT_BALANCE_LIMIT_RATIO_X,
+            balance_limit_ratio_y: Self::DEFAULT_BALANCE_LIMIT_RATIO_Y,
             footprint_cluster_tolerance: Self::DEFAULT_FOOTPRINT_CLUSTER_TOLERANCE,
+            cluster_footprints: Self::DEFAULT_CLUSTER_FOOTPRINTS,
             allow_item_rotation: Self::DEFAULT_ALLOW_ITEM_ROTATION,
+            target_container_count: Self::DEFAULT_TARGET_CONTAINER_COUNT,
+            max_axis_positions: Self::DEFAULT_MAX_AXIS_POSITIONS,
+            strict_layering: Self::DEFAULT_STRICT_LAYERING,
+            primary_objective: Self::DEFAULT_PRIMARY_OBJECTIVE,
+            max_overhang: Self::DEFAULT_MAX_OVERHANG,
+            restarts: Self::DEFAULT_RESTARTS,
+            template_merge_tolerance: Self::DEFAULT_TEMPLATE_MERGE_TOLERANCE,
+            relative_tolerance: Self::DEFAULT_RELATIVE_TOLERANCE,
+            floor_min_coverage: Self::DEFAULT_FLOOR_MIN_COVERAGE,
+            container_order: Self::DEFAULT_CONTAINER_ORDER,
+            template_preference: Self::DEFAULT_TEMPLATE_PREFERENCE,
+            new_container_policy: Self::DEFAULT_NEW_CONTAINER_POLICY,
+            max_object_volume_fraction: Self::DEFAULT_MAX_OBJECT_VOLUME_FRACTION,
+            top_clearance: Self::DEFAULT_TOP_CLEARANCE,
+            allow_equal_weight_stacking: Self::DEFAULT_ALLOW_EQUAL_WEIGHT_STACKING,
+            strict_weight: Self::DEFAULT_STRICT_WEIGHT,
+            target_utilization: Self::DEFAULT_TARGET_UTILIZATION,
+            support_samples_per_axis: Self::DEFAULT_SUPPORT_SAMPLES_PER_AXIS,
+            profile: Self::DEFAULT_PROFILE,
+            compact: Self::DEFAULT_COMPACT,
+            settle: Self::DEFAULT_SETTLE,
+            cluster_by_category: Self::DEFAULT_CLUSTER_BY_CATEGORY,
+            prefer_wall_contact: Self::DEFAULT_PREFER_WALL_CONTACT,
+            prefer_floor_fallback: Self::DEFAULT_PREFER_FLOOR_FALLBACK,
+            prefer_column_stacking: Self::DEFAULT_PREFER_COLUMN_STACKING,
+            track_rejections: Self::DEFAULT_TRACK_REJECTIONS,
+            diagnostics_interval: Self::DEFAULT_DIAGNOSTICS_INTERVAL,
+            mass_placement: Self::DEFAULT_MASS_PLACEMENT,
+            load_direction: Self::DEFAULT_LOAD_DIRECTION,
+            snapshot_every: Self::DEFAULT_SNAPSHOT_EVERY,
+            progress_every: Self::DEFAULT_PROGRESS_EVERY,
+            progress_interval_ms: Self::DEFAULT_PROGRESS_INTERVAL_MS,
         }
     }
 }
@@ -199,18 +537,232 @@ impl PackingConfigBuilder {
         self
     }
 
+    /// Sets separate balance limits per axis (`None` keeps the combined limit for that axis).
+    pub fn balance_limit_ratio_per_axis(mut self, x: Option<f64>, y: Option<f64>) -> Self {
+        self.config.balance_limit_ratio_x = x;
+        self.config.balance_limit_ratio_y = y;
+        self
+    }
+
     /// Sets the tolerance for pre-grouping based on footprint.
     pub fn footprint_cluster_tolerance(mut self, tolerance: f64) -> Self {
         self.config.footprint_cluster_tolerance = tolerance;
         self
     }
 
+    /// Enables or disables the footprint pre-grouping without touching its tolerance.
+    pub fn cluster_footprints(mut self, cluster: bool) -> Self {
+        self.config.cluster_footprints = cluster;
+        self
+    }
+
     /// Enables or disables rotation of objects.
     pub fn allow_item_rotation(mut self, allow: bool) -> Self {
         self.config.allow_item_rotation = allow;
         self
     }
 
+    /// Sets the number of containers to pre-open and distribute objects across.
+    pub fn target_container_count(mut self, count: Option<usize>) -> Self {
+        self.config.target_container_count = count;
+        self
+    }
+
+    /// Sets the maximum number of grid positions per axis (0 = unlimited).
+    pub fn max_axis_positions(mut self, max: usize) -> Self {
+        self.config.max_axis_positions = max;
+        self
+    }
+
+    /// Enables or disables the container-wide heaviest-on-bottom layering rule.
+    pub fn strict_layering(mut self, strict: bool) -> Self {
+        self.config.strict_layering = strict;
+        self
+    }
+
+    /// Sets whether compactness or balance is compared first when scoring placements.
+    pub fn primary_objective(mut self, objective: PrimaryObjective) -> Self {
+        self.config.primary_objective = objective;
+        self
+    }
+
+    /// Caps how far a stacked box may protrude beyond its supports (`None` = unlimited).
+    pub fn max_overhang(mut self, overhang: Option<f64>) -> Self {
+        self.config.max_overhang = overhang;
+        self
+    }
+
+    /// Sets the number of additional randomized packing runs.
+    pub fn restarts(mut self, restarts: usize) -> Self {
+        self.config.restarts = restarts;
+        self
+    }
+
+    /// Sets the relative tolerance for merging near-identical container templates.
+    pub fn template_merge_tolerance(mut self, tolerance: f64) -> Self {
+        self.config.template_merge_tolerance = tolerance;
+        self
+    }
+
+    /// Treats the epsilons as relative to the median object dimension instead of absolute.
+    pub fn relative_tolerance(mut self, relative: bool) -> Self {
+        self.config.relative_tolerance = relative;
+        self
+    }
+
+    /// Sets the floor coverage ratio reported as `floor_coverage_ok` (`None` = no threshold).
+    pub fn floor_min_coverage(mut self, coverage: Option<f64>) -> Self {
+        self.config.floor_min_coverage = coverage;
+        self
+    }
+
+    /// Sets the order in which the result lists its containers.
+    pub fn container_order(mut self, order: ContainerOrder) -> Self {
+        self.config.container_order = order;
+        self
+    }
+
+    /// Sets which container templates are tried first when a new container is opened.
+    pub fn template_preference(mut self, preference: TemplatePreference) -> Self {
+        self.config.template_preference = preference;
+        self
+    }
+
+    /// Sets how the template of a newly opened container is chosen.
+    pub fn new_container_policy(mut self, policy: NewContainerPolicy) -> Self {
+        self.config.new_container_policy = policy;
+        self
+    }
+
+    /// Sets the largest allowed object volume as a share of the largest template (`None` = off).
+    pub fn max_object_volume_fraction(mut self, fraction: Option<f64>) -> Self {
+        self.config.max_object_volume_fraction = fraction;
+        self
+    }
+
+    /// Keeps `clearance` free below the ceiling of every container.
+    pub fn top_clearance(mut self, clearance: f64) -> Self {
+        self.config.top_clearance = clearance;
+        self
+    }
+
+    /// Allows or forbids stacking boxes of equal weight on each other.
+    pub fn allow_equal_weight_stacking(mut self, allow: bool) -> Self {
+        self.config.allow_equal_weight_stacking = allow;
+        self
+    }
+
+    /// Rejects objects exceeding a weight limit by any amount, without epsilon slack.
+    pub fn strict_weight(mut self, strict: bool) -> Self {
+        self.config.strict_weight = strict;
+        self
+    }
+
+    /// Sets the volume utilization at which containers stop receiving objects (`None` = off).
+    pub fn target_utilization(mut self, target: Option<f64>) -> Self {
+        self.config.target_utilization = target;
+        self
+    }
+
+    /// Sets the support sampling resolution per axis (0 = exact rectangle overlaps).
+    pub fn support_samples_per_axis(mut self, samples: usize) -> Self {
+        self.config.support_samples_per_axis = samples;
+        self
+    }
+
+    /// Enables recording of phase durations in the packing result.
+    pub fn profile(mut self, profile: bool) -> Self {
+        self.config.profile = profile;
+        self
+    }
+
+    /// Enables the compaction pass that slides objects toward the origin after packing.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.config.compact = compact;
+        self
+    }
+
+    /// Enables the settling pass that drops floating objects onto the surface beneath them.
+    pub fn settle(mut self, settle: bool) -> Self {
+        self.config.settle = settle;
+        self
+    }
+
+    /// Prefers placing objects beside already placed objects of the same category.
+    pub fn cluster_by_category(mut self, cluster: bool) -> Self {
+        self.config.cluster_by_category = cluster;
+        self
+    }
+
+    /// Prefers positions against the container walls, e.g. for easier strapping.
+    pub fn prefer_wall_contact(mut self, prefer: bool) -> Self {
+        self.config.prefer_wall_contact = prefer;
+        self
+    }
+
+    /// Prefers a floor position over an elevated one when no position meets the balance limit.
+    pub fn prefer_floor_fallback(mut self, prefer: bool) -> Self {
+        self.config.prefer_floor_fallback = prefer;
+        self
+    }
+
+    /// Stacks objects in columns on top of placed objects with the same footprint first.
+    pub fn prefer_column_stacking(mut self, prefer: bool) -> Self {
+        self.config.prefer_column_stacking = prefer;
+        self
+    }
+
+    /// Records per unplaced object how many candidate positions failed each check.
+    pub fn track_rejections(mut self, track: bool) -> Self {
+        self.config.track_rejections = track;
+        self
+    }
+
+    /// Chooses whether heavy objects are pulled toward the XY center of the container.
+    pub fn mass_placement(mut self, placement: MassPlacement) -> Self {
+        self.config.mass_placement = placement;
+        self
+    }
+
+    /// Chooses whether objects accumulate from the front or the back of the container.
+    pub fn load_direction(mut self, direction: LoadDirection) -> Self {
+        self.config.load_direction = direction;
+        self
+    }
+
+    /// Throttles live container diagnostics to every `interval` placements per container.
+    pub fn diagnostics_interval(mut self, interval: usize) -> Self {
+        self.config.diagnostics_interval = interval;
+        self
+    }
+
+    /// Emits a full snapshot of every container each `every` placements (`None` = never).
+    pub fn snapshot_every(mut self, every: Option<usize>) -> Self {
+        self.config.snapshot_every = every;
+        self
+    }
+
+    /// Emits throughput progress every `every` processed objects (`None` = never).
+    pub fn progress_every(mut self, every: Option<usize>) -> Self {
+        self.config.progress_every = every;
+        self
+    }
+
+    /// Emits throughput progress at least every `interval_ms` milliseconds (`None` = never).
+    pub fn progress_interval_ms(mut self, interval_ms: Option<u64>) -> Self {
+        self.config.progress_interval_ms = interval_ms;
+        self
+    }
+
+    /// Applies the [`PackingConfig::conservative`] preset to the settings so far.
+    pub fn conservative(self) -> Self {
+        self.support_ratio(PackingConfig::CONSERVATIVE_SUPPORT_RATIO)
+            .balance_limit_ratio(PackingConfig::CONSERVATIVE_BALANCE_LIMIT_RATIO)
+            .max_overhang(Some(0.0))
+            .allow_item_rotation(false)
+            .primary_objective(PrimaryObjective::Balance)
+    }
+
     /// Creates the final configuration.
     pub fn build(self) -> PackingConfig {
         self.config
@@ -379,7 +931,33 @@ fn compare_objects_for_packing(a: &Box3D, b: &Box3D, config: &PackingConfig) ->
         .then_with(|| a.id.cmp(&b.id))
 }
 
-fn orientations_for(object: &Box3D, allow_rotation: bool) -> Vec<Box3D> {
+/// Resolves whether `object` may rotate: its own override wins over the global setting.
+fn rotation_allowed(object: &Box3D, config: &PackingConfig) -> bool {
+    object.allow_rotation.unwrap_or(config.allow_item_rotation)
+}
+
+/// Enumerates the distinct orientations of `object` as copies with permuted `dims`.
+///
+/// With `allow_rotation` all six axis permutations are considered; permutations that yield
+/// the same dimensions (to a precision of 1e-6 units) are returned once, in the order the
+/// search tries them. Without rotation the object is returned unchanged.
+///
+/// # Examples
+/// ```
+/// use sort_it_now::model::Box3D;
+/// use sort_it_now::optimizer::orientations_for;
+///
+/// let cube = Box3D::new(1, (10.0, 10.0, 10.0), 1.0).unwrap();
+/// assert_eq!(orientations_for(&cube, true).len(), 1);
+///
+/// let two_equal = Box3D::new(2, (10.0, 10.0, 20.0), 1.0).unwrap();
+/// assert_eq!(orientations_for(&two_equal, true).len(), 3);
+///
+/// let distinct = Box3D::new(3, (10.0, 20.0, 30.0), 1.0).unwrap();
+/// assert_eq!(orientations_for(&distinct, true).len(), 6);
+/// assert_eq!(orientations_for(&distinct, false).len(), 1);
+/// ```
+pub fn orientations_for(object: &Box3D, allow_rotation: bool) -> Vec<Box3D> {
     if !allow_rotation {
         return vec![object.clone()];
     }
@@ -420,36 +998,90 @@ fn orientations_for(object: &Box3D, allow_rotation: bool) -> Vec<Box3D> {
     unique
 }
 
+/// Returns the orientations of `object` that fit into an empty container of `template`.
+///
+/// Orientations come from [`orientations_for`]; the object's own `allow_rotation` override
+/// applies, and without one every orientation is considered. Only dimensions and the weight
+/// capacity are checked, not the object's `allowed_template_ids`.
+///
+/// # Examples
+/// ```
+/// use sort_it_now::model::{Box3D, ContainerBlueprint};
+/// use sort_it_now::optimizer::fitting_orientations;
+///
+/// let template = ContainerBlueprint::new(0, None, (30.0, 20.0, 10.0), 100.0).unwrap();
+/// let object = Box3D::new(1, (10.0, 20.0, 30.0), 1.0).unwrap();
+///
+/// let fitting = fitting_orientations(&object, &template);
+/// assert_eq!(fitting.len(), 1);
+/// assert_eq!(fitting[0].dims, (30.0, 20.0, 10.0));
+/// ```
+pub fn fitting_orientations(object: &Box3D, template: &ContainerBlueprint) -> Vec<Box3D> {
+    orientations_for(object, object.allow_rotation.unwrap_or(true))
+        .into_iter()
+        .filter(|oriented| template.can_fit(oriented))
+        .collect()
+}
+
 /// Support metrics per object.
-#[derive(Clone, Debug, serde::Serialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct SupportDiagnostics {
     pub object_id: usize,
     pub support_percent: f64,
     pub rests_on_floor: bool,
+    /// How safely the object rests, from 0 (at the limit) to 1 (fully supported and centered).
+    ///
+    /// Product of the support ratio's share above `support_ratio` and the distance of the base
+    /// center from the edge of the support region, relative to half the shorter base edge.
+    pub stability_margin: f64,
 }
 
 /// Diagnostic metrics per container for monitoring.
-#[derive(Clone, Debug, serde::Serialize, ToSchema)]
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct ContainerDiagnostics {
     pub center_of_mass_offset: f64,
     pub balance_limit: f64,
     pub imbalance_ratio: f64,
     pub average_support_percent: f64,
     pub minimum_support_percent: f64,
+    /// Volume occupied by the placed objects (cubic units).
+    pub used_volume: f64,
+    /// Interior volume of the container (cubic units).
+    pub total_volume: f64,
     /// Occupied volume as a percentage of the container volume (0.0 to 100.0).
     pub volume_utilization_percent: f64,
+    /// Base area covered by floor-resting objects as a percentage of the container base area.
+    #[serde(default)]
+    pub floor_coverage_percent: f64,
+    /// `false` if the floor layer covers less than `floor_min_coverage` of the container base.
+    ///
+    /// Always `true` when no threshold is configured.
+    #[serde(default)]
+    pub floor_coverage_ok: bool,
+    /// Directly stacked pairs that violate the weight-order rule (0 for optimizer output).
+    #[serde(default)]
+    pub weight_order_violations: usize,
     /// Loaded weight as a percentage of the container weight limit (0.0 to 100.0).
     pub weight_utilization_percent: f64,
+    /// Smallest gap between an object's top and the ceiling above it (full height when empty).
+    ///
+    /// Never below `top_clearance` for optimizer output.
+    #[serde(default)]
+    pub headroom_remaining: f64,
     /// Void-space / packaging-material requirement for this container.
     ///
     /// The empty volume inside the container that must be filled with cushioning material to
     /// immobilise the load during transport.
     pub packaging: PackagingFill,
     pub support_samples: Vec<SupportDiagnostics>,
+    /// Coarser grid step used over this container's longer base axis because `grid_step` would
+    /// exceed [`PackingConfig::max_axis_positions`]; absent if the configured step applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coarsened_grid_step: Option<f64>,
 }
 
 /// Summary of key metrics across all containers.
-#[derive(Clone, Debug, serde::Serialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct PackingDiagnosticsSummary {
     pub max_imbalance_ratio: f64,
     pub worst_support_percent: f64,
@@ -475,6 +1107,26 @@ impl Default for PackingDiagnosticsSummary {
     }
 }
 
+/// Wall-clock durations of the packing phases in milliseconds (see [`PackingConfig::profile`]).
+///
+/// With restarts, the phases belong to the winning run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PhaseTimings {
+    /// Sorting of container templates and objects
+    pub sort_ms: f64,
+    /// Footprint clustering of the sorted objects
+    pub cluster_ms: f64,
+    /// Placement search across all objects, including diagnostics
+    pub placement_ms: f64,
+    /// Complete run, covering the phases above and everything in between
+    pub total_ms: f64,
+}
+
+/// Returns the milliseconds elapsed since `start`.
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
 /// Result of the packing calculation.
 #[derive(Clone, Debug)]
 pub struct PackingResult {
@@ -482,11 +1134,48 @@ pub struct PackingResult {
     pub unplaced: Vec<UnplacedBox>,
     pub container_diagnostics: Vec<ContainerDiagnostics>,
     pub diagnostics_summary: PackingDiagnosticsSummary,
+    /// Phase durations, only recorded when `profile` is enabled
+    pub timings: Option<PhaseTimings>,
 }
 
 impl PackingResult {
+    /// Reorders the containers (together with their diagnostics) by `order`.
+    ///
+    /// Also returns the 1-based creation ids in the new order, or `None` if `order` keeps the
+    /// creation order.
+    fn ordered_by(mut self, order: ContainerOrder) -> (Self, Option<Vec<usize>>) {
+        let key = match order {
+            ContainerOrder::Creation => return (self, None),
+            ContainerOrder::Utilization => |_: &Container, diagnostics: &ContainerDiagnostics| {
+                diagnostics.volume_utilization_percent
+            },
+            ContainerOrder::Weight => {
+                |container: &Container, _: &ContainerDiagnostics| container.total_weight()
+            }
+        };
+
+        let mut entries: Vec<(usize, Container, ContainerDiagnostics)> =
+            std::mem::take(&mut self.containers)
+                .into_iter()
+                .zip(std::mem::take(&mut self.container_diagnostics))
+                .enumerate()
+                .map(|(idx, (container, diagnostics))| (idx + 1, container, diagnostics))
+                .collect();
+        entries.sort_by(|(_, a, a_diag), (_, b, b_diag)| {
+            key(b, b_diag)
+                .partial_cmp(&key(a, a_diag))
+                .unwrap_or(Ordering::Equal)
+        });
+        let mut creation_ids = Vec::with_capacity(entries.len());
+        for (id, container, diagnostics) in entries {
+            creation_ids.push(id);
+            self.containers.push(container);
+            self.container_diagnostics.push(diagnostics);
+        }
+        (self, Some(creation_ids))
+    }
+
     /// Indicates whether all objects were packed.
-    #[allow(dead_code)]
     pub fn is_complete(&self) -> bool {
         self.unplaced.is_empty()
     }
@@ -501,6 +1190,35 @@ impl PackingResult {
         self.unplaced.len()
     }
 
+    /// Padding added to every axis of [`Self::suggested_container`], as a fraction.
+    pub const SUGGESTED_CONTAINER_PADDING: f64 = 0.1;
+
+    /// Suggests a container for the objects that could not be placed.
+    ///
+    /// The dimensions are those of the largest unplaced object (largest volume, the heavier one
+    /// on ties) in its input orientation, padded by [`Self::SUGGESTED_CONTAINER_PADDING`]; the
+    /// weight capacity is the total weight of all unplaced objects. Optional objects that were
+    /// skipped are ignored. Returns `None` if no such object is left.
+    pub fn suggested_container(&self) -> Option<ContainerSpec> {
+        let required = || {
+            self.unplaced
+                .iter()
+                .filter(|entry| entry.reason != UnplacedReason::OptionalSkipped)
+                .map(|entry| &entry.object)
+        };
+        let largest = required().max_by(|a, b| {
+            a.volume()
+                .total_cmp(&b.volume())
+                .then(a.weight.total_cmp(&b.weight))
+        })?;
+        let pad = 1.0 + Self::SUGGESTED_CONTAINER_PADDING;
+        let (w, d, h) = largest.dims;
+        Some(ContainerSpec {
+            dims: (w * pad, d * pad, h * pad),
+            max_weight: required().map(|object| object.weight).sum(),
+        })
+    }
+
     /// Calculates the average utilization of all containers.
     #[allow(dead_code)]
     pub fn average_utilization(&self) -> f64 {
@@ -533,14 +1251,83 @@ impl PackingResult {
 pub struct UnplacedBox {
     pub object: Box3D,
     pub reason: UnplacedReason,
+    /// Failed checks of the rejected candidate positions, with
+    /// [`PackingConfig::track_rejections`].
+    pub rejections: Option<RejectionCounts>,
+}
+
+/// Container size suggested for objects no available template could take.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ContainerSpec {
+    /// Inner dimensions `(width, depth, height)`.
+    #[serde(with = "crate::dim_format::dims")]
+    #[schema(value_type = crate::dim_format::DimensionsValue, example = json!([110.0, 55.0, 33.0]))]
+    pub dims: (f64, f64, f64),
+    /// Combined weight of all unplaced objects the suggestion is based on.
+    pub max_weight: f64,
+}
+
+/// Number of candidate positions that failed each placement check.
+///
+/// Tuning aid: shows whether support, weight or balance is the bottleneck for an object.
+/// Balance is a soft limit, so balance rejections alone never leave an object unplaced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RejectionCounts {
+    /// Overlaps with placed objects or reserved space, or violated clearances
+    pub collision: usize,
+    /// Too little support area or too much overhang
+    pub support: usize,
+    /// Supports that cannot carry the weight, or a violated layer weight order
+    pub weight: usize,
+    /// Center of gravity outside the supported area
+    pub center: usize,
+    /// Otherwise valid positions outside the balance limit
+    pub balance: usize,
+    /// Positions above the usable ceiling (including `top_clearance`) or the object's
+    /// `max_placement_height`, or on top of a floor-only region
+    #[serde(default)]
+    pub height: usize,
+}
+
+impl RejectionCounts {
+    fn record(&mut self, rejection: CandidateRejection) {
+        match rejection {
+            CandidateRejection::Collision => self.collision += 1,
+            CandidateRejection::Support => self.support += 1,
+            CandidateRejection::Weight => self.weight += 1,
+            CandidateRejection::Center => self.center += 1,
+        }
+    }
+}
+
+/// Check a candidate position failed in [`evaluate_placement`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CandidateRejection {
+    Collision,
+    Support,
+    Weight,
+    Center,
 }
 
 /// Reasons why an object could not be placed.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum UnplacedReason {
     TooHeavyForContainer,
     DimensionsExceedContainer,
     NoStablePosition,
+    ObjectTooBulky,
+    NoAllowedContainer,
+    /// Rotation is disabled for the object, but some rotated orientation would fit.
+    WouldFitIfRotated,
+    /// Optional object that fit into none of the open containers; it never opens a new one.
+    OptionalSkipped,
+    /// Some container types carry the weight and others fit the dimensions, but none both.
+    CombinedConstraint {
+        /// Ids of the allowed container types whose weight capacity suffices.
+        weight_capable: Vec<usize>,
+        /// Ids of the allowed container types the object fits into dimensionally.
+        dimension_capable: Vec<usize>,
+    },
 }
 
 impl UnplacedReason {
@@ -549,6 +1336,11 @@ impl UnplacedReason {
             UnplacedReason::TooHeavyForContainer => "too_heavy_for_container",
             UnplacedReason::DimensionsExceedContainer => "dimensions_exceed_container",
             UnplacedReason::NoStablePosition => "no_stable_position",
+            UnplacedReason::ObjectTooBulky => "object_too_bulky",
+            UnplacedReason::NoAllowedContainer => "no_allowed_container",
+            UnplacedReason::WouldFitIfRotated => "would_fit_if_rotated",
+            UnplacedReason::OptionalSkipped => "optional_skipped",
+            UnplacedReason::CombinedConstraint { .. } => "combined_constraint",
         }
     }
 }
@@ -568,6 +1360,37 @@ impl std::fmt::Display for UnplacedReason {
             UnplacedReason::NoStablePosition => {
                 write!(f, "No stable position found within the container")
             }
+            UnplacedReason::ObjectTooBulky => {
+                write!(
+                    f,
+                    "Object exceeds the allowed share of the largest container volume"
+                )
+            }
+            UnplacedReason::NoAllowedContainer => {
+                write!(
+                    f,
+                    "None of the object's allowed container types is available"
+                )
+            }
+            UnplacedReason::WouldFitIfRotated => {
+                write!(
+                    f,
+                    "Object only fits the container when rotated, but rotation is disabled"
+                )
+            }
+            UnplacedReason::OptionalSkipped => {
+                write!(f, "Optional object did not fit into any open container")
+            }
+            UnplacedReason::CombinedConstraint {
+                weight_capable,
+                dimension_capable,
+            } => {
+                write!(
+                    f,
+                    "Object is too heavy for the container types it fits into ({:?}) and too large for those that can carry its weight ({:?})",
+                    dimension_capable, weight_capable
+                )
+            }
         }
     }
 }
@@ -581,28 +1404,95 @@ fn determine_unfit_reason_across_templates(
         return UnplacedReason::DimensionsExceedContainer;
     }
 
-    let weight_blocked = templates
+    let templates: Vec<&ContainerBlueprint> = templates
         .iter()
-        .all(|tpl| object.weight > tpl.max_weight + config.general_epsilon);
+        .filter(|tpl| object.allows_template(Some(tpl.id)))
+        .collect();
+    if templates.is_empty() {
+        return UnplacedReason::NoAllowedContainer;
+    }
+
+    let carries_weight = |tpl: &ContainerBlueprint| {
+        object.weight <= tpl.available_weight() + config.weight_tolerance()
+    };
+    let weight_blocked = !templates.iter().any(|tpl| carries_weight(tpl));
     if weight_blocked {
         return UnplacedReason::TooHeavyForContainer;
     }
 
-    let orientations = orientations_for(object, config.allow_item_rotation);
-    let dimension_blocked = templates.iter().all(|tpl| {
-        orientations.iter().all(|orientation| {
-            orientation.dims.0 > tpl.dims.0 + config.general_epsilon
-                || orientation.dims.1 > tpl.dims.1 + config.general_epsilon
-                || orientation.dims.2 > tpl.dims.2 + config.general_epsilon
+    let fits_dimensions = |tpl: &ContainerBlueprint, orientations: &[Box3D]| {
+        orientations.iter().any(|orientation| {
+            orientation.dims.0 <= tpl.dims.0 + config.general_epsilon
+                && orientation.dims.1 <= tpl.dims.1 + config.general_epsilon
+                && orientation.dims.2 <= tpl.dims.2 + config.general_epsilon
         })
-    });
-    if dimension_blocked {
+    };
+    let dimension_blocked = |orientations: &[Box3D]| {
+        !templates
+            .iter()
+            .any(|tpl| fits_dimensions(tpl, orientations))
+    };
+    let rotation = rotation_allowed(object, config);
+    let orientations = orientations_for(object, rotation);
+    if dimension_blocked(&orientations) {
+        // Only for the diagnosis: tell clients when enabling rotation would help.
+        if !rotation && !dimension_blocked(&orientations_for(object, true)) {
+            return UnplacedReason::WouldFitIfRotated;
+        }
         return UnplacedReason::DimensionsExceedContainer;
     }
 
+    // Each constraint alone can be met, but maybe never by the same container type.
+    if !templates
+        .iter()
+        .any(|tpl| carries_weight(tpl) && fits_dimensions(tpl, &orientations))
+    {
+        let ids = |keep: &dyn Fn(&ContainerBlueprint) -> bool| {
+            templates
+                .iter()
+                .filter(|tpl| keep(tpl))
+                .map(|tpl| tpl.id)
+                .collect()
+        };
+        return UnplacedReason::CombinedConstraint {
+            weight_capable: ids(&carries_weight),
+            dimension_capable: ids(&|tpl| fits_dimensions(tpl, &orientations)),
+        };
+    }
+
     UnplacedReason::NoStablePosition
 }
 
+/// Checks whether a single object fits into an empty container of the given type.
+///
+/// Runs the same placement search as a full pack (including rotations if enabled, height
+/// profiles, and reserved space) and returns the placed object with the orientation used, or the
+/// reason why no position exists.
+pub fn find_fit(
+    object: &Box3D,
+    template: &ContainerBlueprint,
+    config: PackingConfig,
+) -> Result<PlacedBox, UnplacedReason> {
+    if !object.allows_template(Some(template.id)) {
+        return Err(UnplacedReason::NoAllowedContainer);
+    }
+    let config = config.sanitized().scaled_for(std::slice::from_ref(object));
+    let container = template.instantiate();
+
+    orientations_for(object, rotation_allowed(object, &config))
+        .into_iter()
+        .filter(|oriented| {
+            container.can_fit_with(oriented, config.general_epsilon, config.weight_tolerance())
+        })
+        .find_map(|oriented| {
+            find_stable_position(&oriented, &container, &config)
+                .map(|position| PlacedBox::new(oriented, position))
+        })
+        .ok_or_else(|| {
+            determine_unfit_reason_across_templates(std::slice::from_ref(template), object, &config)
+        })
+}
+
 /// Main function for packing objects into containers.
 ///
 /// Sorts objects by weight and volume (heavy/large first) and places
@@ -638,25 +1528,188 @@ pub fn pack_objects_with_config(
     pack_objects_with_progress(objects, container_templates, config, |_| {})
 }
 
-/// Events that occur during packing to enable live visualization.
-#[derive(Clone, Debug, serde::Serialize)]
-#[serde(tag = "type")]
-pub enum PackEvent {
-    /// A new container is started.
-    ContainerStarted {
-        id: usize,
-        dims: (f64, f64, f64),
-        max_weight: f64,
-        label: Option<String>,
-        template_id: Option<usize>,
-    },
-    /// An object was placed.
-    ObjectPlaced {
-        container_id: usize,
-        id: usize,
-        pos: (f64, f64, f64),
-        weight: f64,
-        dims: (f64, f64, f64),
+/// Packs every `group_id` partition of `objects` into containers of its own, concurrently.
+///
+/// Experimental, behind the `parallel` feature. Groups never share a container, so this only
+/// pays off when the groups are independent and containers are ample. Objects without a group
+/// form one partition. Each partition is packed on a scoped thread with
+/// [`pack_objects_with_config`]; the results are concatenated in ascending group order
+/// (ungrouped objects first). Labels are then expanded again with ordinals counted across all
+/// groups, so `{n}` never repeats, and [`PackingConfig::container_order`] is applied to the merged
+/// list, so containers are numbered the same on every run. Timings, if profiled, are the slowest
+/// partition per phase.
+#[cfg(feature = "parallel")]
+pub fn pack_groups_parallel(
+    objects: Vec<Box3D>,
+    container_templates: Vec<ContainerBlueprint>,
+    config: PackingConfig,
+) -> PackingResult {
+    let mut groups: std::collections::BTreeMap<Option<String>, Vec<Box3D>> =
+        std::collections::BTreeMap::new();
+    for object in objects {
+        groups
+            .entry(object.group_id.clone())
+            .or_default()
+            .push(object);
+    }
+
+    let partials: Vec<PackingResult> = std::thread::scope(|scope| {
+        let handles: Vec<_> = groups
+            .into_values()
+            .map(|group| {
+                let templates = container_templates.clone();
+                let config = PackingConfig {
+                    container_order: ContainerOrder::Creation,
+                    ..config
+                };
+                scope.spawn(move || pack_objects_with_config(group, templates, config))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("group packing thread panicked"))
+            .collect()
+    });
+
+    let mut merged = PackingResult {
+        containers: Vec::new(),
+        unplaced: Vec::new(),
+        container_diagnostics: Vec::new(),
+        diagnostics_summary: PackingDiagnosticsSummary::default(),
+        timings: config.profile.then_some(PhaseTimings::default()),
+    };
+    for partial in partials {
+        merged.containers.extend(partial.containers);
+        merged.unplaced.extend(partial.unplaced);
+        merged
+            .container_diagnostics
+            .extend(partial.container_diagnostics);
+        if let (Some(total), Some(part)) = (merged.timings.as_mut(), partial.timings) {
+            total.sort_ms = total.sort_ms.max(part.sort_ms);
+            total.cluster_ms = total.cluster_ms.max(part.cluster_ms);
+            total.placement_ms = total.placement_ms.max(part.placement_ms);
+            total.total_ms = total.total_ms.max(part.total_ms);
+        }
+    }
+    let mut ordinals: std::collections::BTreeMap<usize, usize> = std::collections::BTreeMap::new();
+    for container in &mut merged.containers {
+        let Some(template) = container
+            .template_id
+            .and_then(|id| container_templates.iter().find(|t| t.id == id))
+        else {
+            continue;
+        };
+        let ordinal = ordinals.entry(template.id).or_insert(0);
+        *ordinal += 1;
+        container.label = template.instantiate_nth(*ordinal).label;
+    }
+    merged.diagnostics_summary = summarize_diagnostics(&merged.container_diagnostics);
+    merged.ordered_by(config.container_order).0
+}
+
+/// Item of a [`pack_items`] run placed into a container.
+#[derive(Clone, Debug)]
+pub struct PlacedItem<T> {
+    /// Index of the item in the input slice.
+    pub index: usize,
+    pub item: T,
+    /// Index of the container in [`PackingResult::containers`].
+    pub container: usize,
+    pub position: (f64, f64, f64),
+    /// Dimensions as placed, i.e. after a possible rotation.
+    pub dims: (f64, f64, f64),
+}
+
+/// Item of a [`pack_items`] run that could not be placed.
+#[derive(Clone, Debug)]
+pub struct UnplacedItem<T> {
+    /// Index of the item in the input slice.
+    pub index: usize,
+    pub item: T,
+    pub reason: UnplacedReason,
+}
+
+/// Result of [`pack_items`], keyed back to the caller's items.
+#[derive(Clone, Debug)]
+pub struct ItemPackingResult<T> {
+    pub placed: Vec<PlacedItem<T>>,
+    pub unplaced: Vec<UnplacedItem<T>>,
+    /// Underlying result; object ids are the item indices.
+    pub result: PackingResult,
+}
+
+/// Packs arbitrary item types through the [`Dimensional`] and [`Weighted`] traits.
+///
+/// Every item becomes a [`Box3D`] whose id is its index in `items`, so library users can pack
+/// their own types without converting them first.
+///
+/// # Returns
+/// The placements keyed back to the items, or a `ValidationError` if an item has invalid
+/// dimensions or weight
+pub fn pack_items<T: Dimensional + Weighted + Clone>(
+    items: &[T],
+    container_templates: Vec<ContainerBlueprint>,
+    config: PackingConfig,
+) -> Result<ItemPackingResult<T>, ValidationError> {
+    let objects = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| Box3D::new(index, item.dimensions().as_tuple(), item.weight()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let result = pack_objects_with_config(objects, container_templates, config);
+
+    let placed = result
+        .containers
+        .iter()
+        .enumerate()
+        .flat_map(|(container, c)| {
+            c.placed.iter().map(move |p| PlacedItem {
+                index: p.object.id,
+                item: items[p.object.id].clone(),
+                container,
+                position: p.position,
+                dims: p.object.dims,
+            })
+        })
+        .collect();
+    let unplaced = result
+        .unplaced
+        .iter()
+        .map(|u| UnplacedItem {
+            index: u.object.id,
+            item: items[u.object.id].clone(),
+            reason: u.reason.clone(),
+        })
+        .collect();
+    Ok(ItemPackingResult {
+        placed,
+        unplaced,
+        result,
+    })
+}
+
+/// Events that occur during packing to enable live visualization.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum PackEvent {
+    /// A new container is started.
+    ContainerStarted {
+        id: usize,
+        #[serde(with = "crate::dim_format::dims")]
+        dims: (f64, f64, f64),
+        max_weight: f64,
+        label: Option<String>,
+        template_id: Option<usize>,
+    },
+    /// An object was placed.
+    ObjectPlaced {
+        container_id: usize,
+        id: usize,
+        #[serde(with = "crate::dim_format::position")]
+        pos: (f64, f64, f64),
+        weight: f64,
+        #[serde(with = "crate::dim_format::dims")]
+        dims: (f64, f64, f64),
         total_weight: f64,
     },
     /// Updated diagnostics for a container.
@@ -664,22 +1717,80 @@ pub enum PackEvent {
         container_id: usize,
         diagnostics: ContainerDiagnostics,
     },
+    /// Full state of a container, emitted every [`PackingConfig::snapshot_every`] placements
+    /// for the containers that changed since the previous snapshot, and after
+    /// [`PackingConfig::settle`] or [`PackingConfig::compact`] moved its objects.
+    ///
+    /// Positions are in packing coordinates, like those of `ObjectPlaced`.
+    Snapshot {
+        container_id: usize,
+        placed: Vec<SnapshotObject>,
+    },
+    /// Throughput so far, emitted per [`PackingConfig::progress_every`] and
+    /// [`PackingConfig::progress_interval_ms`].
+    Progress {
+        /// Objects placed or rejected so far.
+        processed: usize,
+        /// Objects in the run, excluding placements from a prior result.
+        total: usize,
+        elapsed_ms: f64,
+        rate_per_sec: f64,
+    },
     /// An object could not be placed.
     ObjectRejected {
         id: usize,
         weight: f64,
+        #[serde(with = "crate::dim_format::dims")]
         dims: (f64, f64, f64),
         reason_code: String,
         reason_text: String,
     },
+    /// The final [`PackingConfig::container_order`] renumbered the containers; sent right
+    /// before `Finished`.
+    ///
+    /// `creation_ids[i]` is the id under which the container reported as `i + 1` was announced.
+    /// Only `Finished` uses the final ids.
+    ContainersReordered { creation_ids: Vec<usize> },
     /// Packing completed.
     Finished {
         containers: usize,
         unplaced: usize,
         diagnostics_summary: PackingDiagnosticsSummary,
+        /// Final fill per container as `(container_id, volume_utilization_percent, total_weight)`.
+        container_fill: Vec<(usize, f64, f64)>,
     },
 }
 
+/// Placement listed in a [`PackEvent::Snapshot`], in the same shape as the placements of a
+/// `/pack` response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SnapshotObject {
+    pub id: usize,
+    #[serde(with = "crate::dim_format::position")]
+    pub pos: (f64, f64, f64),
+    pub weight: f64,
+    #[serde(with = "crate::dim_format::dims")]
+    pub dims: (f64, f64, f64),
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// Converts a placement, keeping its position in packing coordinates (without `origin`).
+impl From<&PlacedBox> for SnapshotObject {
+    fn from(placed: &PlacedBox) -> Self {
+        Self {
+            id: placed.object.id,
+            pos: placed.position,
+            weight: placed.object.weight,
+            dims: placed.object.dims,
+            category: placed.object.category.clone(),
+            color: placed.object.color.clone(),
+        }
+    }
+}
+
 /// Packing with custom configuration and live progress callback.
 ///
 /// Calls a callback for each important step (suitable for SSE/WebSocket).
@@ -687,23 +1798,209 @@ pub fn pack_objects_with_progress(
     objects: Vec<Box3D>,
     container_templates: Vec<ContainerBlueprint>,
     config: PackingConfig,
+    on_event: impl FnMut(&PackEvent),
+) -> PackingResult {
+    pack_objects_incremental_with_progress(
+        Vec::new(),
+        objects,
+        container_templates,
+        config,
+        on_event,
+    )
+}
+
+/// Incremental packing into containers from a previous run.
+///
+/// Objects already placed in `existing` are never moved; the new `objects` fill the remaining
+/// space first, and containers from `container_templates` are only opened when necessary.
+pub fn pack_objects_incremental(
+    existing: Vec<Container>,
+    objects: Vec<Box3D>,
+    container_templates: Vec<ContainerBlueprint>,
+    config: PackingConfig,
+) -> PackingResult {
+    pack_objects_incremental_with_progress(existing, objects, container_templates, config, |_| {})
+}
+
+/// Incremental packing with live progress callback.
+///
+/// The pre-existing containers and their objects are announced first (`ContainerStarted`,
+/// `ObjectPlaced`, `ContainerDiagnostics`) so live viewers can reconstruct the prior layout.
+///
+/// With `restarts > 0` the deterministic run is followed by `restarts` runs with a randomly
+/// perturbed object order. Only the events of the winning run are emitted, after all runs have
+/// finished.
+pub fn pack_objects_incremental_with_progress(
+    existing: Vec<Container>,
+    objects: Vec<Box3D>,
+    container_templates: Vec<ContainerBlueprint>,
+    config: PackingConfig,
+    mut on_event: impl FnMut(&PackEvent),
+) -> PackingResult {
+    let restarts = config.sanitized().restarts;
+    if restarts == 0 || objects.is_empty() {
+        // `Finished` is held back so the reordering is announced before it.
+        let mut finished = None;
+        let result = pack_single_run(
+            existing,
+            objects,
+            container_templates,
+            config,
+            None,
+            |event| match event {
+                PackEvent::Finished { .. } => finished = Some(event.clone()),
+                _ => on_event(event),
+            },
+        );
+        return finish_run(result, finished, config.container_order, on_event);
+    }
+
+    let mut best: Option<(PackingResult, Vec<PackEvent>)> = None;
+    for run in 0..=restarts {
+        // Run 0 keeps the deterministic order so restarts can never do worse than a single run.
+        let mut rng = (run > 0).then(|| SplitMix64::new(RESTART_SEED ^ run as u64));
+        let mut events = Vec::new();
+        let result = pack_single_run(
+            existing.clone(),
+            objects.clone(),
+            container_templates.clone(),
+            config,
+            rng.as_mut(),
+            |event| events.push(event.clone()),
+        );
+        if best
+            .as_ref()
+            .is_none_or(|(current, _)| is_better_result(&result, current))
+        {
+            best = Some((result, events));
+        }
+    }
+
+    let (result, mut events) = best.expect("at least one packing run");
+    let finished = events.pop_if(|event| matches!(event, PackEvent::Finished { .. }));
+    for event in &events {
+        on_event(event);
+    }
+    finish_run(result, finished, config.container_order, on_event)
+}
+
+/// Applies the container `order` to a finished run and emits its last events.
+///
+/// If the order renumbers the containers, [`PackEvent::ContainersReordered`] is sent first and
+/// the `Finished` event reports the fill per final container id.
+fn finish_run(
+    result: PackingResult,
+    finished: Option<PackEvent>,
+    order: ContainerOrder,
+    mut on_event: impl FnMut(&PackEvent),
+) -> PackingResult {
+    let (result, creation_ids) = result.ordered_by(order);
+    let Some(mut finished) = finished else {
+        return result;
+    };
+    if let Some(creation_ids) = creation_ids {
+        if let PackEvent::Finished { container_fill, .. } = &mut finished {
+            let final_id = |id: usize| creation_ids.iter().position(|&c| c == id).map(|i| i + 1);
+            for fill in container_fill.iter_mut() {
+                fill.0 = final_id(fill.0).unwrap_or(fill.0);
+            }
+            container_fill.sort_by_key(|fill| fill.0);
+        }
+        on_event(&PackEvent::ContainersReordered { creation_ids });
+    }
+    on_event(&finished);
+    result
+}
+
+/// Seed for the perturbed restart runs; fixed so that results are reproducible.
+const RESTART_SEED: u64 = 0x736F_7274_6974_6E6F;
+
+/// Probability of swapping two neighbouring objects when perturbing the packing order.
+const RESTART_SWAP_PROBABILITY: f64 = 0.3;
+
+/// Minimal SplitMix64 generator for reproducible restart perturbations.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Slightly shuffles the packing order by swapping random neighbours.
+///
+/// Objects only move locally, so the heavy-first ordering is largely preserved while greedy
+/// tie-breaks between similar objects change from run to run.
+fn perturb_order(objects: &mut [Box3D], rng: &mut SplitMix64) {
+    let mut i = 1;
+    while i < objects.len() {
+        if rng.next_f64() < RESTART_SWAP_PROBABILITY {
+            objects.swap(i - 1, i);
+            // Skip the next pair so a single object cannot travel far in one pass.
+            i += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Compares two packing runs: fewer unplaced objects, then fewer containers, then lower maximum
+/// imbalance.
+fn is_better_result(new: &PackingResult, current: &PackingResult) -> bool {
+    new.unplaced
+        .len()
+        .cmp(&current.unplaced.len())
+        .then_with(|| new.containers.len().cmp(&current.containers.len()))
+        .then_with(|| {
+            new.diagnostics_summary
+                .max_imbalance_ratio
+                .partial_cmp(&current.diagnostics_summary.max_imbalance_ratio)
+                .unwrap_or(Ordering::Equal)
+        })
+        == Ordering::Less
+}
+
+/// A single greedy packing run, optionally with a perturbed object order.
+fn pack_single_run(
+    existing: Vec<Container>,
+    objects: Vec<Box3D>,
+    container_templates: Vec<ContainerBlueprint>,
+    config: PackingConfig,
+    rng: Option<&mut SplitMix64>,
     mut on_event: impl FnMut(&PackEvent),
 ) -> PackingResult {
-    if objects.is_empty() {
+    let started = Instant::now();
+    if objects.is_empty() && existing.is_empty() {
         on_event(&PackEvent::Finished {
             containers: 0,
             unplaced: 0,
             diagnostics_summary: PackingDiagnosticsSummary::default(),
+            container_fill: Vec::new(),
         });
         return PackingResult {
             containers: Vec::new(),
             unplaced: Vec::new(),
             container_diagnostics: Vec::new(),
             diagnostics_summary: PackingDiagnosticsSummary::default(),
+            timings: config.profile.then_some(PhaseTimings::default()),
         };
     }
 
-    if container_templates.is_empty() {
+    if container_templates.is_empty() && existing.is_empty() {
         let mut unplaced = Vec::new();
         for obj in objects {
             on_event(&PackEvent::ObjectRejected {
@@ -716,62 +2013,247 @@ pub fn pack_objects_with_progress(
             unplaced.push(UnplacedBox {
                 object: obj,
                 reason: UnplacedReason::DimensionsExceedContainer,
+                rejections: None,
             });
         }
         on_event(&PackEvent::Finished {
             containers: 0,
             unplaced: unplaced.len(),
             diagnostics_summary: PackingDiagnosticsSummary::default(),
+            container_fill: Vec::new(),
         });
         return PackingResult {
             containers: Vec::new(),
             unplaced,
             container_diagnostics: Vec::new(),
             diagnostics_summary: PackingDiagnosticsSummary::default(),
+            timings: config.profile.then_some(PhaseTimings::default()),
         };
     }
 
-    let config = config.sanitized();
+    let config = config.sanitized().scaled_for(&objects);
 
-    let mut templates = container_templates;
+    let sort_started = Instant::now();
+    let mut templates = merge_similar_templates(
+        container_templates,
+        config.template_merge_tolerance,
+        &objects,
+    );
     templates.sort_by(|a, b| {
-        a.volume()
+        let ordering = a
+            .volume()
             .partial_cmp(&b.volume())
             .unwrap_or(Ordering::Equal)
             .then_with(|| {
                 a.max_weight
                     .partial_cmp(&b.max_weight)
                     .unwrap_or(Ordering::Equal)
-            })
+            });
+        match config.template_preference {
+            TemplatePreference::SmallestFirst => ordering,
+            TemplatePreference::LargestFirst => ordering.reverse(),
+        }
     });
 
     // Sorting: heavy and large objects first, then refine ties with
     // pressure/density/slenderness to keep physically demanding items low.
     let mut objects = objects;
     objects.sort_by(|a, b| compare_objects_for_packing(a, b, &config));
+    let sort_ms = elapsed_ms(sort_started);
 
-    let cluster_strategy = FootprintClusterStrategy::new(config.footprint_cluster_tolerance);
+    let cluster_started = Instant::now();
+    let cluster_tolerance = if config.cluster_footprints {
+        config.footprint_cluster_tolerance
+    } else {
+        0.0
+    };
+    let cluster_strategy = FootprintClusterStrategy::new(cluster_tolerance);
     objects = cluster_strategy.reorder(objects);
+    let cluster_ms = elapsed_ms(cluster_started);
+    let tier = |obj: &Box3D| fill_tier(obj, &templates, &config);
+    let ranked = templates.iter().any(|t| t.fill_rank.is_some());
+    if ranked {
+        // Stable, so objects of equal tier and weight keep their clustered order.
+        objects.sort_by(|a, b| {
+            tier(a)
+                .cmp(&tier(b))
+                .then_with(|| b.weight.total_cmp(&a.weight))
+        });
+    }
+    if let Some(rng) = rng {
+        // Restarts only shuffle within a tier, so the fill order holds in every run.
+        if ranked {
+            for run in objects.chunk_by_mut(|a, b| tier(a) == tier(b)) {
+                perturb_order(run, rng);
+            }
+        } else {
+            perturb_order(&mut objects, rng);
+        }
+    }
+
+    let placement_started = Instant::now();
+    let mut progress = ProgressReporter::new(placement_started, objects.len(), &config);
 
     let mut containers: Vec<Container> = Vec::new();
     let mut unplaced: Vec<UnplacedBox> = Vec::new();
     let mut container_diagnostics: Vec<ContainerDiagnostics> = Vec::new();
 
+    // Placements from a prior result are never moved, not even by the compaction pass.
+    let fixed_counts: Vec<usize> = existing.iter().map(|c| c.placed.len()).collect();
+    let mut snapshot_sizes = fixed_counts.clone();
+    for container in existing {
+        let id = containers.len() + 1;
+        on_event(&PackEvent::ContainerStarted {
+            id,
+            dims: container.dims,
+            max_weight: container.max_weight,
+            label: container.label.clone(),
+            template_id: container.template_id,
+        });
+        let mut total_weight = 0.0;
+        for placed in &container.placed {
+            total_weight += placed.object.weight;
+            on_event(&PackEvent::ObjectPlaced {
+                container_id: id,
+                id: placed.object.id,
+                pos: placed.position,
+                weight: placed.object.weight,
+                dims: placed.object.dims,
+                total_weight,
+            });
+        }
+        let diagnostics = compute_container_diagnostics(&container, &config);
+        on_event(&PackEvent::ContainerDiagnostics {
+            container_id: id,
+            diagnostics: diagnostics.clone(),
+        });
+        container_diagnostics.push(diagnostics);
+        containers.push(container);
+    }
+
+    // Reject implausibly bulky objects (often data errors) before they can claim a container.
+    let volume_limit = config.max_object_volume_fraction.and_then(|fraction| {
+        templates
+            .iter()
+            .map(ContainerBlueprint::volume)
+            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|largest| largest * fraction)
+    });
+    if let Some(limit) = volume_limit {
+        let (bulky, regular): (Vec<Box3D>, Vec<Box3D>) = objects
+            .into_iter()
+            .partition(|obj| obj.volume() > limit + config.general_epsilon);
+        objects = regular;
+        for obj in bulky {
+            let reason = UnplacedReason::ObjectTooBulky;
+            on_event(&PackEvent::ObjectRejected {
+                id: obj.id,
+                weight: obj.weight,
+                dims: obj.dims,
+                reason_code: reason.code().to_string(),
+                reason_text: reason.to_string(),
+            });
+            unplaced.push(UnplacedBox {
+                object: obj,
+                reason,
+                rejections: None,
+            });
+        }
+    }
+
+    // Contract mode: open the committed number of containers up front so objects are spread
+    // across all of them instead of filling one container at a time.
+    if let Some(target) = config.target_container_count
+        && let Some(template) = select_seed_template(&templates, &objects, target)
+    {
+        for _ in containers.len()..target {
+            let container = template.instantiate_nth(template_ordinal(&containers, template));
+            on_event(&PackEvent::ContainerStarted {
+                id: containers.len() + 1,
+                dims: container.dims,
+                max_weight: container.max_weight,
+                label: container.label.clone(),
+                template_id: container.template_id,
+            });
+            container_diagnostics.push(compute_container_diagnostics(&container, &config));
+            containers.push(container);
+        }
+    }
+
+    // Optional objects only fill the containers the required ones leave behind.
+    let (required, optional): (Vec<Box3D>, Vec<Box3D>) =
+        objects.into_iter().partition(|obj| !obj.optional);
+    let objects: Vec<Box3D> = required.into_iter().chain(optional).collect();
+
+    // Placements per container since its diagnostics were last computed; throttled containers
+    // are refreshed after the loop so the final diagnostics are always complete.
+    let mut stale_placements: Vec<usize> = vec![0; containers.len()];
+
+    // Volume of the required objects not yet processed, including the current one.
+    let mut remaining_volume: f64 = objects
+        .iter()
+        .filter(|obj| !obj.optional)
+        .map(Box3D::volume)
+        .sum();
+
+    // New placements so far; prior placements are not counted.
+    let mut placements = 0;
+
+    // Objects rejected up front count as processed.
+    let pre_rejected = unplaced.len();
+
     // Place every object in turn.
-    'object_loop: for obj in objects {
-        let orientations = orientations_for(&obj, config.allow_item_rotation);
+    'object_loop: for (index, obj) in objects.into_iter().enumerate() {
+        progress.report(pre_rejected + index, &mut on_event);
+        let remaining = remaining_volume;
+        if !obj.optional {
+            remaining_volume -= obj.volume();
+        }
+        let orientations = orientations_for(&obj, rotation_allowed(&obj, &config));
+        let mut rejections = config.track_rejections.then(RejectionCounts::default);
+        // Objects no template or container can take in any orientation, or whose weight none of
+        // them can carry, skip straight to the unfit reason instead of walking every container
+        // for every orientation.
+        let feasible = !exceeds_every_weight_limit(&obj, &templates, &containers, &config)
+            && orientations.iter().any(|oriented| {
+                templates.iter().any(|t| {
+                    obj.allows_template(Some(t.id))
+                        && t.can_fit_with(
+                            oriented,
+                            config.general_epsilon,
+                            config.weight_tolerance(),
+                        )
+                }) || containers.iter().any(|c| {
+                    obj.allows_template(c.template_id)
+                        && c.can_fit_with(
+                            oriented,
+                            config.general_epsilon,
+                            config.weight_tolerance(),
+                        )
+                })
+            });
 
-        for oriented in &orientations {
+        for oriented in orientations.iter().filter(|_| feasible) {
             // Try to place into existing containers. The index is needed both to mutate the
-            // container in place and to keep the parallel diagnostics vector and event ids in sync,
-            // so an indexed loop is clearer than iterator gymnastics here.
-            #[allow(clippy::needless_range_loop)]
-            for idx in 0..containers.len() {
-                if !containers[idx].can_fit(oriented) {
+            // container in place and to keep the parallel diagnostics vector and event ids in sync.
+            for idx in container_visit_order(&containers, &config) {
+                if !obj.allows_template(containers[idx].template_id)
+                    || reached_target_utilization(&containers[idx], &config)
+                    || !containers[idx].can_fit_with(
+                        oriented,
+                        config.general_epsilon,
+                        config.weight_tolerance(),
+                    )
+                {
                     continue;
                 }
 
-                if let Some(position) = find_stable_position(oriented, &containers[idx], &config) {
+                if let Some(position) = find_stable_position_tracked(
+                    oriented,
+                    &containers[idx],
+                    &config,
+                    rejections.as_mut(),
+                ) {
                     containers[idx].placed.push(PlacedBox {
                         object: oriented.clone(),
                         position,
@@ -789,6 +2271,19 @@ pub fn pack_objects_with_progress(
                         dims: placed.object.dims,
                         total_weight: total_w,
                     });
+                    placements += 1;
+                    emit_snapshots(
+                        &containers,
+                        placements,
+                        &mut snapshot_sizes,
+                        &config,
+                        &mut on_event,
+                    );
+                    stale_placements[idx] += 1;
+                    if stale_placements[idx] < config.diagnostics_interval {
+                        continue 'object_loop;
+                    }
+                    stale_placements[idx] = 0;
                     let diagnostics = compute_container_diagnostics(&containers[idx], &config);
                     if let Some(slot) = container_diagnostics.get_mut(idx) {
                         *slot = diagnostics.clone();
@@ -810,13 +2305,28 @@ pub fn pack_objects_with_progress(
             }
 
             // No existing container is suitable, so try opening a new container.
-            for template in &templates {
-                if !template.can_fit(oriented) {
+            if obj.optional {
+                continue;
+            }
+            for template in templates_for_new_container(&templates, oriented, remaining, &config) {
+                if !obj.allows_template(Some(template.id))
+                    || !template.can_fit_with(
+                        oriented,
+                        config.general_epsilon,
+                        config.weight_tolerance(),
+                    )
+                {
                     continue;
                 }
 
-                let mut new_container = template.instantiate();
-                if let Some(position) = find_stable_position(oriented, &new_container, &config) {
+                let mut new_container =
+                    template.instantiate_nth(template_ordinal(&containers, template));
+                if let Some(position) = find_stable_position_tracked(
+                    oriented,
+                    &new_container,
+                    &config,
+                    rejections.as_mut(),
+                ) {
                     let new_id = containers.len() + 1;
                     let dims = new_container.dims;
                     let max_weight = new_container.max_weight;
@@ -848,6 +2358,17 @@ pub fn pack_objects_with_progress(
                         dims: placed.object.dims,
                         total_weight: total_w,
                     });
+                    placements += 1;
+                    emit_snapshots(
+                        &containers,
+                        placements,
+                        &mut snapshot_sizes,
+                        &config,
+                        &mut on_event,
+                    );
+                    // A new container always starts with real diagnostics; the interval only
+                    // throttles the updates of later placements.
+                    stale_placements.push(0);
                     let diagnostics = containers
                         .last()
                         .map(|c| compute_container_diagnostics(c, &config))
@@ -862,7 +2383,12 @@ pub fn pack_objects_with_progress(
             }
         }
 
-        let reason = determine_unfit_reason_across_templates(&templates, &obj, &config);
+        // An optional object that no container could take at all keeps the usual reason.
+        let reason = if obj.optional && feasible {
+            UnplacedReason::OptionalSkipped
+        } else {
+            determine_unfit_reason_across_templates(&templates, &obj, &config)
+        };
         on_event(&PackEvent::ObjectRejected {
             id: obj.id,
             weight: obj.weight,
@@ -873,53 +2399,350 @@ pub fn pack_objects_with_progress(
         unplaced.push(UnplacedBox {
             object: obj,
             reason,
+            rejections,
         });
     }
+    progress.report(progress.total, &mut on_event);
+
+    for (idx, container) in containers.iter_mut().enumerate() {
+        let fixed = fixed_counts.get(idx).copied().unwrap_or(0);
+        let settled = config.settle && settle_container(container, fixed, &config);
+        let compacted = config.compact && compact_container(container, fixed, &config);
+        if settled || compacted {
+            // The placement events announced the positions before the post-pass.
+            on_event(&snapshot_event(idx + 1, container));
+        }
+        if settled || compacted || stale_placements[idx] > 0 {
+            let diagnostics = compute_container_diagnostics(container, &config);
+            container_diagnostics[idx] = diagnostics.clone();
+            on_event(&PackEvent::ContainerDiagnostics {
+                container_id: idx + 1,
+                diagnostics,
+            });
+        }
+    }
 
     let diagnostics_summary = summarize_diagnostics(container_diagnostics.iter());
     on_event(&PackEvent::Finished {
         containers: containers.len(),
         unplaced: unplaced.len(),
         diagnostics_summary: diagnostics_summary.clone(),
+        container_fill: containers
+            .iter()
+            .zip(&container_diagnostics)
+            .enumerate()
+            .map(|(idx, (container, diagnostics))| {
+                (
+                    idx + 1,
+                    diagnostics.volume_utilization_percent,
+                    container.total_weight(),
+                )
+            })
+            .collect(),
+    });
+    let timings = config.profile.then(|| PhaseTimings {
+        sort_ms,
+        cluster_ms,
+        placement_ms: elapsed_ms(placement_started),
+        total_ms: elapsed_ms(started),
     });
     PackingResult {
         containers,
         unplaced,
         container_diagnostics,
         diagnostics_summary,
+        timings,
     }
 }
 
-/// Finds a stable position for an object in a container.
-///
-/// Searches through different Z-layers, Y and X positions and evaluates each
-/// position for stability, support, weight distribution, and balance.
+/// Emits [`PackEvent::Progress`] according to the configured object and time intervals.
+struct ProgressReporter {
+    started: Instant,
+    last_emitted: Instant,
+    last_processed: usize,
+    total: usize,
+    every: Option<usize>,
+    interval_ms: Option<u64>,
+}
+
+impl ProgressReporter {
+    fn new(started: Instant, total: usize, config: &PackingConfig) -> Self {
+        Self {
+            started,
+            last_emitted: started,
+            last_processed: 0,
+            total,
+            every: config.progress_every,
+            interval_ms: config.progress_interval_ms,
+        }
+    }
+
+    /// Reports `processed` objects if an interval is due; repeated counts are ignored.
+    fn report(&mut self, processed: usize, on_event: &mut impl FnMut(&PackEvent)) {
+        if processed == 0 || processed == self.last_processed {
+            return;
+        }
+        let count_due = self
+            .every
+            .is_some_and(|every| processed.is_multiple_of(every));
+        let time_due = self.interval_ms.is_some_and(|interval| {
+            self.last_emitted.elapsed().as_millis() >= u128::from(interval)
+        });
+        if !count_due && !time_due {
+            return;
+        }
+        let elapsed = self.started.elapsed();
+        on_event(&PackEvent::Progress {
+            processed,
+            total: self.total,
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            rate_per_sec: processed as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        });
+        self.last_emitted = Instant::now();
+        self.last_processed = processed;
+    }
+}
+
+/// Emits a [`PackEvent::Snapshot`] for every container that received objects since the previous
+/// snapshot, once `placements` reaches a multiple of [`PackingConfig::snapshot_every`].
 ///
-/// # Parameters
-/// * `b` - The object to place
-/// * `cont` - The container
-/// * `config` - Configuration parameters
+/// `snapshot_sizes` holds the object count of each container at its last snapshot (or when it
+/// was taken over from a prior result); containers are only ever filled during the run.
+fn emit_snapshots(
+    containers: &[Container],
+    placements: usize,
+    snapshot_sizes: &mut Vec<usize>,
+    config: &PackingConfig,
+    on_event: &mut impl FnMut(&PackEvent),
+) {
+    if !config
+        .snapshot_every
+        .is_some_and(|every| placements.is_multiple_of(every))
+    {
+        return;
+    }
+    snapshot_sizes.resize(containers.len(), 0);
+    for (idx, container) in containers.iter().enumerate() {
+        if snapshot_sizes[idx] != container.placed.len() {
+            snapshot_sizes[idx] = container.placed.len();
+            on_event(&snapshot_event(idx + 1, container));
+        }
+    }
+}
+
+/// Builds a [`PackEvent::Snapshot`] of `container`.
+fn snapshot_event(container_id: usize, container: &Container) -> PackEvent {
+    PackEvent::Snapshot {
+        container_id,
+        placed: container.placed.iter().map(SnapshotObject::from).collect(),
+    }
+}
+
+/// Returns `true` if `object` exceeds the weight capacity of every allowed template and of
+/// every given container.
 ///
-/// # Returns
-/// `Some((x, y, z))` on successful placement, otherwise `None`
-fn find_stable_position(
-    b: &Box3D,
-    cont: &Container,
+/// Objects without any allowed template are left alone so the placement loop reports
+/// [`UnplacedReason::NoAllowedContainer`] for them as before.
+fn exceeds_every_weight_limit(
+    object: &Box3D,
+    templates: &[ContainerBlueprint],
+    containers: &[Container],
     config: &PackingConfig,
-) -> Option<(f64, f64, f64)> {
-    if !cont.can_fit(b) {
-        return None;
+) -> bool {
+    let mut allowed = templates
+        .iter()
+        .filter(|tpl| object.allows_template(Some(tpl.id)))
+        .peekable();
+    allowed.peek().is_some()
+        && allowed.all(|tpl| object.weight > tpl.available_weight() + config.weight_tolerance())
+        && containers.iter().all(|container| {
+            !object.allows_template(container.template_id)
+                || object.weight > container.remaining_weight() + config.weight_tolerance()
+        })
+}
+
+/// Returns `true` once a container's volume utilization has reached `target_utilization`.
+///
+/// Such a container keeps its objects but receives no further ones, leaving headroom for
+/// last-minute additions.
+fn reached_target_utilization(container: &Container, config: &PackingConfig) -> bool {
+    config.target_utilization.is_some_and(|target| {
+        let volume = container.total_volume();
+        volume > config.general_epsilon
+            && container.used_volume() / volume + config.general_epsilon >= target
+    })
+}
+
+/// Collapses container templates whose dimensions and weight limits differ by at most
+/// `tolerance` (relative) into a single representative.
+///
+/// The most capacious template of each group (largest volume, then weight limit) is kept, so
+/// results report its id. Templates with a height profile or reserved space are never merged,
+/// since their usable space is not described by the dimensions alone, and neither are templates
+/// named in an object's `allowed_template_ids` or ranked in a fill order.
+fn merge_similar_templates(
+    templates: Vec<ContainerBlueprint>,
+    tolerance: f64,
+    objects: &[Box3D],
+) -> Vec<ContainerBlueprint> {
+    if tolerance <= 0.0 {
+        return templates;
     }
 
-    // Candidate positions combine a coarse grid with the edges of already placed objects.
+    let close = |a: f64, b: f64| (a - b).abs() / a.abs().max(b.abs()).max(1.0) <= tolerance;
+    // Templates named in an object's whitelist must keep their id, so they are never merged.
+    let whitelisted = |t: &ContainerBlueprint| {
+        objects
+            .iter()
+            .filter_map(|o| o.allowed_template_ids.as_ref())
+            .any(|ids| ids.contains(&t.id))
+    };
+    let is_plain = |t: &ContainerBlueprint| {
+        t.height_profile.is_empty()
+            && t.reserved.is_empty()
+            && t.reserved_weight == 0.0
+            && t.fill_rank.is_none()
+            && t.floor_only_region.is_none()
+            && t.max_items.is_none()
+            && t.cost.is_none()
+            && !whitelisted(t)
+    };
+
+    let mut merged: Vec<ContainerBlueprint> = Vec::with_capacity(templates.len());
+    for template in templates {
+        let similar = is_plain(&template).then(|| {
+            merged.iter().position(|kept| {
+                is_plain(kept)
+                    && close(kept.dims.0, template.dims.0)
+                    && close(kept.dims.1, template.dims.1)
+                    && close(kept.dims.2, template.dims.2)
+                    && close(kept.max_weight, template.max_weight)
+            })
+        });
+
+        match similar.flatten() {
+            Some(idx) => {
+                let kept = &merged[idx];
+                let more_capacious = match template.volume().partial_cmp(&kept.volume()) {
+                    Some(Ordering::Greater) => true,
+                    Some(Ordering::Equal) => template.max_weight > kept.max_weight,
+                    _ => false,
+                };
+                if more_capacious {
+                    merged[idx] = template;
+                }
+            }
+            None => merged.push(template),
+        }
+    }
+    merged
+}
+
+/// 1-based ordinal of the next container opened from `template`, used for label placeholders.
+fn template_ordinal(containers: &[Container], template: &ContainerBlueprint) -> usize {
+    containers
+        .iter()
+        .filter(|c| c.template_id == Some(template.id))
+        .count()
+        + 1
+}
+
+/// Picks the template used to pre-open `count` containers in contract mode.
+///
+/// The smallest template whose combined capacity covers the total object volume and weight is the
+/// tightest fit, regardless of the template preference. Falls back to the largest template.
+fn select_seed_template<'a>(
+    templates: &'a [ContainerBlueprint],
+    objects: &[Box3D],
+    count: usize,
+) -> Option<&'a ContainerBlueprint> {
+    let total_volume: f64 = objects.iter().map(Box3D::volume).sum();
+    let total_weight: f64 = objects.iter().map(|o| o.weight).sum();
+    let count = count as f64;
+
+    let by_capacity = |a: &&ContainerBlueprint, b: &&ContainerBlueprint| {
+        a.volume()
+            .total_cmp(&b.volume())
+            .then_with(|| a.max_weight.total_cmp(&b.max_weight))
+    };
+    templates
+        .iter()
+        .filter(|tpl| {
+            tpl.volume() * count >= total_volume && tpl.available_weight() * count >= total_weight
+        })
+        .min_by(by_capacity)
+        .or_else(|| templates.iter().max_by(by_capacity))
+}
+
+/// Returns the order in which existing containers are tried for the next object.
+///
+/// By default containers are filled in creation order. In contract mode
+/// (`target_container_count`) the lightest container is tried first, which distributes objects
+/// round-robin across the committed containers.
+fn container_visit_order(containers: &[Container], config: &PackingConfig) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..containers.len()).collect();
+    if config.target_container_count.is_some() {
+        order.sort_by(|&a, &b| {
+            containers[a]
+                .total_weight()
+                .total_cmp(&containers[b].total_weight())
+                .then_with(|| a.cmp(&b))
+        });
+    }
+    order
+}
+
+/// Finds a stable position for an object in a container.
+///
+/// Searches through different Z-layers, Y and X positions and evaluates each
+/// position for stability, support, weight distribution, and balance.
+///
+/// # Parameters
+/// * `b` - The object to place
+/// * `cont` - The container
+/// * `config` - Configuration parameters
+///
+/// # Returns
+/// `Some((x, y, z))` on successful placement, otherwise `None`
+fn find_stable_position(
+    b: &Box3D,
+    cont: &Container,
+    config: &PackingConfig,
+) -> Option<(f64, f64, f64)> {
+    find_stable_position_tracked(b, cont, config, None)
+}
+
+/// [`find_stable_position`] that adds the failed check of every rejected candidate to
+/// `rejections`, if given.
+fn find_stable_position_tracked(
+    b: &Box3D,
+    cont: &Container,
+    config: &PackingConfig,
+    mut rejections: Option<&mut RejectionCounts>,
+) -> Option<(f64, f64, f64)> {
+    if !cont.can_fit_with(b, config.general_epsilon, config.weight_tolerance()) {
+        return None;
+    }
+    if config.prefer_column_stacking
+        && let Some(position) = column_stack_position(b, cont, config)
+    {
+        return Some(position);
+    }
+
+    // Candidate positions combine a coarse grid with the edges of already placed objects.
     // Edge-anchored placement lets new objects sit flush against existing ones regardless of
     // grid alignment, which yields tighter packing without depending on a fine grid step.
-    let (x_edges, y_edges) = placed_axis_edges(cont);
+    let (mut x_edges, mut y_edges) = placed_axis_edges(cont);
+    if let Some(hint) = b.position_hint {
+        x_edges.push(hint.0);
+        y_edges.push(hint.1);
+    }
     let xs = candidate_positions(
         cont.dims.0,
         b.dims.0,
         config.grid_step,
         config.general_epsilon,
+        config.max_axis_positions,
         &x_edges,
     );
     let ys = candidate_positions(
@@ -927,27 +2750,34 @@ fn find_stable_position(
         b.dims.1,
         config.grid_step,
         config.general_epsilon,
+        config.max_axis_positions,
         &y_edges,
     );
 
-    // Collect all relevant Z-layers (floor + tops of all placed objects)
-    let mut z_layers: Vec<f64> = cont
-        .placed
-        .iter()
-        .map(|p| p.position.2 + p.object.dims.2)
-        .collect();
-    z_layers.push(0.0);
-    z_layers.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    z_layers.dedup_by(|a, b| (*a - *b).abs() < config.height_epsilon);
-
+    let z_layers = z_layers(cont, config);
     let balance_limit = calculate_balance_limit(cont, config);
     let current_balance = calculate_current_balance_offset(cont);
 
     let mut best_in_limit: Option<((f64, f64, f64), PlacementScore)> = None;
     let mut best_any: Option<((f64, f64, f64), PlacementScore)> = None;
+    let mut best_floor: Option<((f64, f64, f64), PlacementScore)> = None;
 
+    // Footprints within the walls; a layer skipped as a whole counts each of them.
+    let layer_candidates = xs
+        .iter()
+        .filter(|&&x| x + b.dims.0 <= cont.dims.0 + config.general_epsilon)
+        .count()
+        * ys.iter()
+            .filter(|&&y| y + b.dims.1 <= cont.dims.1 + config.general_epsilon)
+            .count();
     for &z in &z_layers {
-        if z + b.dims.2 > cont.dims.2 + config.general_epsilon {
+        if z + b.dims.2 > cont.dims.2 - config.top_clearance + config.general_epsilon
+            || b.max_placement_height
+                .is_some_and(|max| z > max + config.general_epsilon)
+        {
+            if let Some(counts) = rejections.as_deref_mut() {
+                counts.height += layer_candidates;
+            }
             continue;
         }
 
@@ -960,32 +2790,30 @@ fn find_stable_position(
                 if x + b.dims.0 > cont.dims.0 + config.general_epsilon {
                     continue;
                 }
+                if z + b.dims.2
+                    > usable_ceiling(cont, x, x + b.dims.0, config) + config.general_epsilon
+                    || !cont.allows_stacking_at((x, y, z), b.dims, config.general_epsilon)
+                {
+                    if let Some(counts) = rejections.as_deref_mut() {
+                        counts.height += 1;
+                    }
+                    continue;
+                }
 
                 let candidate = PlacedBox {
                     object: b.clone(),
                     position: (x, y, z),
                 };
 
-                // Check for collisions
-                if cont.placed.iter().any(|p| intersects(p, &candidate)) {
-                    continue;
-                }
-
-                // For placement above the floor: Check stability
-                let support_analysis = analyze_support_surface(&candidate, cont, config);
-                if z > 0.0 {
-                    let required_support = (config.support_ratio - config.general_epsilon).max(0.0);
-                    if support_analysis.support_ratio < required_support {
+                let support_analysis = match evaluate_placement(&candidate, cont, config) {
+                    Ok(analysis) => analysis,
+                    Err(rejection) => {
+                        if let Some(counts) = rejections.as_deref_mut() {
+                            counts.record(rejection);
+                        }
                         continue;
                     }
-                    if !support_analysis.supports_weight {
-                        continue;
-                    }
-                    if !support_analysis.center_supported {
-                        // Prevents overhangs where the center of gravity is not supported
-                        continue;
-                    }
-                }
+                };
 
                 let stability =
                     simulate_static_stability_from_analysis(&candidate, config, support_analysis);
@@ -996,6 +2824,10 @@ fn find_stable_position(
                     support_ratio: stability.support_ratio,
                     support_centroid_offset_ratio: stability.support_centroid_offset_ratio,
                     support_contact_count: stability.support_contact_count,
+                    category_contacts: count_category_contacts(cont, &candidate, config),
+                    center_moment: center_moment(cont, &candidate, config),
+                    wall_contacts: count_wall_contacts(cont, &candidate, config),
+                    hint_distance: hint_distance(&candidate),
                     y,
                     x,
                     balance_shift: (balance - current_balance).abs(),
@@ -1003,15 +2835,600 @@ fn find_stable_position(
                 };
 
                 update_best(&mut best_any, (x, y, z), score, config);
+                if z <= config.height_epsilon {
+                    update_best(&mut best_floor, (x, y, z), score, config);
+                }
 
-                if balance <= balance_limit + config.general_epsilon {
+                if within_balance_limits(cont, &candidate, balance, balance_limit, config) {
                     update_best(&mut best_in_limit, (x, y, z), score, config);
+                } else if let Some(counts) = rejections.as_deref_mut() {
+                    counts.balance += 1;
+                }
+            }
+        }
+    }
+
+    // Outside the balance limit, a floor spot is the safer compromise than a stacked one.
+    let fallback = if config.prefer_floor_fallback {
+        best_floor.or(best_any)
+    } else {
+        best_any
+    };
+    best_in_limit.or(fallback).map(|(pos, _)| pos)
+}
+
+/// Slides the objects of a packed container toward the origin to close gaps.
+///
+/// Objects are visited bottom-up in loading order and moved along x, then y, then z up to the
+/// nearest obstacle: toward the origin, except along y with [`LoadDirection::BackToFront`],
+/// where they slide toward the back wall. A move is kept only if the new position passes
+/// [`check_placement`], stays under the ceiling, does not push the load beyond the balance
+/// limit, and every object resting on the old position remains supported. Passes repeat while
+/// anything moves (at most [`PackingConfig::MAX_COMPACTION_PASSES`]). The first `fixed` objects
+/// and objects with a [`Box3D::position_hint`] never move. Returns `true` if any object was
+/// moved.
+fn compact_container(container: &mut Container, fixed: usize, config: &PackingConfig) -> bool {
+    let from_back = config.load_direction == LoadDirection::BackToFront;
+    let mut moved_any = false;
+    for _ in 0..PackingConfig::MAX_COMPACTION_PASSES {
+        let mut order: Vec<usize> = (fixed..container.placed.len())
+            .filter(|&idx| container.placed[idx].object.position_hint.is_none())
+            .collect();
+        order.sort_by(|&a, &b| {
+            let (pa, pb) = (container.placed[a].position, container.placed[b].position);
+            let y_order = pa.1.total_cmp(&pb.1);
+            pa.2.total_cmp(&pb.2)
+                .then(if from_back {
+                    y_order.reverse()
+                } else {
+                    y_order
+                })
+                .then(pa.0.total_cmp(&pb.0))
+        });
+
+        let mut moved = false;
+        for idx in order {
+            for axis in 0..3 {
+                let toward_origin = axis != 1 || !from_back;
+                let current = container.placed.remove(idx);
+                let next = slide_to_wall(&current, container, axis, toward_origin, config)
+                    .filter(|next| compacted_position_is_valid(&current, next, container, config));
+                moved |= next.is_some();
+                container.placed.insert(idx, next.unwrap_or(current));
+            }
+        }
+        if !moved {
+            break;
+        }
+        moved_any = true;
+    }
+    moved_any
+}
+
+/// Orders the templates tried when a new container is opened (see [`NewContainerPolicy`]).
+///
+/// `templates` is expected in [`TemplatePreference`] order; `remaining_volume` is the volume of
+/// all objects still to be placed, including the current one. Among templates of equal volume,
+/// the one leaving the least headroom above `object` comes first, so short items do not waste
+/// tall containers.
+fn templates_for_new_container<'a>(
+    templates: &'a [ContainerBlueprint],
+    object: &Box3D,
+    remaining_volume: f64,
+    config: &PackingConfig,
+) -> Vec<&'a ContainerBlueprint> {
+    let by_volume = |a: &&ContainerBlueprint, b: &&ContainerBlueprint| {
+        a.volume()
+            .partial_cmp(&b.volume())
+            .unwrap_or(Ordering::Equal)
+    };
+    let mut ordered: Vec<&ContainerBlueprint> = templates.iter().collect();
+    match config.new_container_policy {
+        NewContainerPolicy::SmallestFitting => {}
+        NewContainerPolicy::LargestAvailable => ordered.sort_by(|a, b| by_volume(b, a)),
+        NewContainerPolicy::BestFitForRemaining => {
+            let covers =
+                |t: &ContainerBlueprint| t.volume() + config.general_epsilon >= remaining_volume;
+            let (mut covering, mut short): (Vec<_>, Vec<_>) =
+                ordered.into_iter().partition(|t| covers(t));
+            covering.sort_by(by_volume);
+            short.sort_by(|a, b| by_volume(b, a));
+            covering.extend(short);
+            ordered = covering;
+        }
+    }
+    let headroom = |t: &ContainerBlueprint| {
+        let headroom = t.dims.2 - object.dims.2;
+        if headroom < -config.general_epsilon {
+            f64::INFINITY
+        } else {
+            headroom
+        }
+    };
+    for run in
+        ordered.chunk_by_mut(|a, b| (a.volume() - b.volume()).abs() <= config.general_epsilon)
+    {
+        run.sort_by(|a, b| headroom(a).total_cmp(&headroom(b)));
+    }
+    // A fixed fill order overrides the policy; unranked templates follow the ranked ones.
+    ordered.sort_by_key(|t| t.fill_rank.unwrap_or(usize::MAX));
+    ordered
+}
+
+/// Lowest fill rank among the allowed templates `object` fits into (`usize::MAX` if none).
+///
+/// Objects are packed tier by tier, so no container of a later template is opened while an
+/// earlier one could still take any of the remaining objects.
+fn fill_tier(object: &Box3D, templates: &[ContainerBlueprint], config: &PackingConfig) -> usize {
+    let orientations = orientations_for(object, rotation_allowed(object, config));
+    templates
+        .iter()
+        .filter(|t| object.allows_template(Some(t.id)))
+        .filter(|t| {
+            orientations
+                .iter()
+                .any(|o| t.can_fit_with(o, config.general_epsilon, config.weight_tolerance()))
+        })
+        .filter_map(|t| t.fill_rank)
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
+/// Drops floating objects of a packed container onto the highest surface beneath them.
+///
+/// Objects are visited bottom-up and lowered along z with the same validation as compaction:
+/// the new position must pass [`check_placement`] and every object resting on the old position
+/// must stay supported. An object that cannot drop alone because others rest on it is lowered
+/// together with them (see [`lower_stack`]), so a floating stack comes down as a whole.
+/// Passes repeat until nothing moves; every move lowers an object onto a lower surface, so this
+/// terminates. The first `fixed` objects never move. Returns `true` if any object was lowered.
+fn settle_container(container: &mut Container, fixed: usize, config: &PackingConfig) -> bool {
+    let mut moved_any = false;
+    loop {
+        let mut order: Vec<usize> = (fixed..container.placed.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (pa, pb) = (container.placed[a].position, container.placed[b].position);
+            pa.2.total_cmp(&pb.2)
+        });
+
+        let mut moved = false;
+        for idx in order {
+            let current = container.placed.remove(idx);
+            let next = slide_to_wall(&current, container, 2, true, config)
+                .filter(|next| compacted_position_is_valid(&current, next, container, config));
+            let dropped = next.is_some();
+            moved |= dropped;
+            container.placed.insert(idx, next.unwrap_or(current));
+            if !dropped && let Some(moves) = lower_stack(container, idx, fixed, config) {
+                for (moved_idx, placed) in moves {
+                    container.placed[moved_idx] = placed;
+                }
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+        moved_any = true;
+    }
+    moved_any
+}
+
+/// Lowers the object at `idx` together with every object resting on it, directly or indirectly.
+///
+/// The object drops onto the highest surface beneath it and the objects above follow by the same
+/// distance, bottom-up, each validated like a single settling move against everything already
+/// in place. Returns the new placements by index, or `None` if nothing rests on the object, a
+/// fixed object does, or any move of the stack fails.
+fn lower_stack(
+    container: &Container,
+    idx: usize,
+    fixed: usize,
+    config: &PackingConfig,
+) -> Option<Vec<(usize, PlacedBox)>> {
+    let placed = &container.placed;
+    let mut stack = vec![idx];
+    let mut next_lower = 0;
+    while let Some(&lower) = stack.get(next_lower) {
+        next_lower += 1;
+        for (upper, candidate) in placed.iter().enumerate() {
+            if !stack.contains(&upper) && rests_on(candidate, &placed[lower], config.height_epsilon)
+            {
+                if upper < fixed {
+                    return None;
                 }
+                stack.push(upper);
+            }
+        }
+    }
+    if stack.len() == 1 {
+        return None;
+    }
+    stack[1..].sort_by(|&a, &b| placed[a].position.2.total_cmp(&placed[b].position.2));
+
+    let mut rest = container.clone();
+    rest.placed = placed
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !stack.contains(i))
+        .map(|(_, p)| p.clone())
+        .collect();
+    let bottom = &placed[idx];
+    let lowered = slide_to_wall(bottom, &rest, 2, true, config)
+        .filter(|next| compacted_position_is_valid(bottom, next, &rest, config))?;
+    let drop = bottom.position.2 - lowered.position.2;
+
+    let mut moves = Vec::with_capacity(stack.len());
+    for &i in &stack {
+        let from = &placed[i];
+        let to = if i == idx {
+            lowered.clone()
+        } else {
+            let (x, y, z) = from.position;
+            let to = PlacedBox {
+                position: (x, y, z - drop),
+                ..from.clone()
+            };
+            if !compacted_position_is_valid(from, &to, &rest, config) {
+                return None;
+            }
+            to
+        };
+        rest.placed.push(to.clone());
+        moves.push((i, to));
+    }
+    Some(moves)
+}
+
+/// Returns `value`'s coordinate along `axis` (0 = x, 1 = y, 2 = z).
+fn axis_component(value: (f64, f64, f64), axis: usize) -> f64 {
+    match axis {
+        0 => value.0,
+        1 => value.1,
+        _ => value.2,
+    }
+}
+
+/// Moves `placed` along `axis` until it touches the nearest obstacle or the container wall.
+///
+/// Slides toward the origin, or toward the far wall if `toward_origin` is `false`. Returns
+/// `None` if the object already touches an obstacle on that side.
+fn slide_to_wall(
+    placed: &PlacedBox,
+    cont: &Container,
+    axis: usize,
+    toward_origin: bool,
+    config: &PackingConfig,
+) -> Option<PlacedBox> {
+    let eps = config.general_epsilon;
+    let start = axis_component(placed.position, axis);
+    let size = axis_component(placed.object.dims, axis);
+    let overlaps_across = |other: &PlacedBox| {
+        (0..3)
+            .filter(|&other_axis| other_axis != axis)
+            .all(|other_axis| {
+                overlap_1d(
+                    axis_component(placed.position, other_axis),
+                    axis_component(placed.position, other_axis)
+                        + axis_component(placed.object.dims, other_axis),
+                    axis_component(other.position, other_axis),
+                    axis_component(other.position, other_axis)
+                        + axis_component(other.object.dims, other_axis),
+                ) > eps
+            })
+    };
+    let obstacles = cont
+        .placed
+        .iter()
+        .chain(&cont.reserved)
+        .filter(|other| overlaps_across(other));
+    let target = if toward_origin {
+        obstacles
+            .map(|other| {
+                axis_component(other.position, axis) + axis_component(other.object.dims, axis)
+            })
+            .filter(|&end| end <= start + eps)
+            .fold(0.0, f64::max)
+    } else {
+        obstacles
+            .map(|other| axis_component(other.position, axis))
+            .filter(|&other_start| other_start >= start + size - eps)
+            .fold(axis_component(cont.dims, axis), f64::min)
+            - size
+    };
+    if (target - start).abs() <= eps || (target > start) == toward_origin {
+        return None;
+    }
+
+    let mut moved = placed.clone();
+    match axis {
+        0 => moved.position.0 = target,
+        1 => moved.position.1 = target,
+        _ => moved.position.2 = target,
+    }
+    Some(moved)
+}
+
+/// Finds the lowest valid spot directly on top of a placed object with the same footprint.
+///
+/// Fast path for [`PackingConfig::prefer_column_stacking`]: identical boxes form neat columns
+/// instead of spreading over the floor. Candidates face the same rules as the grid search,
+/// including the balance limit; `None` leaves the decision to the full search.
+fn column_stack_position(
+    b: &Box3D,
+    cont: &Container,
+    config: &PackingConfig,
+) -> Option<(f64, f64, f64)> {
+    let eps = config.general_epsilon;
+    let balance_limit = calculate_balance_limit(cont, config);
+    cont.placed
+        .iter()
+        .filter(|p| {
+            (p.object.dims.0 - b.dims.0).abs() <= eps && (p.object.dims.1 - b.dims.1).abs() <= eps
+        })
+        .map(|p| (p.position.0, p.position.1, p.position.2 + p.object.dims.2))
+        .filter(|&(x, y, z)| {
+            z + b.dims.2 <= usable_ceiling(cont, x, x + b.dims.0, config) + eps
+                && cont.allows_stacking_at((x, y, z), b.dims, eps)
+                && b.max_placement_height.is_none_or(|max| z <= max + eps)
+        })
+        .filter(|&position| {
+            let candidate = PlacedBox {
+                object: b.clone(),
+                position,
+            };
+            let balance = calculate_balance_after(cont, &candidate);
+            check_placement(&candidate, cont, config).is_some()
+                && within_balance_limits(cont, &candidate, balance, balance_limit, config)
+        })
+        .min_by(|a, b| {
+            a.2.total_cmp(&b.2)
+                .then(a.1.total_cmp(&b.1))
+                .then(a.0.total_cmp(&b.0))
+        })
+}
+
+/// Returns the height available to an object spanning `x_start..x_end`: the (possibly stepped)
+/// ceiling minus [`PackingConfig::top_clearance`].
+fn usable_ceiling(cont: &Container, x_start: f64, x_end: f64, config: &PackingConfig) -> f64 {
+    cont.ceiling_between(x_start, x_end) - config.top_clearance
+}
+
+/// Collects the candidate Z-layers: the floor plus the tops of all placed objects, ascending and
+/// merged within `height_epsilon`.
+///
+/// Non-finite tops (only possible with hand-built containers) are skipped instead of breaking
+/// the sort.
+fn z_layers(cont: &Container, config: &PackingConfig) -> Vec<f64> {
+    let mut layers: Vec<f64> = cont
+        .placed
+        .iter()
+        .map(|p| p.position.2 + p.object.dims.2)
+        .filter(|top| top.is_finite())
+        .collect();
+    layers.push(0.0);
+    layers.sort_by(f64::total_cmp);
+    layers.dedup_by(|a, b| (*a - *b).abs() < config.height_epsilon);
+    layers
+}
+
+/// Validates a compaction or settling move from `from` to `to` within `cont` (which excludes the object).
+fn compacted_position_is_valid(
+    from: &PlacedBox,
+    to: &PlacedBox,
+    cont: &Container,
+    config: &PackingConfig,
+) -> bool {
+    let (x, _, z) = to.position;
+    let (w, _, h) = to.object.dims;
+    if z + h > usable_ceiling(cont, x, x + w, config) + config.general_epsilon {
+        return false;
+    }
+    if !cont.allows_stacking_at(to.position, to.object.dims, config.general_epsilon) {
+        return false;
+    }
+    if check_placement(to, cont, config).is_none() {
+        return false;
+    }
+    // The move may not push the load beyond the balance limit, or further beyond it.
+    let balance = calculate_balance_after(cont, to);
+    if !within_balance_limits(
+        cont,
+        to,
+        balance,
+        calculate_balance_limit(cont, config),
+        config,
+    ) && balance > calculate_balance_after(cont, from) + config.general_epsilon
+    {
+        return false;
+    }
+
+    // Objects resting on the old position must still be supported after the move.
+    let old_top = from.position.2 + from.object.dims.2;
+    let mut after = cont.clone();
+    after.placed.push(to.clone());
+    cont.placed
+        .iter()
+        .filter(|p| (p.position.2 - old_top).abs() <= config.height_epsilon)
+        .all(|p| meets_support_requirements(&analyze_support_surface(p, &after, config), config))
+}
+
+/// Applies the placement rules to `candidate` against the load of `cont`.
+///
+/// Returns the support analysis if the candidate neither collides with the load or reserved
+/// space nor violates the stability, layering, access or clearance rules. Container bounds and
+/// the ceiling are left to the caller.
+fn check_placement(
+    candidate: &PlacedBox,
+    cont: &Container,
+    config: &PackingConfig,
+) -> Option<SupportAnalysis> {
+    evaluate_placement(candidate, cont, config).ok()
+}
+
+/// [`check_placement`] that reports which check a rejected candidate failed.
+fn evaluate_placement(
+    candidate: &PlacedBox,
+    cont: &Container,
+    config: &PackingConfig,
+) -> Result<SupportAnalysis, CandidateRejection> {
+    // Check for collisions with the load and with reserved space
+    if cont
+        .placed
+        .iter()
+        .chain(&cont.reserved)
+        .any(|p| intersects(p, candidate))
+    {
+        return Err(CandidateRejection::Collision);
+    }
+
+    // For placement above the floor: Check stability
+    let support_analysis = analyze_support_surface(candidate, cont, config);
+    if candidate.position.2 > 0.0
+        && let Some(rejection) = support_rejection(&support_analysis, config)
+    {
+        return Err(rejection);
+    }
+
+    if config.strict_layering && !layer_weights_non_increasing(cont, candidate, config) {
+        return Err(CandidateRejection::Weight);
+    }
+
+    if !keeps_access_corridors_free(cont, candidate, config) {
+        return Err(CandidateRejection::Collision);
+    }
+
+    if !keeps_min_gaps(cont, candidate, config) {
+        return Err(CandidateRejection::Collision);
+    }
+
+    Ok(support_analysis)
+}
+
+/// Checks the support rules for an object resting above the floor.
+fn meets_support_requirements(analysis: &SupportAnalysis, config: &PackingConfig) -> bool {
+    support_rejection(analysis, config).is_none()
+}
+
+/// First support rule `analysis` violates, if any.
+fn support_rejection(
+    analysis: &SupportAnalysis,
+    config: &PackingConfig,
+) -> Option<CandidateRejection> {
+    let required_support = (config.support_ratio - config.general_epsilon).max(0.0);
+    if analysis.support_ratio < required_support {
+        return Some(CandidateRejection::Support);
+    }
+    if !analysis.supports_weight {
+        return Some(CandidateRejection::Weight);
+    }
+    if !analysis.center_supported {
+        // Prevents overhangs where the center of gravity is not supported
+        return Some(CandidateRejection::Center);
+    }
+    let overhang_ok = config
+        .max_overhang
+        .is_none_or(|max_overhang| analysis.max_overhang <= max_overhang + config.general_epsilon);
+    (!overhang_ok).then_some(CandidateRejection::Support)
+}
+
+/// Checks the strict layering rule for a container after adding `candidate`.
+///
+/// Objects are grouped into Z-layers by their bottom height (within `height_epsilon`). The
+/// average object weight per layer must not increase from the floor upwards, which enforces
+/// heaviest-on-bottom across the whole container instead of only between direct supports.
+fn layer_weights_non_increasing(
+    cont: &Container,
+    candidate: &PlacedBox,
+    config: &PackingConfig,
+) -> bool {
+    // (bottom z, total weight, object count) per layer
+    let mut layers: Vec<(f64, f64, usize)> = Vec::new();
+    for placed in cont.placed.iter().chain(std::iter::once(candidate)) {
+        let z = placed.position.2;
+        match layers
+            .iter_mut()
+            .find(|(layer_z, _, _)| (*layer_z - z).abs() <= config.height_epsilon)
+        {
+            Some(layer) => {
+                layer.1 += placed.object.weight;
+                layer.2 += 1;
             }
+            None => layers.push((z, placed.object.weight, 1)),
         }
     }
+    layers.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    layers.windows(2).all(|pair| {
+        pair[1].1 / pair[1].2 as f64 <= pair[0].1 / pair[0].2 as f64 + config.general_epsilon
+    })
+}
+
+/// Checks that `candidate` neither is blocked nor blocks an accessible object.
+///
+/// An object flagged `must_be_accessible` needs the corridor between its front face and the
+/// door (`y = 0`) within its XZ footprint to stay empty. This applies to the candidate itself
+/// and to every accessible object that is already placed.
+fn keeps_access_corridors_free(
+    cont: &Container,
+    candidate: &PlacedBox,
+    config: &PackingConfig,
+) -> bool {
+    let eps = config.general_epsilon;
+    let blocks = |blocker: &PlacedBox, target: &PlacedBox| {
+        blocker.position.1 < target.position.1 - eps
+            && overlap_1d(
+                blocker.position.0,
+                blocker.position.0 + blocker.object.dims.0,
+                target.position.0,
+                target.position.0 + target.object.dims.0,
+            ) > eps
+            && overlap_1d(
+                blocker.position.2,
+                blocker.position.2 + blocker.object.dims.2,
+                target.position.2,
+                target.position.2 + target.object.dims.2,
+            ) > eps
+    };
+
+    !cont.placed.iter().chain(&cont.reserved).any(|placed| {
+        (candidate.object.must_be_accessible && blocks(placed, candidate))
+            || (placed.object.must_be_accessible && blocks(candidate, placed))
+    })
+}
 
-    best_in_limit.or(best_any).map(|(pos, _)| pos)
+/// Checks the pairwise clearances (`min_gaps`) between `candidate` and the placed objects.
+///
+/// Either side of a pair may declare the gap; the larger requirement applies. Two boxes are
+/// far enough apart once they are separated by at least the gap along any one axis.
+fn keeps_min_gaps(cont: &Container, candidate: &PlacedBox, config: &PackingConfig) -> bool {
+    cont.placed.iter().all(|placed| {
+        let gap = candidate
+            .object
+            .required_gap_to(placed.object.id)
+            .max(placed.object.required_gap_to(candidate.object.id));
+        if gap <= 0.0 {
+            return true;
+        }
+        let separation = |start_a: f64, len_a: f64, start_b: f64, len_b: f64| {
+            (start_b - (start_a + len_a)).max(start_a - (start_b + len_b))
+        };
+        let (a, b) = (candidate, placed);
+        let widest = separation(a.position.0, a.object.dims.0, b.position.0, b.object.dims.0)
+            .max(separation(
+                a.position.1,
+                a.object.dims.1,
+                b.position.1,
+                b.object.dims.1,
+            ))
+            .max(separation(
+                a.position.2,
+                a.object.dims.2,
+                b.position.2,
+                b.object.dims.2,
+            ));
+        widest >= gap - config.general_epsilon
+    })
 }
 
 /// Generates possible positions along an axis.
@@ -1023,7 +3440,14 @@ fn find_stable_position(
 /// * `object_len` - Length of the object in this dimension
 /// * `step` - Step size of the grid
 /// * `epsilon` - Numerical tolerance
-fn axis_positions(container_len: f64, object_len: f64, step: f64, epsilon: f64) -> Vec<f64> {
+/// * `max_positions` - Cap on the number of grid positions (0 = unlimited)
+fn axis_positions(
+    container_len: f64,
+    object_len: f64,
+    step: f64,
+    epsilon: f64,
+    max_positions: usize,
+) -> Vec<f64> {
     let max_pos = (container_len - object_len).max(0.0);
     let mut positions = Vec::new();
 
@@ -1032,6 +3456,7 @@ fn axis_positions(container_len: f64, object_len: f64, step: f64, epsilon: f64)
         return positions;
     }
 
+    let step = effective_grid_step(max_pos, step, max_positions);
     let mut pos = 0.0;
     while pos <= max_pos + epsilon {
         positions.push(pos.min(max_pos));
@@ -1051,15 +3476,36 @@ fn axis_positions(container_len: f64, object_len: f64, step: f64, epsilon: f64)
     positions
 }
 
-/// Collects the X and Y edge coordinates of every placed object.
+/// Coarsens `step` so that the grid over `0..=max_pos` yields at most `max_positions` entries.
 ///
-/// Both the near edge (object position) and the far edge (position + dimension) are returned so a
-/// new object can be placed flush against either side of an existing one.
-fn placed_axis_edges(cont: &Container) -> (Vec<f64>, Vec<f64>) {
-    let mut x_edges = Vec::with_capacity(cont.placed.len() * 2);
-    let mut y_edges = Vec::with_capacity(cont.placed.len() * 2);
-    for p in &cont.placed {
-        x_edges.push(p.position.0);
+/// Huge containers combined with a tiny grid step would otherwise allocate millions of candidate
+/// positions per axis. [`ContainerDiagnostics::coarsened_grid_step`] reports when the cap kicks in.
+fn effective_grid_step(max_pos: f64, step: f64, max_positions: usize) -> f64 {
+    if max_positions == 0 {
+        return step;
+    }
+
+    // The boundary positions (0 and max_pos) are always generated, so tiny caps are widened.
+    let max_positions = max_positions.max(3);
+    let requested = (max_pos / step).floor() + 1.0;
+    if requested <= max_positions as f64 {
+        return step;
+    }
+
+    // One slot is reserved for max_pos, which is appended when accumulated rounding stops short.
+    max_pos / (max_positions - 2) as f64
+}
+
+/// Collects the X and Y edge coordinates of every placed object and reserved block.
+///
+/// Both the near edge (object position) and the far edge (position + dimension) are returned so a
+/// new object can be placed flush against either side of an existing one.
+fn placed_axis_edges(cont: &Container) -> (Vec<f64>, Vec<f64>) {
+    let count = cont.placed.len() + cont.reserved.len();
+    let mut x_edges = Vec::with_capacity(count * 2);
+    let mut y_edges = Vec::with_capacity(count * 2);
+    for p in cont.placed.iter().chain(&cont.reserved) {
+        x_edges.push(p.position.0);
         x_edges.push(p.position.0 + p.object.dims.0);
         y_edges.push(p.position.1);
         y_edges.push(p.position.1 + p.object.dims.1);
@@ -1077,10 +3523,11 @@ fn candidate_positions(
     object_len: f64,
     step: f64,
     epsilon: f64,
+    max_positions: usize,
     edges: &[f64],
 ) -> Vec<f64> {
     let max_pos = (container_len - object_len).max(0.0);
-    let mut positions = axis_positions(container_len, object_len, step, epsilon);
+    let mut positions = axis_positions(container_len, object_len, step, epsilon, max_positions);
 
     if max_pos <= epsilon || edges.is_empty() {
         return positions;
@@ -1099,6 +3546,9 @@ fn candidate_positions(
 
 /// Calculates the ratio of an object's base area that is supported.
 ///
+/// Uses exact rectangle overlaps by default, or the sample grid configured via
+/// `support_samples_per_axis` (see [`sampled_support_ratio`]).
+///
 /// # Parameters
 /// * `b` - The placed object to check
 /// * `cont` - The container
@@ -1119,13 +3569,21 @@ fn support_ratio_of(b: &PlacedBox, cont: &Container, config: &PackingConfig) ->
 /// * `cont` - The container
 /// * `new_box` - The object to add
 fn calculate_balance_after(cont: &Container, new_box: &PlacedBox) -> f64 {
+    match center_of_mass_after(cont, new_box) {
+        Some(cm) => distance_2d(cm, container_center_xy(cont)),
+        None => 0.0,
+    }
+}
+
+/// Computes the XY center of gravity of the load after adding `new_box`.
+fn center_of_mass_after(cont: &Container, new_box: &PlacedBox) -> Option<(f64, f64)> {
     let new_point = (
         new_box.position.0 + new_box.object.dims.0 / 2.0,
         new_box.position.1 + new_box.object.dims.1 / 2.0,
         new_box.object.weight,
     );
 
-    match compute_center_of_mass_xy(
+    compute_center_of_mass_xy(
         cont.placed
             .iter()
             .map(|p| {
@@ -1136,10 +3594,38 @@ fn calculate_balance_after(cont: &Container, new_box: &PlacedBox) -> f64 {
                 )
             })
             .chain(std::iter::once(new_point)),
-    ) {
-        Some(cm) => distance_2d(cm, container_center_xy(cont)),
-        None => 0.0,
+    )
+}
+
+/// Checks whether adding `new_box` keeps the load within the balance limits.
+///
+/// By default the Euclidean offset `balance` is compared with the combined `balance_limit`.
+/// Once `balance_limit_ratio_x` or `balance_limit_ratio_y` is set, each axis is checked on its
+/// own against that ratio of the half width or depth; the box fails if either axis exceeds its
+/// limit. An axis without its own ratio falls back to `balance_limit_ratio`.
+fn within_balance_limits(
+    cont: &Container,
+    new_box: &PlacedBox,
+    balance: f64,
+    balance_limit: f64,
+    config: &PackingConfig,
+) -> bool {
+    if config.balance_limit_ratio_x.is_none() && config.balance_limit_ratio_y.is_none() {
+        return balance <= balance_limit + config.general_epsilon;
     }
+
+    let Some((cx, cy)) = center_of_mass_after(cont, new_box) else {
+        return true;
+    };
+    let (center_x, center_y) = container_center_xy(cont);
+    let ratio_x = config
+        .balance_limit_ratio_x
+        .unwrap_or(config.balance_limit_ratio);
+    let ratio_y = config
+        .balance_limit_ratio_y
+        .unwrap_or(config.balance_limit_ratio);
+    (cx - center_x).abs() <= cont.dims.0 / 2.0 * ratio_x + config.general_epsilon
+        && (cy - center_y).abs() <= cont.dims.1 / 2.0 * ratio_y + config.general_epsilon
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1150,6 +3636,44 @@ struct StaticStabilityMetrics {
     instability_score: f64,
 }
 
+/// Weight-order rule: may a box of `upper_weight` rest on one of `lower_weight`?
+///
+/// Heavier boxes never go on lighter ones. Weights within `general_epsilon` count as equal and
+/// may stack only with `allow_equal_weight_stacking`. Placement and diagnostics both use this
+/// single comparison so they cannot disagree at the boundary.
+fn weight_may_rest_on(upper_weight: f64, lower_weight: f64, config: &PackingConfig) -> bool {
+    if config.allow_equal_weight_stacking {
+        upper_weight <= lower_weight + config.general_epsilon
+    } else {
+        upper_weight + config.general_epsilon < lower_weight
+    }
+}
+
+/// Counts stacked pairs in which the upper box is directly supported by a box it may not rest on.
+fn count_weight_order_violations(cont: &Container, config: &PackingConfig) -> usize {
+    cont.placed
+        .iter()
+        .flat_map(|upper| cont.placed.iter().map(move |lower| (upper, lower)))
+        .filter(|(upper, lower)| {
+            !std::ptr::eq(*upper, *lower)
+                && (lower.top_z() - upper.position.2).abs() <= config.height_epsilon
+                && overlap_1d(
+                    lower.position.0,
+                    lower.position.0 + lower.object.dims.0,
+                    upper.position.0,
+                    upper.position.0 + upper.object.dims.0,
+                ) > config.general_epsilon
+                && overlap_1d(
+                    lower.position.1,
+                    lower.position.1 + lower.object.dims.1,
+                    upper.position.1,
+                    upper.position.1 + upper.object.dims.1,
+                ) > config.general_epsilon
+                && !weight_may_rest_on(upper.object.weight, lower.object.weight, config)
+        })
+        .count()
+}
+
 #[derive(Clone, Copy, Debug)]
 struct SupportAnalysis {
     support_ratio: f64,
@@ -1157,11 +3681,62 @@ struct SupportAnalysis {
     support_centroid_offset_ratio: f64,
     supports_weight: bool,
     center_supported: bool,
+    /// Largest distance the base protrudes beyond the bounding extent of its supports
+    max_overhang: f64,
+    /// Distance of the base center from the edge of the supports' bounding extent, relative to
+    /// half the shorter base edge (0 on or outside the edge, at most 1)
+    center_edge_margin: f64,
 }
 
 const SUPPORT_DEFICIT_WEIGHT: f64 = 4.0;
 const SINGLE_SUPPORT_CONTACT_PENALTY: f64 = 0.15;
 
+/// Estimates the supported share of an object's base by sampling.
+///
+/// The base is divided into `samples × samples` cells, and the share of cell centers lying on a
+/// supporting surface is returned. Unlike the exact rectangle sum, this only needs a
+/// point-in-surface test, so it also works for support surfaces that are not plain rectangles.
+/// The result converges to the exact ratio as `samples` grows.
+fn sampled_support_ratio(
+    b: &PlacedBox,
+    cont: &Container,
+    config: &PackingConfig,
+    samples: usize,
+) -> f64 {
+    let (bx, by, bz) = b.position;
+    if bz <= config.height_epsilon {
+        return 1.0;
+    }
+    let (bw, bd, _) = b.object.dims;
+    let supports: Vec<&PlacedBox> = cont
+        .placed
+        .iter()
+        .filter(|p| (bz - (p.position.2 + p.object.dims.2)).abs() <= config.height_epsilon)
+        .collect();
+    if supports.is_empty() || samples == 0 {
+        return 0.0;
+    }
+
+    let step_x = bw / samples as f64;
+    let step_y = bd / samples as f64;
+    let mut supported = 0usize;
+    for i in 0..samples {
+        let x = bx + (i as f64 + 0.5) * step_x;
+        for j in 0..samples {
+            let y = by + (j as f64 + 0.5) * step_y;
+            if supports.iter().any(|p| {
+                x >= p.position.0
+                    && x <= p.position.0 + p.object.dims.0
+                    && y >= p.position.1
+                    && y <= p.position.1 + p.object.dims.1
+            }) {
+                supported += 1;
+            }
+        }
+    }
+    supported as f64 / (samples * samples) as f64
+}
+
 fn analyze_support_surface(
     b: &PlacedBox,
     cont: &Container,
@@ -1174,6 +3749,8 @@ fn analyze_support_surface(
             support_centroid_offset_ratio: 0.0,
             supports_weight: true,
             center_supported: true,
+            max_overhang: 0.0,
+            center_edge_margin: 1.0,
         };
     }
 
@@ -1188,6 +3765,8 @@ fn analyze_support_surface(
     let mut support_contacts = 0usize;
     let mut supports_weight = true;
     let mut center_supported = false;
+    // Bounding extent (min x, max x, min y, max y) of all support contact areas
+    let mut supported_extent: Option<(f64, f64, f64, f64)> = None;
 
     for p in &cont.placed {
         let support_surface_z = p.position.2 + p.object.dims.2;
@@ -1202,15 +3781,32 @@ fn analyze_support_surface(
         }
 
         let overlap_area = over_x * over_y;
-        let overlap_center_x = bx.max(p.position.0) + over_x / 2.0;
-        let overlap_center_y = by.max(p.position.1) + over_y / 2.0;
+        let overlap_min_x = bx.max(p.position.0);
+        let overlap_min_y = by.max(p.position.1);
+        let overlap_center_x = overlap_min_x + over_x / 2.0;
+        let overlap_center_y = overlap_min_y + over_y / 2.0;
+        let contact = (
+            overlap_min_x,
+            overlap_min_x + over_x,
+            overlap_min_y,
+            overlap_min_y + over_y,
+        );
+        supported_extent = Some(match supported_extent {
+            None => contact,
+            Some((min_x, max_x, min_y, max_y)) => (
+                min_x.min(contact.0),
+                max_x.max(contact.1),
+                min_y.min(contact.2),
+                max_y.max(contact.3),
+            ),
+        });
 
         support_area += overlap_area;
         support_center_x += overlap_center_x * overlap_area;
         support_center_y += overlap_center_y * overlap_area;
         support_contacts += 1;
 
-        if p.object.weight + config.general_epsilon < b.object.weight {
+        if !weight_may_rest_on(b.object.weight, p.object.weight, config) {
             supports_weight = false;
         }
 
@@ -1219,8 +3815,29 @@ fn analyze_support_surface(
         }
     }
 
-    let support_ratio = (support_area / base_area).clamp(0.0, 1.0);
+    let support_ratio = if config.support_samples_per_axis > 0 {
+        sampled_support_ratio(b, cont, config, config.support_samples_per_axis)
+    } else {
+        (support_area / base_area).clamp(0.0, 1.0)
+    };
+    let max_overhang = match supported_extent {
+        Some((min_x, max_x, min_y, max_y)) => (min_x - bx)
+            .max(bx + bw - max_x)
+            .max(min_y - by)
+            .max(by + bd - max_y)
+            .max(0.0),
+        None => bw.max(bd),
+    };
     let min_base_edge = bw.min(bd).max(config.general_epsilon);
+    let center_edge_margin = match supported_extent {
+        Some((min_x, max_x, min_y, max_y)) => ((center_xy.0 - min_x)
+            .min(max_x - center_xy.0)
+            .min(center_xy.1 - min_y)
+            .min(max_y - center_xy.1)
+            / (min_base_edge / 2.0))
+            .clamp(0.0, 1.0),
+        None => 0.0,
+    };
     let support_centroid_offset_ratio = if support_area >= min_base_area {
         let centroid = (
             support_center_x / support_area,
@@ -1239,9 +3856,26 @@ fn analyze_support_surface(
         // Without any supporting contacts, the candidate is inherently unsupported for load transfer.
         supports_weight: support_contacts > 0 && supports_weight,
         center_supported,
+        max_overhang,
+        center_edge_margin,
     }
 }
 
+/// Combines the support ratio above the configured minimum with the base center's distance
+/// from the edge of its supports into a margin from 0 (at the limit) to 1 (safest).
+fn stability_margin(b: &PlacedBox, cont: &Container, config: &PackingConfig) -> f64 {
+    let support = analyze_support_surface(b, cont, config);
+    let spare = 1.0 - config.support_ratio;
+    let ratio_margin = if spare > config.general_epsilon {
+        ((support.support_ratio - config.support_ratio) / spare).clamp(0.0, 1.0)
+    } else if support.support_ratio + config.general_epsilon >= 1.0 {
+        1.0
+    } else {
+        0.0
+    };
+    ratio_margin * support.center_edge_margin
+}
+
 fn simulate_static_stability_from_analysis(
     b: &PlacedBox,
     config: &PackingConfig,
@@ -1302,6 +3936,10 @@ struct PlacementScore {
     support_ratio: f64,
     support_centroid_offset_ratio: f64,
     support_contact_count: usize,
+    category_contacts: usize,
+    center_moment: f64,
+    wall_contacts: usize,
+    hint_distance: Option<f64>,
     y: f64,
     x: f64,
     balance_shift: f64,
@@ -1336,14 +3974,40 @@ fn update_best(
 /// Compares two placement scores.
 ///
 /// Priority: z (low) > local instability (low) > support ratio (high)
-/// > center-offset ratio (low) > support contacts (high) > y (low)
-/// > x (low) > balance shift (low) > balance (low)
+/// > center-offset ratio (low) > support contacts (high)
+/// > same-category neighbors (high) > center moment (low) > wall contacts (high)
+/// > y (low, high with [`LoadDirection::BackToFront`]) > x (low)
+/// > balance shift (low) > balance (low)
+///
+/// With [`PrimaryObjective::Balance`] the balance (low) is compared before everything else.
+/// Positions near the object's [`Box3D::position_hint`] (closest first) win before all of it.
 ///
 /// # Parameters
 /// * `new` - New score
 /// * `current` - Current score
 /// * `config` - Configuration parameters
 fn is_better_score(new: PlacementScore, current: PlacementScore, config: &PackingConfig) -> bool {
+    match (new.hint_distance, current.hint_distance) {
+        (Some(new_distance), Some(current_distance)) => {
+            match compare_with_epsilon(new_distance, current_distance, config.general_epsilon) {
+                Ordering::Less => return true,
+                Ordering::Greater => return false,
+                Ordering::Equal => {}
+            }
+        }
+        (Some(_), None) => return true,
+        (None, Some(_)) => return false,
+        (None, None) => {}
+    }
+
+    if config.primary_objective == PrimaryObjective::Balance {
+        match compare_with_epsilon(new.balance, current.balance, config.general_epsilon) {
+            Ordering::Less => return true,
+            Ordering::Greater => return false,
+            Ordering::Equal => {}
+        }
+    }
+
     match compare_with_epsilon(new.z, current.z, config.height_epsilon) {
         Ordering::Less => return true,
         Ordering::Greater => return false,
@@ -1385,7 +4049,35 @@ fn is_better_score(new: PlacementScore, current: PlacementScore, config: &Packin
         Ordering::Equal => {}
     }
 
-    match compare_with_epsilon(new.y, current.y, config.general_epsilon) {
+    match current.category_contacts.cmp(&new.category_contacts) {
+        Ordering::Less => return true,
+        Ordering::Greater => return false,
+        Ordering::Equal => {}
+    }
+
+    // Below one grid step the centering preference yields to the front-left preference.
+    match compare_with_epsilon(
+        new.center_moment,
+        current.center_moment,
+        config.grid_step.max(config.general_epsilon),
+    ) {
+        Ordering::Less => return true,
+        Ordering::Greater => return false,
+        Ordering::Equal => {}
+    }
+
+    match current.wall_contacts.cmp(&new.wall_contacts) {
+        Ordering::Less => return true,
+        Ordering::Greater => return false,
+        Ordering::Equal => {}
+    }
+
+    let y_order = compare_with_epsilon(new.y, current.y, config.general_epsilon);
+    let y_order = match config.load_direction {
+        LoadDirection::FrontToBack => y_order,
+        LoadDirection::BackToFront => y_order.reverse(),
+    };
+    match y_order {
         Ordering::Less => return true,
         Ordering::Greater => return false,
         Ordering::Equal => {}
@@ -1410,6 +4102,86 @@ fn is_better_score(new: PlacementScore, current: PlacementScore, config: &Packin
     new.balance + config.general_epsilon < current.balance
 }
 
+/// Counts the placed objects of the candidate's category that touch it side by side.
+///
+/// Always 0 unless [`PackingConfig::cluster_by_category`] is set and the candidate has a category.
+fn count_category_contacts(
+    cont: &Container,
+    candidate: &PlacedBox,
+    config: &PackingConfig,
+) -> usize {
+    let Some(category) = candidate.object.category.as_deref() else {
+        return 0;
+    };
+    if !config.cluster_by_category {
+        return 0;
+    }
+    cont.placed
+        .iter()
+        .filter(|p| p.object.category.as_deref() == Some(category))
+        .filter(|p| touches_sideways(p, candidate, config.general_epsilon))
+        .count()
+}
+
+/// Counts the side walls (x = 0, y = 0 and the far walls) the candidate touches.
+///
+/// Always 0 unless [`PackingConfig::prefer_wall_contact`] is set.
+fn count_wall_contacts(cont: &Container, candidate: &PlacedBox, config: &PackingConfig) -> usize {
+    if !config.prefer_wall_contact {
+        return 0;
+    }
+    let eps = config.general_epsilon;
+    let (x, y, _) = candidate.position;
+    let (w, d, _) = candidate.object.dims;
+    [
+        x <= eps,
+        y <= eps,
+        x + w >= cont.dims.0 - eps,
+        y + d >= cont.dims.1 - eps,
+    ]
+    .into_iter()
+    .filter(|&touches| touches)
+    .count()
+}
+
+/// Distance between the candidate position and the object's position hint.
+///
+/// `None` without a hint or outside the hint region, i.e. farther away than the object's longest
+/// edge; such positions fall back to the regular scoring.
+fn hint_distance(candidate: &PlacedBox) -> Option<f64> {
+    let hint = candidate.object.position_hint?;
+    let (x, y, z) = candidate.position;
+    let distance = ((x - hint.0).powi(2) + (y - hint.1).powi(2) + (z - hint.2).powi(2)).sqrt();
+    let (w, d, h) = candidate.object.dims;
+    (distance <= w.max(d).max(h)).then_some(distance)
+}
+
+/// XY distance between the candidate's center and the container center, scaled by the
+/// candidate's weight relative to the heaviest object in the container (itself included).
+///
+/// Always 0 unless [`PackingConfig::mass_placement`] is [`MassPlacement::Centered`]. Heavy
+/// objects are placed first and count with their full distance, so they win the central
+/// positions; for objects that are light compared to the load, the scaled differences stay below
+/// the tolerance of [`is_better_score`] and the usual front-left preference decides.
+fn center_moment(cont: &Container, candidate: &PlacedBox, config: &PackingConfig) -> f64 {
+    if config.mass_placement != MassPlacement::Centered {
+        return 0.0;
+    }
+    let heaviest = cont
+        .placed
+        .iter()
+        .map(|p| p.object.weight)
+        .fold(candidate.object.weight, f64::max);
+    if heaviest <= 0.0 {
+        return 0.0;
+    }
+    let center = (
+        candidate.position.0 + candidate.object.dims.0 / 2.0,
+        candidate.position.1 + candidate.object.dims.1 / 2.0,
+    );
+    candidate.object.weight / heaviest * distance_2d(center, container_center_xy(cont))
+}
+
 /// Compares two values with tolerance.
 ///
 /// # Parameters
@@ -1509,6 +4281,7 @@ pub fn compute_container_diagnostics(
             object_id: placed.object.id,
             support_percent: ratio * 100.0,
             rests_on_floor: placed.position.2 <= config.height_epsilon,
+            stability_margin: stability_margin(placed, cont, config),
         });
     }
 
@@ -1525,8 +4298,9 @@ pub fn compute_container_diagnostics(
     };
 
     let container_volume = cont.total_volume();
+    let used_volume = cont.used_volume();
     let volume_utilization_percent = if container_volume > config.general_epsilon {
-        (cont.used_volume() / container_volume * 100.0).clamp(0.0, 100.0)
+        (used_volume / container_volume * 100.0).clamp(0.0, 100.0)
     } else {
         0.0
     };
@@ -1536,16 +4310,54 @@ pub fn compute_container_diagnostics(
         0.0
     };
 
+    // Floor objects cannot overlap, so their base areas add up to the covered area.
+    let container_base_area = cont.dims.0 * cont.dims.1;
+    let floor_area: f64 = cont
+        .placed
+        .iter()
+        .filter(|p| p.position.2 <= config.height_epsilon)
+        .map(|p| p.object.base_area())
+        .sum();
+    let floor_coverage = if container_base_area > config.general_epsilon {
+        (floor_area / container_base_area).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let floor_coverage_ok = config
+        .floor_min_coverage
+        .is_none_or(|min| floor_coverage + config.general_epsilon >= min);
+    let headroom_remaining = cont
+        .placed
+        .iter()
+        .map(|p| {
+            let (x, _, z) = p.position;
+            cont.ceiling_between(x, x + p.object.dims.0) - (z + p.object.dims.2)
+        })
+        .fold(cont.dims.2, f64::min)
+        .max(0.0);
+    let grid_step = effective_grid_step(
+        cont.dims.0.max(cont.dims.1),
+        config.grid_step,
+        config.max_axis_positions,
+    );
+
     ContainerDiagnostics {
         center_of_mass_offset: center_offset,
         balance_limit,
         imbalance_ratio,
         average_support_percent,
         minimum_support_percent,
+        used_volume,
+        total_volume: container_volume,
         volume_utilization_percent,
+        floor_coverage_percent: floor_coverage * 100.0,
+        floor_coverage_ok,
+        weight_order_violations: count_weight_order_violations(cont, config),
         weight_utilization_percent,
+        headroom_remaining,
         packaging: cont.packaging_fill(),
         support_samples,
+        coarsened_grid_step: (grid_step > config.grid_step).then_some(grid_step),
     }
 }
 
@@ -1641,6 +4453,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::ObjectGap;
 
     fn single_blueprint(dims: (f64, f64, f64), max_weight: f64) -> Vec<ContainerBlueprint> {
         vec![ContainerBlueprint::new(0, None, dims, max_weight).unwrap()]
@@ -1678,7 +4491,7 @@ mod tests {
                 }
 
                 assert!(
-                    lower.object.weight + config.general_epsilon >= upper.object.weight,
+                    weight_may_rest_on(upper.object.weight, lower.object.weight, config),
                     "Object {} ({}kg) under object {} ({}kg) violates weight sorting",
                     lower.object.id,
                     lower.object.weight,
@@ -1696,11 +4509,13 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 10.0),
                 weight: 10.0,
+                ..Default::default()
             },
             Box3D {
                 id: 2,
                 dims: (10.0, 10.0, 10.0),
                 weight: 4.0,
+                ..Default::default()
             },
         ];
 
@@ -1734,6 +4549,7 @@ mod tests {
             id: 1,
             dims: (10.0, 10.0, 10.0),
             weight: 10.0,
+            ..Default::default()
         }];
 
         let result = pack_objects(objects, single_blueprint((20.0, 20.0, 20.0), 100.0));
@@ -1752,16 +4568,19 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 10.0),
                 weight: 300.0,
+                ..Default::default()
             },
             Box3D {
                 id: 2,
                 dims: (10.0, 10.0, 10.0),
                 weight: 300.0,
+                ..Default::default()
             },
             Box3D {
                 id: 3,
                 dims: (10.0, 10.0, 10.0),
                 weight: 300.0,
+                ..Default::default()
             },
         ];
 
@@ -1779,6 +4598,7 @@ mod tests {
             id: 1,
             dims: (12.0, 9.0, 8.0),
             weight: 5.0,
+            ..Default::default()
         }];
 
         let result = pack_objects(objects, single_blueprint((10.0, 10.0, 10.0), 100.0));
@@ -1797,6 +4617,7 @@ mod tests {
             id: 1,
             dims: (5.0, 5.0, 5.0),
             weight: 25.0,
+            ..Default::default()
         }];
 
         let result = pack_objects(objects, single_blueprint((10.0, 10.0, 10.0), 10.0));
@@ -1822,16 +4643,19 @@ mod tests {
                 id: 1,
                 dims: (30.0, 30.0, 20.0),
                 weight: 90.0,
+                ..Default::default()
             },
             Box3D {
                 id: 2,
                 dims: (10.0, 10.0, 10.0),
                 weight: 15.0,
+                ..Default::default()
             },
             Box3D {
                 id: 3,
                 dims: (8.0, 8.0, 8.0),
                 weight: 10.0,
+                ..Default::default()
             },
         ];
 
@@ -1854,6 +4678,7 @@ mod tests {
             id: 1,
             dims: (15.0, 12.0, 12.0),
             weight: 20.0,
+            ..Default::default()
         }];
 
         let result = pack_objects(objects, templates);
@@ -1875,6 +4700,7 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 10.0),
                 weight: 5.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.0),
         });
@@ -1883,17 +4709,44 @@ mod tests {
             id: 2,
             dims: (10.0, 10.0, 10.0),
             weight: 9.0,
+            ..Default::default()
         };
 
         assert!(find_stable_position(&heavy_box, &container, &config).is_none());
     }
 
+    #[test]
+    fn object_failing_weight_and_dimensions_on_different_templates_reports_both() {
+        // Template 0 is large but weak, template 1 strong but small.
+        let templates = vec![
+            ContainerBlueprint::new(0, None, (100.0, 100.0, 100.0), 10.0).unwrap(),
+            ContainerBlueprint::new(1, None, (20.0, 20.0, 20.0), 1000.0).unwrap(),
+        ];
+        let object = Box3D::new(1, (50.0, 50.0, 50.0), 100.0).unwrap();
+
+        let result = pack_objects_with_config(vec![object], templates, PackingConfig::default());
+
+        assert_eq!(result.unplaced.len(), 1);
+        let reason = &result.unplaced[0].reason;
+        assert_eq!(
+            *reason,
+            UnplacedReason::CombinedConstraint {
+                weight_capable: vec![1],
+                dimension_capable: vec![0],
+            }
+        );
+        assert_eq!(reason.code(), "combined_constraint");
+        assert!(reason.to_string().contains("[0]"));
+        assert!(reason.to_string().contains("[1]"));
+    }
+
     #[test]
     fn rotation_toggle_controls_reorientation() {
         let object = Box3D {
             id: 1,
             dims: (80.0, 40.0, 60.0),
             weight: 10.0,
+            ..Default::default()
         };
         let templates = single_blueprint((60.0, 80.0, 40.0), 100.0);
 
@@ -1906,7 +4759,7 @@ mod tests {
         assert_eq!(result_without_rotation.unplaced.len(), 1);
         assert!(matches!(
             result_without_rotation.unplaced[0].reason,
-            UnplacedReason::DimensionsExceedContainer
+            UnplacedReason::WouldFitIfRotated
         ));
 
         config.allow_item_rotation = true;
@@ -1924,6 +4777,7 @@ mod tests {
             id: 1,
             dims: (50.0, 50.0, 50.0),
             weight: 10.0,
+            ..Default::default()
         };
         let cube_orientations = orientations_for(&cube, true);
         assert_eq!(
@@ -1940,6 +4794,7 @@ mod tests {
             id: 2,
             dims: (30.0, 30.0, 60.0),
             weight: 10.0,
+            ..Default::default()
         };
         let rect_orientations = orientations_for(&rect_prism, true);
         assert_eq!(
@@ -1966,6 +4821,7 @@ mod tests {
             id: 3,
             dims: (20.0, 30.0, 40.0),
             weight: 10.0,
+            ..Default::default()
         };
         let distinct_orientations = orientations_for(&distinct, true);
         assert_eq!(
@@ -2006,26 +4862,31 @@ mod tests {
                 id: 1,
                 dims: (30.0, 30.0, 20.0),
                 weight: 50.0,
+                ..Default::default()
             },
             Box3D {
                 id: 2,
                 dims: (20.0, 40.0, 25.0),
                 weight: 30.0,
+                ..Default::default()
             },
             Box3D {
                 id: 3,
                 dims: (10.0, 20.0, 10.0),
                 weight: 10.0,
+                ..Default::default()
             },
             Box3D {
                 id: 4,
                 dims: (50.0, 40.0, 30.0),
                 weight: 70.0,
+                ..Default::default()
             },
             Box3D {
                 id: 5,
                 dims: (60.0, 50.0, 40.0),
                 weight: 90.0,
+                ..Default::default()
             },
         ];
 
@@ -2053,6 +4914,30 @@ mod tests {
         assert!(second.position.2 <= config.height_epsilon);
     }
 
+    #[test]
+    fn disabling_footprint_clustering_keeps_the_weight_volume_order() {
+        let cube =
+            |id: usize, side: f64, weight: f64| Box3D::new(id, (side, side, 10.0), weight).unwrap();
+        // Sorted by weight, the large box 2 comes between the two similar small ones.
+        let objects = vec![cube(3, 10.0, 1.0), cube(2, 30.0, 5.0), cube(1, 10.0, 10.0)];
+        let packed_order = |cluster: bool| {
+            let result = pack_objects_with_config(
+                objects.clone(),
+                single_blueprint((100.0, 100.0, 100.0), 1000.0),
+                PackingConfig::builder().cluster_footprints(cluster).build(),
+            );
+            assert_eq!(result.containers.len(), 1);
+            result.containers[0]
+                .placed
+                .iter()
+                .map(|p| p.object.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(packed_order(true), vec![1, 3, 2]);
+        assert_eq!(packed_order(false), vec![1, 2, 3]);
+    }
+
     #[test]
     fn footprint_cluster_groups_similar_dimensions() {
         let strategy =
@@ -2062,16 +4947,19 @@ mod tests {
                 id: 1,
                 dims: (20.0, 10.0, 10.0),
                 weight: 30.0,
+                ..Default::default()
             },
             Box3D {
                 id: 2,
                 dims: (20.4, 10.1, 9.5),
                 weight: 28.0,
+                ..Default::default()
             },
             Box3D {
                 id: 3,
                 dims: (5.0, 5.0, 5.0),
                 weight: 12.0,
+                ..Default::default()
             },
         ];
 
@@ -2094,6 +4982,7 @@ mod tests {
                 id: 1,
                 dims: (5.0, 10.0, 10.0),
                 weight: 8.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.0),
         });
@@ -2103,6 +4992,7 @@ mod tests {
                 id: 2,
                 dims: (10.0, 10.0, 8.0),
                 weight: 5.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 10.0),
         });
@@ -2130,12 +5020,13 @@ mod tests {
         let eps = PackingConfig::DEFAULT_GENERAL_EPSILON;
 
         // A coarse grid for a width-3 object in a width-10 container yields {0, 5, 7}.
-        let grid = axis_positions(10.0, 3.0, 5.0, eps);
+        let max = PackingConfig::DEFAULT_MAX_AXIS_POSITIONS;
+        let grid = axis_positions(10.0, 3.0, 5.0, eps, max);
         assert_eq!(grid, vec![0.0, 5.0, 7.0]);
 
         // The far edge (x = 3) of an object placed at x = 0 becomes a flush candidate that the
         // grid alone never offers.
-        let with_edges = candidate_positions(10.0, 3.0, 5.0, eps, &[0.0, 3.0]);
+        let with_edges = candidate_positions(10.0, 3.0, 5.0, eps, max, &[0.0, 3.0]);
         assert!(
             with_edges.iter().any(|&p| (p - 3.0).abs() < eps),
             "edge 3.0 must be added as a candidate: {:?}",
@@ -2143,7 +5034,7 @@ mod tests {
         );
 
         // Edges outside the placeable range are dropped instead of producing invalid positions.
-        let clamped = candidate_positions(10.0, 3.0, 5.0, eps, &[20.0]);
+        let clamped = candidate_positions(10.0, 3.0, 5.0, eps, max, &[20.0]);
         assert!(clamped.iter().all(|&p| p <= 7.0 + eps));
     }
 
@@ -2178,6 +5069,7 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 5.0),
                 weight: 40.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.0),
         });
@@ -2212,6 +5104,7 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 4.0),
                 weight: 40.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.0),
         });
@@ -2239,11 +5132,13 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 5.0),
                 weight: 300.0,
+                ..Default::default()
             },
             Box3D {
                 id: 2,
                 dims: (10.0, 10.0, 5.0),
                 weight: 300.0,
+                ..Default::default()
             },
         ];
 
@@ -2267,11 +5162,13 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 10.0),
                 weight: 8.0,
+                ..Default::default()
             },
             Box3D {
                 id: 2,
                 dims: (5.0, 5.0, 5.0),
                 weight: 3.0,
+                ..Default::default()
             },
         ];
 
@@ -2296,11 +5193,13 @@ mod tests {
             id: 1,
             dims: (5.0, 5.0, 4.0),
             weight: 10.0,
+            ..Default::default()
         };
         let lower_floor_load = Box3D {
             id: 2,
             dims: (10.0, 10.0, 1.0),
             weight: 10.0,
+            ..Default::default()
         };
 
         let config = PackingConfig::default();
@@ -2315,6 +5214,7 @@ mod tests {
             id: 1,
             dims: (0.2, 0.2, 0.2),
             weight: 10.0,
+            ..Default::default()
         };
 
         let score = object_ordering_score(&object, &config);
@@ -2333,6 +5233,7 @@ mod tests {
                 id: 1,
                 dims: (10.0, 10.0, 10.0),
                 weight: 20.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.0),
         });
@@ -2342,6 +5243,7 @@ mod tests {
                 id: 2,
                 dims: (10.0, 10.0, 8.0),
                 weight: 8.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 10.0),
         };
@@ -2372,6 +5274,7 @@ mod tests {
                 id: 1,
                 dims: (0.2, 0.2, 0.2),
                 weight: 5.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.0),
         });
@@ -2381,6 +5284,7 @@ mod tests {
                 id: 2,
                 dims: (0.2, 0.2, 0.2),
                 weight: 4.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.2),
         };
@@ -2399,6 +5303,7 @@ mod tests {
                 id: 1,
                 dims: (0.2, 0.2, 0.2),
                 weight: 5.0,
+                ..Default::default()
             },
             position: (0.0, 0.0, 0.0),
         });
@@ -2408,6 +5313,7 @@ mod tests {
                 id: 2,
                 dims: (0.2, 0.2, 0.2),
                 weight: 4.0,
+                ..Default::default()
             },
             position: (0.05, 0.0, 0.2),
         };
@@ -2427,11 +5333,64 @@ mod tests {
             height_epsilon: -1.0,
             general_epsilon: f64::NAN,
             balance_limit_ratio: 2.0,
+            balance_limit_ratio_x: Some(-0.1),
+            balance_limit_ratio_y: Some(0.2),
             footprint_cluster_tolerance: -0.5,
             allow_item_rotation: true,
+            target_container_count: Some(0),
+            max_axis_positions: 0,
+            strict_layering: true,
+            primary_objective: PrimaryObjective::Balance,
+            max_overhang: Some(f64::NAN),
+            restarts: usize::MAX,
+            template_merge_tolerance: -1.0,
+            relative_tolerance: true,
+            floor_min_coverage: Some(1.5),
+            top_clearance: -1.0,
+            container_order: ContainerOrder::Weight,
+            template_preference: TemplatePreference::LargestFirst,
+            new_container_policy: NewContainerPolicy::BestFitForRemaining,
+            max_object_volume_fraction: Some(-0.5),
+            allow_equal_weight_stacking: false,
+            strict_weight: true,
+            target_utilization: Some(1.2),
+            support_samples_per_axis: usize::MAX,
+            profile: true,
+            compact: true,
+            settle: true,
+            cluster_by_category: true,
+            cluster_footprints: false,
+            prefer_wall_contact: true,
+            prefer_floor_fallback: true,
+            prefer_column_stacking: true,
+            track_rejections: true,
+            diagnostics_interval: 0,
+            mass_placement: MassPlacement::Centered,
+            load_direction: LoadDirection::BackToFront,
+            snapshot_every: Some(0),
+            progress_every: Some(0),
+            progress_interval_ms: Some(0),
         };
 
         let sanitized = config.sanitized();
+        assert_eq!(sanitized.snapshot_every, None);
+        assert_eq!(sanitized.progress_every, None);
+        assert_eq!(sanitized.progress_interval_ms, None);
+        assert_eq!(sanitized.balance_limit_ratio_x, None);
+        assert_eq!(sanitized.balance_limit_ratio_y, Some(0.2));
+        assert_eq!(sanitized.container_order, ContainerOrder::Weight);
+        assert_eq!(
+            sanitized.template_preference,
+            TemplatePreference::LargestFirst
+        );
+        assert_eq!(sanitized.max_object_volume_fraction, None);
+        assert!(!sanitized.allow_equal_weight_stacking);
+        assert_eq!(sanitized.target_utilization, None);
+        assert_eq!(
+            sanitized.support_samples_per_axis,
+            PackingConfig::MAX_SUPPORT_SAMPLES_PER_AXIS
+        );
+        assert_eq!(sanitized.diagnostics_interval, 1);
 
         assert_eq!(sanitized.grid_step, PackingConfig::DEFAULT_GRID_STEP);
         // Ratio-like fields fall back to safe defaults when callers provide out-of-range values.
@@ -2457,6 +5416,18 @@ mod tests {
             PackingConfig::DEFAULT_FOOTPRINT_CLUSTER_TOLERANCE
         );
         assert!(sanitized.allow_item_rotation);
+        assert_eq!(sanitized.target_container_count, None);
+        assert_eq!(sanitized.max_overhang, None);
+        assert_eq!(sanitized.restarts, PackingConfig::MAX_RESTARTS);
+        assert_eq!(sanitized.floor_min_coverage, None);
+        assert_eq!(
+            sanitized.top_clearance,
+            PackingConfig::DEFAULT_TOP_CLEARANCE
+        );
+        assert_eq!(
+            sanitized.template_merge_tolerance,
+            PackingConfig::DEFAULT_TEMPLATE_MERGE_TOLERANCE
+        );
     }
 
     #[test]
@@ -2482,4 +5453,2132 @@ mod tests {
         assert_eq!(result.containers[0].placed[0].object.id, 1);
         assert_eq!(result.containers[1].placed[0].object.id, 2);
     }
+
+    #[test]
+    fn target_container_count_spreads_objects_across_all_containers() {
+        let config = PackingConfig::builder()
+            .target_container_count(Some(3))
+            .build();
+        let objects = (1..=6)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 5.0).unwrap())
+            .collect();
+
+        let result =
+            pack_objects_with_config(objects, single_blueprint((50.0, 50.0, 50.0), 100.0), config);
+
+        // A single container could hold every object, but the contract requires three.
+        assert!(result.unplaced.is_empty());
+        assert_eq!(result.containers.len(), 3);
+        for cont in &result.containers {
+            assert_eq!(cont.placed.len(), 2);
+        }
+        assert_eq!(result.container_diagnostics.len(), 3);
+    }
+
+    #[test]
+    fn centered_mass_placement_pulls_the_heaviest_box_toward_the_center() {
+        let objects = vec![
+            Box3D::new(1, (20.0, 20.0, 20.0), 50.0).unwrap(),
+            Box3D::new(2, (20.0, 20.0, 20.0), 2.0).unwrap(),
+            Box3D::new(3, (20.0, 20.0, 20.0), 2.0).unwrap(),
+        ];
+        let heaviest_offset = |placement| {
+            let result = pack_objects_with_config(
+                objects.clone(),
+                single_blueprint((100.0, 100.0, 50.0), 1000.0),
+                PackingConfig::builder().mass_placement(placement).build(),
+            );
+            assert!(result.unplaced.is_empty());
+            let heavy = result.containers[0]
+                .placed
+                .iter()
+                .find(|p| p.object.id == 1)
+                .unwrap();
+            distance_2d(
+                (heavy.position.0 + 10.0, heavy.position.1 + 10.0),
+                (50.0, 50.0),
+            )
+        };
+
+        let balanced = heaviest_offset(MassPlacement::Balanced);
+        let centered = heaviest_offset(MassPlacement::Centered);
+        assert!(
+            centered < balanced,
+            "centered offset {centered} should be below balanced offset {balanced}"
+        );
+        assert!(centered < 1e-6);
+    }
+
+    #[test]
+    fn centered_mass_placement_leaves_light_objects_at_the_front() {
+        let objects = vec![
+            Box3D::new(1, (20.0, 20.0, 20.0), 50.0).unwrap(),
+            Box3D::new(2, (10.0, 10.0, 10.0), 1.0).unwrap(),
+        ];
+        let result = pack_objects_with_config(
+            objects,
+            single_blueprint((100.0, 100.0, 50.0), 1000.0),
+            PackingConfig::builder()
+                .mass_placement(MassPlacement::Centered)
+                .balance_limit_ratio(1.0)
+                .build(),
+        );
+
+        let position = |id: usize| {
+            result.containers[0]
+                .placed
+                .iter()
+                .find(|p| p.object.id == id)
+                .unwrap()
+                .position
+        };
+        assert_eq!(position(1), (40.0, 40.0, 0.0));
+        // 1/50 of the load: its distance to the center is not worth leaving the front corner.
+        assert_eq!(position(2), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn oversized_objects_skip_the_placement_search_with_the_usual_reason() {
+        let objects = vec![
+            Box3D::new(1, (500.0, 500.0, 500.0), 5.0).unwrap(),
+            Box3D::new(2, (10.0, 10.0, 10.0), 5.0).unwrap(),
+        ];
+        let config = PackingConfig::builder()
+            .allow_item_rotation(true)
+            .track_rejections(true)
+            .build();
+
+        let result =
+            pack_objects_with_config(objects, single_blueprint((50.0, 50.0, 50.0), 100.0), config);
+
+        assert_eq!(result.unplaced.len(), 1);
+        assert_eq!(result.unplaced[0].object.id, 1);
+        assert_eq!(
+            result.unplaced[0].reason,
+            UnplacedReason::DimensionsExceedContainer
+        );
+        assert_eq!(result.containers[0].placed.len(), 1);
+        // No candidate position was evaluated for the oversized object.
+        assert_eq!(
+            result.unplaced[0].rejections,
+            Some(RejectionCounts::default())
+        );
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Parcel {
+        sku: &'static str,
+        size: f64,
+        kg: f64,
+    }
+
+    impl Dimensional for Parcel {
+        fn dimensions(&self) -> crate::types::Vec3 {
+            crate::types::Vec3::new(self.size, self.size, self.size)
+        }
+    }
+
+    impl Weighted for Parcel {
+        fn weight(&self) -> f64 {
+            self.kg
+        }
+    }
+
+    #[test]
+    fn pack_items_packs_custom_types_and_keys_results_by_index() {
+        let parcels = vec![
+            Parcel {
+                sku: "small",
+                size: 10.0,
+                kg: 2.0,
+            },
+            Parcel {
+                sku: "huge",
+                size: 80.0,
+                kg: 2.0,
+            },
+            Parcel {
+                sku: "medium",
+                size: 20.0,
+                kg: 5.0,
+            },
+        ];
+
+        let packed = pack_items(
+            &parcels,
+            single_blueprint((50.0, 50.0, 50.0), 100.0),
+            PackingConfig::default(),
+        )
+        .unwrap();
+
+        let mut placed: Vec<(usize, &str)> = packed
+            .placed
+            .iter()
+            .map(|p| (p.index, p.item.sku))
+            .collect();
+        placed.sort();
+        assert_eq!(placed, vec![(0, "small"), (2, "medium")]);
+        assert!(packed.placed.iter().all(|p| p.container == 0));
+        assert_eq!(packed.unplaced.len(), 1);
+        assert_eq!(packed.unplaced[0].index, 1);
+        assert_eq!(packed.unplaced[0].item, parcels[1]);
+
+        let invalid = [Parcel {
+            sku: "flat",
+            size: 0.0,
+            kg: 1.0,
+        }];
+        assert!(
+            pack_items(
+                &invalid,
+                single_blueprint((50.0, 50.0, 50.0), 100.0),
+                Default::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn optional_objects_never_open_a_new_container() {
+        let blueprint = single_blueprint((10.0, 10.0, 10.0), 1000.0);
+        let mut add_on = Box3D::new(1, (10.0, 10.0, 4.0), 1.0).unwrap();
+        add_on.optional = true;
+        let small_add_on = Box3D {
+            id: 2,
+            dims: (10.0, 10.0, 2.0),
+            ..add_on.clone()
+        };
+        // The optional items come first in the input; they are still packed after the crate.
+        let objects = vec![
+            add_on,
+            small_add_on,
+            Box3D::new(3, (10.0, 10.0, 8.0), 1.0).unwrap(),
+        ];
+
+        let result = pack_objects_with_config(objects, blueprint, PackingConfig::default());
+
+        assert_eq!(result.containers.len(), 1);
+        let placed: Vec<usize> = result.containers[0]
+            .placed
+            .iter()
+            .map(|p| p.object.id)
+            .collect();
+        assert_eq!(placed, vec![3, 2]);
+        assert_eq!(result.unplaced.len(), 1);
+        assert_eq!(result.unplaced[0].object.id, 1);
+        assert_eq!(result.unplaced[0].reason, UnplacedReason::OptionalSkipped);
+        assert_eq!(result.unplaced[0].reason.code(), "optional_skipped");
+    }
+
+    #[test]
+    fn optional_objects_too_large_for_every_template_report_the_dimensions() {
+        let blueprint = single_blueprint((10.0, 10.0, 10.0), 1000.0);
+        let mut add_on = Box3D::new(1, (20.0, 10.0, 4.0), 1.0).unwrap();
+        add_on.optional = true;
+        let objects = vec![add_on, Box3D::new(2, (10.0, 10.0, 5.0), 1.0).unwrap()];
+
+        let result = pack_objects_with_config(objects, blueprint, PackingConfig::default());
+
+        assert_eq!(result.unplaced.len(), 1);
+        assert_eq!(
+            result.unplaced[0].reason,
+            UnplacedReason::DimensionsExceedContainer
+        );
+    }
+
+    #[test]
+    fn floor_only_region_prevents_stacking_inside_but_not_elsewhere() {
+        let blueprint = ContainerBlueprint::new(0, None, (20.0, 20.0, 20.0), 1000.0)
+            .unwrap()
+            .with_floor_only_region((0.0, 10.0, 20.0, 10.0))
+            .unwrap();
+        // Two slabs cover the floor; only the one outside the region carries a second layer.
+        let slabs = |count: usize| {
+            (1..=count)
+                .map(|id| Box3D::new(id, (20.0, 10.0, 10.0), 1.0).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let config = PackingConfig::builder()
+            .allow_item_rotation(false)
+            .balance_limit_ratio(1.0)
+            .build();
+
+        let result = pack_objects_with_config(slabs(3), vec![blueprint], config);
+        assert_eq!(result.containers.len(), 1);
+        let stacked: Vec<_> = result.containers[0]
+            .placed
+            .iter()
+            .filter(|p| p.position.2 > 0.0)
+            .collect();
+        assert_eq!(stacked.len(), 1);
+        assert_eq!(stacked[0].position.1, 0.0);
+
+        // Without the region, the top slab bridges both floor slabs and reaches into it.
+        let plain = pack_objects_with_config(
+            slabs(3),
+            single_blueprint((20.0, 20.0, 20.0), 1000.0),
+            config,
+        );
+        assert_eq!(plain.containers.len(), 1);
+        assert!(
+            plain.containers[0]
+                .placed
+                .iter()
+                .any(|p| p.position.2 > 0.0 && p.position.1 > 0.0)
+        );
+    }
+
+    #[test]
+    fn object_at_the_weight_limit_fits_and_strict_weight_rejects_any_excess() {
+        let blueprint = single_blueprint((50.0, 50.0, 50.0), 100.0);
+        let pack = |weight: f64, strict: bool| {
+            pack_objects_with_config(
+                vec![Box3D::new(1, (10.0, 10.0, 10.0), weight).unwrap()],
+                blueprint.clone(),
+                PackingConfig::builder().strict_weight(strict).build(),
+            )
+        };
+
+        // Exactly the limit is accepted in both modes.
+        assert!(pack(100.0, false).unplaced.is_empty());
+        assert!(pack(100.0, true).unplaced.is_empty());
+
+        // A hair above the limit is within the default epsilon slack, but not in strict mode.
+        let over = 100.0 + 1e-8;
+        assert!(pack(over, false).unplaced.is_empty());
+        let strict = pack(over, true);
+        assert!(strict.containers.is_empty());
+        assert_eq!(
+            strict.unplaced[0].reason,
+            UnplacedReason::TooHeavyForContainer
+        );
+    }
+
+    #[test]
+    fn fill_tiers_keep_weight_order_and_survive_restarts() {
+        let mut small = ContainerBlueprint::new(0, None, (20.0, 20.0, 20.0), 100.0).unwrap();
+        small.fill_rank = Some(0);
+        let mut large = ContainerBlueprint::new(1, None, (40.0, 40.0, 40.0), 100.0).unwrap();
+        large.fill_rank = Some(1);
+        // Ids 1-6 fit the small container, ids 7 and 8 only the later-ranked large one.
+        let mut objects: Vec<Box3D> = (1..=6)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 5.0), id as f64).unwrap())
+            .collect();
+        objects.push(Box3D::new(7, (30.0, 30.0, 30.0), 50.0).unwrap());
+        objects.push(Box3D::new(8, (30.0, 30.0, 10.0), 40.0).unwrap());
+
+        for restarts in [0, 8] {
+            let mut placed = Vec::new();
+            pack_objects_with_progress(
+                objects.clone(),
+                vec![small.clone(), large.clone()],
+                PackingConfig::builder().restarts(restarts).build(),
+                |event| {
+                    if let PackEvent::ObjectPlaced { id, .. } = event {
+                        placed.push(*id);
+                    }
+                },
+            );
+            let tier_end = placed.iter().position(|&id| id > 6).unwrap();
+            assert!(placed[tier_end..].iter().all(|&id| id > 6), "{placed:?}");
+            if restarts == 0 {
+                assert_eq!(placed, vec![6, 5, 4, 3, 2, 1, 7, 8]);
+            }
+        }
+    }
+
+    #[test]
+    fn too_heavy_objects_are_rejected_in_packing_order_without_a_search() {
+        let templates = vec![
+            ContainerBlueprint::new(0, None, (50.0, 50.0, 50.0), 50.0).unwrap(),
+            ContainerBlueprint::new(1, None, (50.0, 50.0, 50.0), 500.0).unwrap(),
+        ];
+        let restricted = |id, weight, template_id| {
+            let mut object = Box3D::new(id, (10.0, 10.0, 10.0), weight).unwrap();
+            object.allowed_template_ids = Some(vec![template_id]);
+            object
+        };
+        // Object 1 is packed first as the heavier one; object 2 only may use the light container.
+        let objects = vec![restricted(2, 80.0, 0), restricted(1, 200.0, 1)];
+        let mut events = Vec::new();
+        let result = pack_objects_with_progress(
+            objects,
+            templates,
+            PackingConfig::builder().track_rejections(true).build(),
+            |event| match event {
+                PackEvent::ObjectPlaced { id, .. } => events.push(format!("placed {id}")),
+                PackEvent::ObjectRejected {
+                    id, reason_code, ..
+                } => events.push(format!("rejected {id}: {reason_code}")),
+                _ => {}
+            },
+        );
+
+        assert_eq!(result.unplaced.len(), 1);
+        assert_eq!(result.unplaced[0].object.id, 2);
+        assert_eq!(
+            result.unplaced[0].reason,
+            UnplacedReason::TooHeavyForContainer
+        );
+        assert_eq!(
+            result.unplaced[0].rejections,
+            Some(RejectionCounts::default())
+        );
+        let too_heavy = UnplacedReason::TooHeavyForContainer.code();
+        assert_eq!(
+            events,
+            vec!["placed 1".to_string(), format!("rejected 2: {too_heavy}")]
+        );
+    }
+
+    #[test]
+    fn progress_events_report_throughput_every_n_objects() {
+        let objects: Vec<Box3D> = (1..=6)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 1.0).unwrap())
+            .collect();
+        let mut progress = Vec::new();
+        pack_objects_with_progress(
+            objects,
+            single_blueprint((100.0, 100.0, 100.0), 100.0),
+            PackingConfig::builder().progress_every(Some(2)).build(),
+            |event| {
+                if let PackEvent::Progress {
+                    processed,
+                    total,
+                    elapsed_ms,
+                    rate_per_sec,
+                } = event
+                {
+                    progress.push((*processed, *total, *elapsed_ms, *rate_per_sec));
+                }
+            },
+        );
+
+        let processed: Vec<usize> = progress.iter().map(|p| p.0).collect();
+        assert_eq!(processed, vec![2, 4, 6]);
+        for (_, total, elapsed_ms, rate_per_sec) in progress {
+            assert_eq!(total, 6);
+            assert!(elapsed_ms >= 0.0);
+            assert!(rate_per_sec > 0.0);
+        }
+    }
+
+    #[test]
+    fn snapshots_are_emitted_at_the_configured_interval() {
+        let objects: Vec<Box3D> = (1..=5)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 1.0).unwrap())
+            .collect();
+        let mut snapshots = Vec::new();
+        let mut placed_so_far = 0;
+        pack_objects_with_progress(
+            objects,
+            single_blueprint((100.0, 100.0, 100.0), 100.0),
+            PackingConfig::builder().snapshot_every(Some(2)).build(),
+            |event| match event {
+                PackEvent::ObjectPlaced { .. } => placed_so_far += 1,
+                PackEvent::Snapshot {
+                    container_id,
+                    placed,
+                } => snapshots.push((placed_so_far, *container_id, placed.clone())),
+                _ => {}
+            },
+        );
+
+        assert_eq!(snapshots.len(), 2);
+        for (placed_so_far, container_id, placed) in &snapshots {
+            assert_eq!(*container_id, 1);
+            assert_eq!(placed.len(), *placed_so_far);
+        }
+        assert_eq!(snapshots[0].0, 2);
+        assert_eq!(snapshots[1].0, 4);
+        let ids: Vec<usize> = snapshots[1].2.iter().map(|o| o.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn snapshots_only_cover_containers_changed_since_the_previous_one() {
+        let objects: Vec<Box3D> = (1..=3)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 1.0).unwrap())
+            .collect();
+        let mut snapshots = Vec::new();
+        pack_objects_with_progress(
+            objects,
+            single_blueprint((10.0, 10.0, 10.0), 100.0),
+            PackingConfig::builder().snapshot_every(Some(1)).build(),
+            |event| {
+                if let PackEvent::Snapshot {
+                    container_id,
+                    placed,
+                } = event
+                {
+                    snapshots.push((*container_id, placed.len()));
+                }
+            },
+        );
+
+        // Every object opens a container of its own; the full ones are not repeated.
+        assert_eq!(snapshots, vec![(1, 1), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn centered_full_support_has_a_higher_stability_margin_than_a_barely_supported_box() {
+        let mut cont = Container::new((40.0, 10.0, 30.0), 100.0).unwrap();
+        for (id, dims, position) in [
+            (1, (10.0, 10.0, 10.0), (0.0, 0.0, 0.0)),
+            (2, (10.0, 10.0, 5.0), (0.0, 0.0, 10.0)),
+            (3, (10.0, 10.0, 10.0), (20.0, 0.0, 0.0)),
+            // Only 6 of 10 units rest on box 3, right at the default 60 % support ratio.
+            (4, (10.0, 10.0, 5.0), (16.0, 0.0, 10.0)),
+        ] {
+            cont.placed
+                .push(PlacedBox::new(Box3D::new(id, dims, 1.0).unwrap(), position));
+        }
+
+        let diagnostics = compute_container_diagnostics(&cont, &PackingConfig::default());
+        let margin = |id| {
+            diagnostics
+                .support_samples
+                .iter()
+                .find(|sample| sample.object_id == id)
+                .map(|sample| sample.stability_margin)
+                .unwrap()
+        };
+
+        assert_eq!(margin(1), 1.0);
+        assert!((margin(2) - 1.0).abs() < 1e-9);
+        assert!(margin(4) < 0.1, "barely supported margin {}", margin(4));
+        assert!(margin(2) > margin(4));
+    }
+
+    #[test]
+    fn back_to_front_loading_places_the_first_object_at_the_rear() {
+        let blueprint = single_blueprint((50.0, 100.0, 50.0), 1000.0);
+        let objects = vec![Box3D::new(1, (20.0, 20.0, 20.0), 5.0).unwrap()];
+        let base = PackingConfig::builder().balance_limit_ratio(1.0);
+
+        let front =
+            pack_objects_with_config(objects.clone(), blueprint.clone(), base.clone().build());
+        assert_eq!(front.containers[0].placed[0].position.1, 0.0);
+
+        let back = pack_objects_with_config(
+            objects,
+            blueprint,
+            base.load_direction(LoadDirection::BackToFront).build(),
+        );
+        assert_eq!(back.containers[0].placed[0].position.1, 80.0);
+    }
+
+    #[test]
+    fn column_stacking_piles_identical_boxes_into_columns() {
+        let boxes: Vec<Box3D> = (1..=6)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 5.0).unwrap())
+            .collect();
+        let columns = |config: PackingConfig| {
+            let result = pack_objects_with_config(
+                boxes.clone(),
+                single_blueprint((30.0, 30.0, 30.0), 1000.0),
+                config,
+            );
+            assert_eq!(result.containers.len(), 1);
+            let cont = &result.containers[0];
+            assert_eq!(cont.placed.len(), 6);
+            let mut footprints: Vec<(f64, f64)> = cont
+                .placed
+                .iter()
+                .map(|p| (p.position.0, p.position.1))
+                .collect();
+            footprints.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+            footprints.dedup();
+            footprints.len()
+        };
+
+        // Without the flag, the lowest positions win and the boxes spread over the floor.
+        assert_eq!(columns(PackingConfig::default()), 6);
+        // With it, every box lands on the previous one until a column reaches the ceiling.
+        let stacked = PackingConfig::builder()
+            .prefer_column_stacking(true)
+            .build();
+        assert_eq!(columns(stacked), 2);
+    }
+
+    #[test]
+    fn item_limited_template_opens_new_containers_despite_free_space() {
+        let blueprint = ContainerBlueprint::new(0, None, (100.0, 100.0, 100.0), 1000.0)
+            .unwrap()
+            .with_max_items(2)
+            .unwrap();
+        let objects: Vec<Box3D> = (1..=5)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 1.0).unwrap())
+            .collect();
+
+        let result = pack_objects_with_config(objects, vec![blueprint], PackingConfig::default());
+
+        assert!(result.is_complete());
+        let counts: Vec<usize> = result.containers.iter().map(|c| c.placed.len()).collect();
+        assert_eq!(counts, vec![2, 2, 1]);
+        assert!(
+            ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 10.0)
+                .unwrap()
+                .with_max_items(0)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn top_clearance_lowers_the_stack_height_and_leaves_headroom() {
+        let cubes: Vec<Box3D> = (1..=3)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 1.0).unwrap())
+            .collect();
+        let blueprint = single_blueprint((10.0, 10.0, 30.0), 1000.0);
+
+        let full =
+            pack_objects_with_config(cubes.clone(), blueprint.clone(), PackingConfig::default());
+        assert_eq!(full.containers.len(), 1);
+        let diagnostics =
+            compute_container_diagnostics(&full.containers[0], &PackingConfig::default());
+        assert_eq!(diagnostics.headroom_remaining, 0.0);
+
+        let config = PackingConfig::builder().top_clearance(5.0).build();
+        let result = pack_objects_with_config(cubes, blueprint, config);
+        // The third cube would reach the ceiling and has to open a second container.
+        assert_eq!(result.containers.len(), 2);
+        assert_eq!(result.containers[0].placed.len(), 2);
+        let diagnostics = compute_container_diagnostics(&result.containers[0], &config);
+        assert_eq!(diagnostics.headroom_remaining, 10.0);
+    }
+
+    #[test]
+    fn object_is_never_placed_above_its_max_placement_height() {
+        let blueprint = single_blueprint((10.0, 10.0, 30.0), 1000.0);
+        let base = Box3D::new(1, (10.0, 10.0, 10.0), 10.0).unwrap();
+        let item = Box3D::new(2, (10.0, 10.0, 10.0), 1.0).unwrap();
+
+        // The top of the base is a stable spot within the container height.
+        let stacked = pack_objects_with_config(
+            vec![base.clone(), item.clone()],
+            blueprint.clone(),
+            PackingConfig::default(),
+        );
+        assert_eq!(stacked.containers.len(), 1);
+
+        let limited = Box3D {
+            max_placement_height: Some(5.0),
+            ..item
+        };
+        let result =
+            pack_objects_with_config(vec![base, limited], blueprint, PackingConfig::default());
+        assert_eq!(result.containers.len(), 2);
+        let placed = result.containers[1].placed[0].clone();
+        assert_eq!(placed.object.id, 2);
+        assert_eq!(placed.position.2, 0.0);
+    }
+
+    #[test]
+    fn z_layers_sort_degenerate_tops_without_panicking() {
+        let mut cont = Container::new((100.0, 100.0, 100.0), 1000.0).unwrap();
+        let tower = Box3D {
+            id: 1,
+            dims: (10.0, 10.0, 1e300),
+            weight: 1.0,
+            ..Default::default()
+        };
+        cont.placed.push(PlacedBox::new(tower, (0.0, 0.0, 0.0)));
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(2, (10.0, 10.0, 20.0), 1.0).unwrap(),
+            (50.0, 0.0, 0.0),
+        ));
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(3, (10.0, 10.0, 20.0), 1.0).unwrap(),
+            (50.0, 50.0, 0.0),
+        ));
+        let config = PackingConfig::builder().balance_limit_ratio(1.0).build();
+
+        assert_eq!(z_layers(&cont, &config), vec![0.0, 20.0, 1e300]);
+
+        let object = Box3D::new(4, (10.0, 10.0, 10.0), 1.0).unwrap();
+        let position = find_stable_position(&object, &cont, &config).expect("floor has room");
+        assert_eq!(position.2, 0.0);
+    }
+
+    #[test]
+    fn floor_fallback_beats_an_elevated_spot_outside_the_balance_limit() {
+        let mut cont = Container::new((100.0, 10.0, 100.0), 1000.0).unwrap();
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(1, (10.0, 10.0, 10.0), 100.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(2, (10.0, 10.0, 10.0), 10.0).unwrap(),
+            (90.0, 0.0, 0.0),
+        ));
+        let object = Box3D::new(3, (10.0, 10.0, 10.0), 10.0).unwrap();
+        // The heavy box at the left wall keeps every position outside the limit; balancing
+        // favours the top of box 2 as the farthest spot to the right.
+        let base = PackingConfig::builder()
+            .primary_objective(PrimaryObjective::Balance)
+            .balance_limit_ratio(0.01);
+
+        let elevated = find_stable_position(&object, &cont, &base.clone().build()).unwrap();
+        assert_eq!(elevated, (90.0, 0.0, 10.0));
+
+        let floor = find_stable_position(&object, &cont, &base.prefer_floor_fallback(true).build())
+            .unwrap();
+        assert_eq!(floor.2, 0.0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_group_packing_matches_the_serial_container_count() {
+        // Each group fills exactly one container with eight cubes.
+        let objects: Vec<Box3D> = (1..=16)
+            .map(|id| Box3D {
+                group_id: Some(if id <= 8 { "a" } else { "b" }.to_string()),
+                ..Box3D::new(id, (5.0, 5.0, 5.0), 1.0).unwrap()
+            })
+            .collect();
+        let templates = single_blueprint((10.0, 10.0, 10.0), 100.0);
+        let config = PackingConfig::builder().balance_limit_ratio(1.0).build();
+
+        let serial = pack_objects_with_config(objects.clone(), templates.clone(), config);
+        let parallel = pack_groups_parallel(objects, templates, config);
+
+        assert!(serial.is_complete() && parallel.is_complete());
+        assert_eq!(parallel.container_count(), serial.container_count());
+        for (container, group) in parallel.containers.iter().zip(["a", "b"]) {
+            assert!(
+                container
+                    .placed
+                    .iter()
+                    .all(|p| p.object.group_id.as_deref() == Some(group))
+            );
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_group_packing_numbers_labels_and_order_across_groups() {
+        // Group "b" is heavier, so ordering by weight puts its container first.
+        let objects: Vec<Box3D> = (1..=16)
+            .map(|id| Box3D {
+                group_id: Some(if id <= 8 { "a" } else { "b" }.to_string()),
+                ..Box3D::new(id, (5.0, 5.0, 5.0), if id <= 8 { 1.0 } else { 2.0 }).unwrap()
+            })
+            .collect();
+        let templates = vec![
+            ContainerBlueprint::new(0, Some("Crate #{n}".into()), (10.0, 10.0, 10.0), 100.0)
+                .unwrap(),
+        ];
+        let config = PackingConfig::builder()
+            .balance_limit_ratio(1.0)
+            .container_order(ContainerOrder::Weight)
+            .build();
+
+        let result = pack_groups_parallel(objects, templates, config);
+
+        let labels: Vec<_> = result
+            .containers
+            .iter()
+            .map(|c| c.label.as_deref().unwrap())
+            .collect();
+        assert_eq!(labels, ["Crate #2", "Crate #1"]);
+        assert_eq!(
+            result.containers[0].placed[0].object.group_id.as_deref(),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn rejection_counts_separate_balance_from_collisions() {
+        let cont = Container::new((100.0, 10.0, 100.0), 1000.0).unwrap();
+        // No grid position centers the object, so every spot misses the tight balance limit.
+        let object = Box3D::new(1, (33.0, 10.0, 10.0), 10.0).unwrap();
+        let config = PackingConfig::builder().balance_limit_ratio(0.001).build();
+        let mut counts = RejectionCounts::default();
+
+        let position = find_stable_position_tracked(&object, &cont, &config, Some(&mut counts));
+
+        assert!(position.is_some(), "balance is a soft limit");
+        assert!(counts.balance > 0);
+        assert_eq!(
+            counts,
+            RejectionCounts {
+                balance: counts.balance,
+                ..RejectionCounts::default()
+            }
+        );
+
+        // Packed results attach the counts to unplaced objects only when tracking is enabled.
+        let objects = vec![
+            Box3D::new(1, (10.0, 10.0, 10.0), 20.0).unwrap(),
+            Box3D {
+                optional: true,
+                ..Box3D::new(2, (10.0, 10.0, 10.0), 10.0).unwrap()
+            },
+        ];
+        let templates = single_blueprint((10.0, 10.0, 10.0), 100.0);
+        let tracked = pack_objects_with_config(
+            objects.clone(),
+            templates.clone(),
+            PackingConfig::builder().track_rejections(true).build(),
+        );
+        let rejections = tracked.unplaced[0].rejections.expect("tracking enabled");
+        assert!(rejections.collision > 0);
+        assert_eq!(rejections.balance, 0);
+
+        let untracked = pack_objects_with_config(objects, templates, PackingConfig::default());
+        assert_eq!(untracked.unplaced[0].rejections, None);
+    }
+
+    #[test]
+    fn positions_above_the_height_limits_count_as_height_rejections() {
+        // The floor is taken, and the only stacking layer lies above the allowed height.
+        let mut cont = Container::new((10.0, 10.0, 20.0), 100.0).unwrap();
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(1, (10.0, 10.0, 10.0), 5.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        let mut object = Box3D::new(2, (10.0, 10.0, 5.0), 1.0).unwrap();
+        object.max_placement_height = Some(5.0);
+        let mut counts = RejectionCounts::default();
+
+        let position = find_stable_position_tracked(
+            &object,
+            &cont,
+            &PackingConfig::default(),
+            Some(&mut counts),
+        );
+
+        assert_eq!(position, None);
+        assert!(counts.height > 0);
+        assert!(counts.collision > 0);
+        assert_eq!(counts.support, 0);
+    }
+
+    #[test]
+    fn wall_contact_preference_moves_box_against_the_far_wall() {
+        let mut cont = Container::new((30.0, 10.0, 20.0), 100.0).unwrap();
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(1, (5.0, 10.0, 10.0), 5.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        let object = Box3D::new(2, (10.0, 10.0, 10.0), 5.0).unwrap();
+        let base = PackingConfig::builder().balance_limit_ratio(1.0);
+
+        // Both floor spots are equally low and stable; the front-left preference picks x = 5.
+        assert_eq!(
+            find_stable_position(&object, &cont, &base.clone().build()),
+            Some((5.0, 0.0, 0.0))
+        );
+
+        // x = 20 additionally touches the far wall and wins ahead of the front-left preference.
+        let walls = base.prefer_wall_contact(true).build();
+        assert_eq!(
+            find_stable_position(&object, &cont, &walls),
+            Some((20.0, 0.0, 0.0))
+        );
+
+        // With an equal base score the wall-adjacent position wins; equal walls fall back to x.
+        let score = |x, wall_contacts| PlacementScore {
+            z: 0.0,
+            instability: 0.0,
+            support_ratio: 1.0,
+            support_centroid_offset_ratio: 0.0,
+            support_contact_count: 1,
+            category_contacts: 0,
+            center_moment: 0.0,
+            wall_contacts,
+            hint_distance: None,
+            y: 0.0,
+            x,
+            balance_shift: 0.0,
+            balance: 0.0,
+        };
+        assert!(is_better_score(score(20.0, 3), score(5.0, 2), &walls));
+        assert!(is_better_score(score(5.0, 2), score(20.0, 2), &walls));
+    }
+
+    #[test]
+    fn hinted_box_lands_at_its_hint_when_space_allows() {
+        let blueprint = single_blueprint((100.0, 100.0, 50.0), 1000.0);
+        let mut hinted = Box3D::new(1, (20.0, 20.0, 20.0), 5.0).unwrap();
+
+        let unhinted =
+            pack_objects_with_config(vec![hinted.clone()], blueprint.clone(), Default::default());
+        let regular_position = unhinted.containers[0].placed[0].position;
+
+        hinted.position_hint = Some((42.0, 61.0, 0.0));
+        let result =
+            pack_objects_with_config(vec![hinted.clone()], blueprint.clone(), Default::default());
+        assert_eq!(result.containers[0].placed[0].position, (42.0, 61.0, 0.0));
+
+        // A hint region out of reach (no support up there) falls back to the regular scoring.
+        hinted.position_hint = Some((42.0, 61.0, 45.0));
+        let result = pack_objects_with_config(vec![hinted], blueprint, Default::default());
+        assert_eq!(result.containers[0].placed[0].position, regular_position);
+    }
+
+    #[test]
+    fn object_exactly_filling_the_container_is_placed_at_origin() {
+        let blueprint = single_blueprint((10.0, 20.0, 30.0), 100.0);
+        let config = PackingConfig::default();
+
+        let exact = Box3D::new(1, (10.0, 20.0, 30.0), 5.0).unwrap();
+        let result = pack_objects_with_config(vec![exact], blueprint.clone(), config);
+        assert!(result.unplaced.is_empty());
+        assert_eq!(result.containers.len(), 1);
+        assert_eq!(result.containers[0].placed[0].position, (0.0, 0.0, 0.0));
+
+        let mut rotated = Box3D::new(2, (30.0, 20.0, 10.0), 5.0).unwrap();
+        rotated.allow_rotation = Some(true);
+        let result = pack_objects_with_config(vec![rotated], blueprint, config);
+        assert!(result.unplaced.is_empty());
+        let placed = &result.containers[0].placed[0];
+        assert_eq!(placed.position, (0.0, 0.0, 0.0));
+        assert_eq!(placed.object.dims, (10.0, 20.0, 30.0));
+    }
+
+    #[test]
+    fn axis_positions_are_capped_for_huge_containers() {
+        let eps = PackingConfig::DEFAULT_GENERAL_EPSILON;
+
+        // 1e9 units at a 0.001 step would request 1e12 positions.
+        let positions = axis_positions(1e9, 1.0, 0.001, eps, 10_000);
+        assert!(
+            positions.len() <= 10_000,
+            "got {} positions",
+            positions.len()
+        );
+        assert_eq!(positions.first().copied(), Some(0.0));
+        assert!((positions.last().unwrap() - (1e9 - 1.0)).abs() < eps);
+
+        // Small grids below the cap keep their requested step.
+        assert_eq!(
+            axis_positions(10.0, 3.0, 5.0, eps, 10_000),
+            vec![0.0, 5.0, 7.0]
+        );
+    }
+
+    #[test]
+    fn diagnostics_report_a_coarsened_grid_step() {
+        let container = Container::new((1000.0, 10.0, 10.0), 100.0).unwrap();
+        let capped = PackingConfig::builder()
+            .grid_step(1.0)
+            .max_axis_positions(102)
+            .build();
+
+        let step = compute_container_diagnostics(&container, &capped).coarsened_grid_step;
+        assert_eq!(step, Some(10.0));
+        let uncapped = PackingConfig::builder().grid_step(1.0).build();
+        assert_eq!(
+            compute_container_diagnostics(&container, &uncapped).coarsened_grid_step,
+            None
+        );
+    }
+
+    #[test]
+    fn strict_layering_rejects_light_floor_box_below_heavier_layer() {
+        let mut cont = Container::new((20.0, 10.0, 20.0), 100.0).unwrap();
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(1, (10.0, 10.0, 10.0), 2.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(2, (10.0, 10.0, 10.0), 2.0).unwrap(),
+            (0.0, 0.0, 10.0),
+        ));
+        // A light box next to the floor box pulls the floor average (1.25) below the layer above (2.0).
+        let light = Box3D::new(3, (10.0, 10.0, 10.0), 0.5).unwrap();
+
+        let default_config = PackingConfig::default();
+        assert_eq!(
+            find_stable_position(&light, &cont, &default_config),
+            Some((10.0, 0.0, 0.0))
+        );
+
+        let strict_config = PackingConfig::builder().strict_layering(true).build();
+        assert_eq!(find_stable_position(&light, &cont, &strict_config), None);
+    }
+
+    #[test]
+    fn balance_objective_prefers_higher_centered_placement() {
+        let mut cont = Container::new((12.0, 4.0, 20.0), 100.0).unwrap();
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(1, (4.0, 4.0, 4.0), 10.0).unwrap(),
+            (4.0, 0.0, 0.0),
+        ));
+        let object = Box3D::new(2, (4.0, 4.0, 4.0), 10.0).unwrap();
+        let base = PackingConfig::builder().grid_step(1.0);
+
+        // The floor beside the middle box is lower but shifts the center of gravity sideways.
+        let compact = base.clone().build();
+        assert_eq!(
+            find_stable_position(&object, &cont, &compact),
+            Some((0.0, 0.0, 0.0))
+        );
+
+        // Stacking directly on top keeps the load perfectly centered.
+        let balanced = base.primary_objective(PrimaryObjective::Balance).build();
+        assert_eq!(
+            find_stable_position(&object, &cont, &balanced),
+            Some((4.0, 0.0, 4.0))
+        );
+    }
+
+    #[test]
+    fn max_overhang_rejects_center_supported_box() {
+        let mut cont = Container::new((16.0, 10.0, 30.0), 100.0).unwrap();
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(1, (10.0, 10.0, 10.0), 5.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        // The center (x = 8) rests on the lower box, but 6 units protrude past its edge.
+        let wide = Box3D::new(2, (16.0, 10.0, 5.0), 1.0).unwrap();
+
+        assert_eq!(
+            find_stable_position(&wide, &cont, &PackingConfig::default()),
+            Some((0.0, 0.0, 10.0))
+        );
+
+        let capped = PackingConfig::builder().max_overhang(Some(4.0)).build();
+        assert_eq!(find_stable_position(&wide, &cont, &capped), None);
+
+        let lenient = PackingConfig::builder().max_overhang(Some(6.0)).build();
+        assert_eq!(
+            find_stable_position(&wide, &cont, &lenient),
+            Some((0.0, 0.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn restarts_never_use_more_containers_than_a_single_run() {
+        let templates = single_blueprint((30.0, 30.0, 30.0), 500.0);
+        let objects: Vec<Box3D> = (1..=24)
+            .map(|id| {
+                let size = 6.0 + (id * 7 % 9) as f64;
+                let height = 5.0 + (id * 5 % 7) as f64;
+                Box3D::new(id, (size, 20.0 - size, height), 1.0 + (id % 5) as f64).unwrap()
+            })
+            .collect();
+
+        let single =
+            pack_objects_with_config(objects.clone(), templates.clone(), PackingConfig::default());
+        let restarted = pack_objects_with_config(
+            objects,
+            templates,
+            PackingConfig::builder().restarts(8).build(),
+        );
+
+        assert!(restarted.unplaced.len() <= single.unplaced.len());
+        assert!(restarted.containers.len() <= single.containers.len());
+    }
+
+    #[test]
+    fn restarts_stream_only_the_winning_run() {
+        let templates = single_blueprint((20.0, 20.0, 20.0), 100.0);
+        let objects: Vec<Box3D> = (1..=6)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), id as f64).unwrap())
+            .collect();
+        let mut events = Vec::new();
+        let result = pack_objects_with_progress(
+            objects,
+            templates,
+            PackingConfig::builder().restarts(3).build(),
+            |event| events.push(event.clone()),
+        );
+
+        let placed_events = events
+            .iter()
+            .filter(|event| matches!(event, PackEvent::ObjectPlaced { .. }))
+            .count();
+        let finished_events = events
+            .iter()
+            .filter(|event| matches!(event, PackEvent::Finished { .. }))
+            .count();
+        let placed: usize = result.containers.iter().map(|c| c.placed.len()).sum();
+        assert_eq!(placed_events, placed);
+        assert_eq!(finished_events, 1);
+    }
+
+    #[test]
+    fn finished_event_reports_fill_per_container() {
+        // Every object reaches the weight limit alone, so each needs its own container.
+        let templates = single_blueprint((10.0, 10.0, 10.0), 10.0);
+        let objects: Vec<Box3D> = (1..=3)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 5.0), 10.0).unwrap())
+            .collect();
+        let mut container_fill = None;
+        let result =
+            pack_objects_with_progress(objects, templates, PackingConfig::default(), |event| {
+                if let PackEvent::Finished {
+                    container_fill: fill,
+                    ..
+                } = event
+                {
+                    container_fill = Some(fill.clone());
+                }
+            });
+
+        let container_fill = container_fill.expect("Finished event emitted");
+        assert_eq!(result.containers.len(), 3);
+        assert_eq!(container_fill.len(), result.containers.len());
+        for (idx, (id, utilization, weight)) in container_fill.into_iter().enumerate() {
+            assert_eq!(id, idx + 1);
+            assert!((utilization - 50.0).abs() < 1e-6);
+            assert!((weight - 10.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn near_identical_templates_collapse_into_the_larger_one() {
+        let templates = vec![
+            ContainerBlueprint::new(0, None, (120.0, 100.0, 80.0), 100.0).unwrap(),
+            ContainerBlueprint::new(1, None, (120.0, 100.0, 80.1), 100.0).unwrap(),
+        ];
+        // Two heavy objects exceed one container's weight limit.
+        let objects = vec![
+            Box3D::new(1, (50.0, 50.0, 50.0), 60.0).unwrap(),
+            Box3D::new(2, (50.0, 50.0, 50.0), 60.0).unwrap(),
+        ];
+
+        let separate =
+            pack_objects_with_config(objects.clone(), templates.clone(), PackingConfig::default());
+        assert_eq!(separate.containers.len(), 2);
+        assert!(separate.containers.iter().all(|c| c.template_id == Some(0)));
+
+        let merged = pack_objects_with_config(
+            objects,
+            templates,
+            PackingConfig::builder()
+                .template_merge_tolerance(0.01)
+                .build(),
+        );
+        assert_eq!(merged.containers.len(), 2);
+        assert!(merged.containers.iter().all(|c| c.template_id == Some(1)));
+    }
+
+    #[test]
+    fn find_fit_reports_position_or_reason() {
+        let template = ContainerBlueprint::new(0, None, (30.0, 20.0, 20.0), 50.0).unwrap();
+
+        let small = Box3D::new(1, (10.0, 10.0, 10.0), 5.0).unwrap();
+        let placed = find_fit(&small, &template, PackingConfig::default()).unwrap();
+        assert_eq!(placed.position.2, 0.0);
+        assert!(template.contains_region(placed.position, placed.object.dims));
+
+        let heavy = Box3D::new(2, (10.0, 10.0, 10.0), 60.0).unwrap();
+        assert_eq!(
+            find_fit(&heavy, &template, PackingConfig::default()).unwrap_err(),
+            UnplacedReason::TooHeavyForContainer
+        );
+
+        // Only fits lying down, which requires rotations.
+        let tall = Box3D::new(3, (5.0, 5.0, 25.0), 1.0).unwrap();
+        assert_eq!(
+            find_fit(&tall, &template, PackingConfig::default()).unwrap_err(),
+            UnplacedReason::WouldFitIfRotated
+        );
+        let rotated = PackingConfig::builder().allow_item_rotation(true).build();
+        let placed = find_fit(&tall, &template, rotated).unwrap();
+        assert!(placed.object.dims.2 <= 20.0);
+    }
+
+    #[test]
+    fn relative_tolerance_makes_placement_scale_invariant() {
+        let run = |unit: f64, relative: bool| {
+            let templates = single_blueprint((2.0 * unit, unit, 2.0 * unit), 100.0);
+            let objects = (1..=4)
+                .map(|id| Box3D::new(id, (unit, unit, unit), 1.0).unwrap())
+                .collect();
+            let config = PackingConfig::builder()
+                .grid_step(unit)
+                .relative_tolerance(relative)
+                .build();
+            pack_objects_with_config(objects, templates, config)
+        };
+        let positions = |result: &PackingResult, unit: f64| {
+            let mut positions: Vec<(usize, (i64, i64, i64))> = result
+                .containers
+                .iter()
+                .flat_map(|c| &c.placed)
+                .map(|p| {
+                    let (x, y, z) = p.position;
+                    let scaled = |v: f64| (v / unit * 1e6).round() as i64;
+                    (p.object.id, (scaled(x), scaled(y), scaled(z)))
+                })
+                .collect();
+            positions.sort();
+            positions
+        };
+
+        // Millimeters and meters produce the same layout once tolerances scale with the objects.
+        let millimeters = run(500.0, true);
+        let meters = run(0.5, true);
+        assert!(millimeters.unplaced.is_empty() && meters.unplaced.is_empty());
+        assert_eq!(positions(&millimeters, 500.0), positions(&meters, 0.5));
+
+        // At micro scale the absolute height tolerance swallows whole layers, so nothing can be
+        // stacked and extra containers are opened.
+        assert_eq!(meters.containers.len(), 1);
+        let micro_absolute = run(1e-7, false);
+        assert!(micro_absolute.containers.len() > 1);
+        let micro_relative = run(1e-7, true);
+        assert_eq!(micro_relative.containers.len(), 1);
+        assert_eq!(positions(&micro_relative, 1e-7), positions(&meters, 0.5));
+    }
+
+    #[test]
+    fn containers_from_one_template_get_numbered_labels() {
+        let templates = vec![
+            ContainerBlueprint::new(
+                0,
+                Some("Crate A #{n}".to_string()),
+                (10.0, 10.0, 10.0),
+                10.0,
+            )
+            .unwrap(),
+        ];
+        // Every object reaches the weight limit on its own, forcing one container each.
+        let objects = (1..=3)
+            .map(|id| Box3D::new(id, (5.0, 5.0, 5.0), 10.0).unwrap())
+            .collect();
+
+        let result = pack_objects(objects, templates);
+        let labels: Vec<_> = result
+            .containers
+            .iter()
+            .map(|c| c.label.as_deref().unwrap())
+            .collect();
+        assert_eq!(labels, vec!["Crate A #1", "Crate A #2", "Crate A #3"]);
+    }
+
+    #[test]
+    fn sparse_floor_layer_is_flagged_below_coverage_threshold() {
+        let mut cont = Container::new((100.0, 100.0, 50.0), 1000.0).unwrap();
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(1, (20.0, 20.0, 10.0), 5.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(2, (20.0, 20.0, 10.0), 5.0).unwrap(),
+            (80.0, 80.0, 0.0),
+        ));
+        // Stacked objects do not count towards floor coverage.
+        cont.placed.push(PlacedBox::new(
+            Box3D::new(3, (20.0, 20.0, 10.0), 1.0).unwrap(),
+            (0.0, 0.0, 10.0),
+        ));
+
+        let unchecked = compute_container_diagnostics(&cont, &PackingConfig::default());
+        assert!((unchecked.floor_coverage_percent - 8.0).abs() < 1e-9);
+        assert!(unchecked.floor_coverage_ok);
+
+        let config = PackingConfig::builder()
+            .floor_min_coverage(Some(0.5))
+            .build();
+        let checked = compute_container_diagnostics(&cont, &config);
+        assert!(!checked.floor_coverage_ok);
+
+        let lenient = PackingConfig::builder()
+            .floor_min_coverage(Some(0.05))
+            .build();
+        assert!(compute_container_diagnostics(&cont, &lenient).floor_coverage_ok);
+    }
+
+    #[test]
+    fn utilization_order_lists_the_fullest_container_first() {
+        // The heavier object opens the first container; together they exceed the weight limit.
+        let templates = vec![ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 10.0).unwrap()];
+        let objects = vec![
+            Box3D::new(1, (10.0, 10.0, 2.0), 10.0).unwrap(),
+            Box3D::new(2, (10.0, 10.0, 8.0), 9.0).unwrap(),
+        ];
+        let pack = |order| {
+            pack_objects_with_config(
+                objects.clone(),
+                templates.clone(),
+                PackingConfig::builder().container_order(order).build(),
+            )
+        };
+
+        let created = pack(ContainerOrder::Creation);
+        assert_eq!(created.containers.len(), 2);
+        assert_eq!(created.containers[0].placed[0].object.id, 1);
+
+        let by_utilization = pack(ContainerOrder::Utilization);
+        assert_eq!(by_utilization.containers[0].placed[0].object.id, 2);
+        let utilization: Vec<f64> = by_utilization
+            .container_diagnostics
+            .iter()
+            .map(|d| d.volume_utilization_percent)
+            .collect();
+        assert!((utilization[0] - 80.0).abs() < 1e-6);
+        assert!((utilization[1] - 20.0).abs() < 1e-6);
+
+        let by_weight = pack(ContainerOrder::Weight);
+        assert_eq!(by_weight.containers[0].placed[0].object.id, 1);
+    }
+
+    #[test]
+    fn bulky_object_is_rejected_above_volume_fraction() {
+        let templates = vec![ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 100.0).unwrap()];
+        // 10 × 10 × 9 occupies 90% of the container volume.
+        let objects = vec![
+            Box3D::new(1, (10.0, 10.0, 9.0), 5.0).unwrap(),
+            Box3D::new(2, (5.0, 5.0, 5.0), 1.0).unwrap(),
+        ];
+
+        let unrestricted = pack_objects(objects.clone(), templates.clone());
+        assert!(unrestricted.unplaced.is_empty());
+
+        let result = pack_objects_with_config(
+            objects,
+            templates,
+            PackingConfig::builder()
+                .max_object_volume_fraction(Some(0.5))
+                .build(),
+        );
+        assert_eq!(result.unplaced.len(), 1);
+        assert_eq!(result.unplaced[0].object.id, 1);
+        assert_eq!(result.unplaced[0].reason, UnplacedReason::ObjectTooBulky);
+        assert_eq!(result.containers.len(), 1);
+        assert_eq!(result.containers[0].placed[0].object.id, 2);
+    }
+
+    #[test]
+    fn equal_weight_boxes_stack_unless_disabled() {
+        let templates = single_blueprint((10.0, 10.0, 20.0), 100.0);
+        let objects: Vec<Box3D> = (1..=2)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 5.0).unwrap())
+            .collect();
+
+        let stacked = pack_objects(objects.clone(), templates.clone());
+        assert_eq!(stacked.containers.len(), 1);
+        assert_eq!(stacked.containers[0].placed.len(), 2);
+        assert_eq!(stacked.container_diagnostics[0].weight_order_violations, 0);
+
+        let strict = pack_objects_with_config(
+            objects,
+            templates,
+            PackingConfig::builder()
+                .allow_equal_weight_stacking(false)
+                .build(),
+        );
+        assert_eq!(strict.containers.len(), 2);
+    }
+
+    #[test]
+    fn weight_order_epsilon_boundary_is_consistent() {
+        let config = PackingConfig::default();
+        let eps = config.general_epsilon;
+
+        assert!(weight_may_rest_on(5.0 + eps / 2.0, 5.0, &config));
+        assert!(!weight_may_rest_on(5.0 + 2.0 * eps, 5.0, &config));
+
+        // Placement and diagnostics agree on the same boundary.
+        let lower = PlacedBox::new(
+            Box3D::new(1, (10.0, 10.0, 10.0), 5.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        );
+        for (upper_weight, allowed) in [(5.0 + eps / 2.0, true), (5.0 + 2.0 * eps, false)] {
+            let mut container = Container::new((10.0, 10.0, 20.0), 100.0).unwrap();
+            container.placed.push(lower.clone());
+            let upper = Box3D::new(2, (10.0, 10.0, 10.0), upper_weight).unwrap();
+            assert_eq!(
+                find_stable_position(&upper, &container, &config).is_some(),
+                allowed
+            );
+
+            container
+                .placed
+                .push(PlacedBox::new(upper, (0.0, 0.0, 10.0)));
+            let violations =
+                compute_container_diagnostics(&container, &config).weight_order_violations;
+            assert_eq!(violations, usize::from(!allowed));
+        }
+    }
+
+    #[test]
+    fn whitelisted_object_skips_other_container_types() {
+        let templates = vec![
+            ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 100.0).unwrap(),
+            ContainerBlueprint::new(1, Some("Refrigerated".into()), (20.0, 20.0, 20.0), 100.0)
+                .unwrap(),
+        ];
+        let restricted = |id: usize, allowed: Vec<usize>| Box3D {
+            allowed_template_ids: Some(allowed),
+            ..Box3D::new(id, (5.0, 5.0, 5.0), 1.0).unwrap()
+        };
+        let objects = vec![
+            // Opens a container of the smaller type 0 first.
+            Box3D::new(1, (5.0, 5.0, 5.0), 10.0).unwrap(),
+            // Would fit next to object 1, but may only use type 1.
+            restricted(2, vec![1]),
+            restricted(3, vec![7]),
+        ];
+
+        let result = pack_objects(objects, templates);
+
+        assert_eq!(result.containers.len(), 2);
+        let container_of = |id: usize| {
+            result
+                .containers
+                .iter()
+                .find(|c| c.placed.iter().any(|p| p.object.id == id))
+                .and_then(|c| c.template_id)
+        };
+        assert_eq!(container_of(1), Some(0));
+        assert_eq!(container_of(2), Some(1));
+
+        assert_eq!(result.unplaced.len(), 1);
+        assert_eq!(result.unplaced[0].object.id, 3);
+        assert_eq!(
+            result.unplaced[0].reason,
+            UnplacedReason::NoAllowedContainer
+        );
+    }
+
+    #[test]
+    fn container_stops_receiving_objects_at_target_utilization() {
+        let templates = single_blueprint((10.0, 10.0, 10.0), 100.0);
+        // Each object fills 25% of a container's volume.
+        let objects: Vec<Box3D> = (1..=4)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 2.5), 1.0).unwrap())
+            .collect();
+
+        let unrestricted = pack_objects(objects.clone(), templates.clone());
+        assert_eq!(unrestricted.containers.len(), 1);
+
+        let capped = pack_objects_with_config(
+            objects,
+            templates,
+            PackingConfig::builder()
+                .target_utilization(Some(0.5))
+                .build(),
+        );
+        assert!(capped.unplaced.is_empty());
+        assert_eq!(capped.containers.len(), 2);
+        for diagnostics in &capped.container_diagnostics {
+            assert!((diagnostics.volume_utilization_percent - 50.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn sampled_support_matches_exact_overlap_for_plain_boxes() {
+        let exact = PackingConfig::default();
+        let sampled = PackingConfig::builder()
+            .support_samples_per_axis(64)
+            .build();
+        let mut container = Container::new((40.0, 40.0, 40.0), 100.0).unwrap();
+        container.placed.push(PlacedBox::new(
+            Box3D::new(1, (20.0, 20.0, 10.0), 10.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        container.placed.push(PlacedBox::new(
+            Box3D::new(2, (10.0, 20.0, 10.0), 10.0).unwrap(),
+            (20.0, 0.0, 0.0),
+        ));
+
+        for (position, dims) in [
+            ((5.0, 5.0, 10.0), (20.0, 10.0, 5.0)),
+            ((12.5, 3.0, 10.0), (15.0, 25.0, 5.0)),
+            ((0.0, 0.0, 10.0), (30.0, 20.0, 5.0)),
+        ] {
+            let upper = PlacedBox::new(Box3D::new(3, dims, 1.0).unwrap(), position);
+            let exact_ratio = support_ratio_of(&upper, &container, &exact);
+            let sampled_ratio = support_ratio_of(&upper, &container, &sampled);
+            assert!(
+                (exact_ratio - sampled_ratio).abs() < 0.02,
+                "exact {exact_ratio} vs sampled {sampled_ratio} at {position:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn accessible_object_stays_reachable_from_the_door() {
+        let container = ContainerBlueprint::new(0, None, (10.0, 10.0, 10.0), 1000.0).unwrap();
+        let mut objects: Vec<Box3D> = (1..=7)
+            .map(|id| Box3D::new(id, (5.0, 5.0, 5.0), 10.0).unwrap())
+            .collect();
+        objects.push(Box3D {
+            must_be_accessible: true,
+            ..Box3D::new(99, (5.0, 5.0, 5.0), 1.0).unwrap()
+        });
+
+        let result = pack_objects(objects, vec![container]);
+
+        assert!(result.unplaced.is_empty());
+        let (host, accessible) = result
+            .containers
+            .iter()
+            .find_map(|c| c.placed.iter().find(|p| p.object.id == 99).map(|p| (c, p)))
+            .expect("accessible object must be placed");
+        assert!(accessible.position.1.abs() < 1e-6);
+        assert!(host.placed.iter().all(|p| {
+            p.object.id == 99
+                || p.position.1 >= accessible.position.1
+                || overlap_1d(
+                    p.position.0,
+                    p.position.0 + p.object.dims.0,
+                    accessible.position.0,
+                    accessible.position.0 + accessible.object.dims.0,
+                ) <= 1e-6
+                || overlap_1d(
+                    p.position.2,
+                    p.position.2 + p.object.dims.2,
+                    accessible.position.2,
+                    accessible.position.2 + accessible.object.dims.2,
+                ) <= 1e-6
+        }));
+    }
+
+    #[test]
+    fn short_item_prefers_the_short_container_of_equal_volume() {
+        let templates = vec![
+            ContainerBlueprint::new(0, Some("Tall".into()), (20.0, 20.0, 40.0), 1000.0).unwrap(),
+            ContainerBlueprint::new(1, Some("Short".into()), (40.0, 40.0, 10.0), 1000.0).unwrap(),
+        ];
+        let objects = vec![Box3D::new(1, (10.0, 10.0, 8.0), 1.0).unwrap()];
+
+        let result = pack_objects_with_config(objects, templates, PackingConfig::default());
+
+        assert!(result.unplaced.is_empty());
+        assert_eq!(result.containers[0].template_id, Some(1));
+    }
+
+    #[test]
+    fn best_fit_for_remaining_opens_a_container_for_the_remaining_volume() {
+        let templates = vec![
+            ContainerBlueprint::new(0, Some("Tiny".into()), (10.0, 10.0, 10.0), 1000.0).unwrap(),
+            ContainerBlueprint::new(1, Some("Large".into()), (20.0, 20.0, 20.0), 1000.0).unwrap(),
+        ];
+        let objects: Vec<Box3D> = (1..=20)
+            .map(|id| Box3D::new(id, (5.0, 5.0, 5.0), 1.0).unwrap())
+            .collect();
+        let pack = |policy| {
+            pack_objects_with_config(
+                objects.clone(),
+                templates.clone(),
+                PackingConfig::builder()
+                    .new_container_policy(policy)
+                    .build(),
+            )
+        };
+
+        let smallest = pack(NewContainerPolicy::SmallestFitting);
+        let best_fit = pack(NewContainerPolicy::BestFitForRemaining);
+
+        assert!(smallest.unplaced.is_empty());
+        assert!(best_fit.unplaced.is_empty());
+        assert_eq!(smallest.containers[0].template_id, Some(0));
+        assert!(smallest.containers.len() > 1);
+        assert_eq!(best_fit.containers.len(), 1);
+        assert_eq!(best_fit.containers[0].template_id, Some(1));
+    }
+
+    #[test]
+    fn largest_first_packs_bulk_orders_into_fewer_containers() {
+        let templates = vec![
+            ContainerBlueprint::new(0, Some("Small".into()), (10.0, 10.0, 10.0), 1000.0).unwrap(),
+            ContainerBlueprint::new(1, Some("Large".into()), (20.0, 20.0, 20.0), 1000.0).unwrap(),
+        ];
+        let objects: Vec<Box3D> = (1..=16)
+            .map(|id| Box3D::new(id, (5.0, 5.0, 5.0), 1.0).unwrap())
+            .collect();
+        let pack = |preference| {
+            pack_objects_with_config(
+                objects.clone(),
+                templates.clone(),
+                PackingConfig::builder()
+                    .template_preference(preference)
+                    .build(),
+            )
+        };
+
+        let smallest_first = pack(TemplatePreference::SmallestFirst);
+        let largest_first = pack(TemplatePreference::LargestFirst);
+
+        assert!(smallest_first.unplaced.is_empty());
+        assert!(largest_first.unplaced.is_empty());
+        assert_eq!(smallest_first.containers.len(), 2);
+        assert_eq!(largest_first.containers.len(), 1);
+        assert_eq!(largest_first.containers[0].template_id, Some(1));
+    }
+
+    #[test]
+    fn gap_constrained_objects_keep_their_clearance() {
+        let container = ContainerBlueprint::new(0, None, (20.0, 10.0, 10.0), 1000.0).unwrap();
+        let objects = vec![
+            Box3D {
+                min_gaps: vec![ObjectGap { id: 2, gap: 6.0 }],
+                ..Box3D::new(1, (5.0, 5.0, 5.0), 2.0).unwrap()
+            },
+            Box3D::new(2, (5.0, 5.0, 5.0), 2.0).unwrap(),
+            Box3D::new(3, (5.0, 5.0, 5.0), 1.0).unwrap(),
+        ];
+
+        // Lift the balance limit so both objects can share the narrow container.
+        let config = PackingConfig::builder().balance_limit_ratio(1.0).build();
+        let result = pack_objects_with_config(objects, vec![container], config);
+
+        assert!(result.unplaced.is_empty());
+        assert_eq!(result.containers.len(), 1);
+        let position = |id: usize| {
+            result.containers[0]
+                .placed
+                .iter()
+                .find(|p| p.object.id == id)
+                .map(|p| p.position)
+                .unwrap()
+        };
+        let (a, b) = (position(1), position(2));
+        let separation = |start_a: f64, start_b: f64| (start_b - start_a).abs() - 5.0;
+        let widest = separation(a.0, b.0)
+            .max(separation(a.1, b.1))
+            .max(separation(a.2, b.2));
+        assert!(widest >= 6.0 - 1e-6, "objects only {widest} apart");
+    }
+
+    #[test]
+    fn object_fitting_only_rotated_reports_would_fit_if_rotated() {
+        let container = ContainerBlueprint::new(0, None, (10.0, 30.0, 10.0), 100.0).unwrap();
+        let objects = vec![
+            Box3D::new(1, (25.0, 5.0, 5.0), 1.0).unwrap(),
+            Box3D::new(2, (40.0, 5.0, 5.0), 1.0).unwrap(),
+        ];
+
+        let result = pack_objects(objects, vec![container]);
+
+        let reason_of = |id: usize| {
+            result
+                .unplaced
+                .iter()
+                .find(|entry| entry.object.id == id)
+                .map(|entry| entry.reason.clone())
+        };
+        assert_eq!(reason_of(1), Some(UnplacedReason::WouldFitIfRotated));
+        assert_eq!(
+            reason_of(2),
+            Some(UnplacedReason::DimensionsExceedContainer)
+        );
+    }
+
+    #[test]
+    fn diagnostics_interval_throttles_events_without_changing_the_result() {
+        let objects: Vec<Box3D> = (1..=40)
+            .map(|id| Box3D::new(id, (5.0, 5.0, 5.0), 1.0).unwrap())
+            .collect();
+        let run = |interval: usize| {
+            let mut diagnostics_events = 0usize;
+            let mut last_diagnostics = std::collections::HashMap::new();
+            let result = pack_objects_with_progress(
+                objects.clone(),
+                single_blueprint((20.0, 20.0, 20.0), 1000.0),
+                PackingConfig::builder()
+                    .diagnostics_interval(interval)
+                    .build(),
+                |evt| {
+                    if let PackEvent::ContainerDiagnostics {
+                        container_id,
+                        diagnostics,
+                    } = evt
+                    {
+                        diagnostics_events += 1;
+                        last_diagnostics.insert(*container_id, diagnostics.clone());
+                    }
+                },
+            );
+            (result, diagnostics_events, last_diagnostics)
+        };
+
+        let (every, every_events, _) = run(1);
+        let (throttled, throttled_events, last_streamed) = run(5);
+
+        assert!(
+            throttled_events * 4 <= every_events,
+            "{throttled_events} vs {every_events} events"
+        );
+        let positions = |result: &PackingResult| {
+            result
+                .containers
+                .iter()
+                .flat_map(|c| c.placed.iter().map(|p| (p.object.id, p.position)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(positions(&every), positions(&throttled));
+        assert_eq!(every.container_diagnostics, throttled.container_diagnostics);
+        for (idx, diagnostics) in throttled.container_diagnostics.iter().enumerate() {
+            assert_eq!(&last_streamed[&(idx + 1)], diagnostics);
+        }
+    }
+
+    #[test]
+    fn throttled_diagnostics_are_real_for_new_containers() {
+        let objects: Vec<Box3D> = (1..=20)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 1.0).unwrap())
+            .collect();
+        let mut events = Vec::new();
+        pack_objects_with_progress(
+            objects,
+            single_blueprint((20.0, 20.0, 10.0), 1000.0),
+            PackingConfig::builder().diagnostics_interval(5).build(),
+            |evt| events.push(evt.clone()),
+        );
+
+        let started: Vec<usize> = events
+            .iter()
+            .enumerate()
+            .filter(|(_, evt)| matches!(evt, PackEvent::ContainerStarted { .. }))
+            .map(|(idx, _)| idx)
+            .collect();
+        assert!(started.len() > 1, "expected containers opened mid-run");
+        for idx in started {
+            // ContainerStarted, ObjectPlaced, then the diagnostics of the opened container.
+            match events.get(idx + 2) {
+                Some(PackEvent::ContainerDiagnostics { diagnostics, .. }) => {
+                    assert!(diagnostics.volume_utilization_percent > 0.0);
+                }
+                other => panic!("expected diagnostics for a new container, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn conservative_profile_lowers_imbalance() {
+        let templates =
+            vec![ContainerBlueprint::new(0, None, (100.0, 100.0, 60.0), 1000.0).unwrap()];
+        let objects: Vec<Box3D> = (1..=3)
+            .map(|id| Box3D::new(id, (20.0, 20.0, 20.0), 10.0).unwrap())
+            .collect();
+        let pack = |config: PackingConfig| {
+            let result = pack_objects_with_config(objects.clone(), templates.clone(), config);
+            assert!(result.unplaced.is_empty());
+            result.diagnostics_summary.max_imbalance_ratio
+        };
+
+        let default = pack(PackingConfig::default());
+        let conservative =
+            pack(PackingConfig::default().with_profile(PackingProfile::Conservative));
+
+        assert!(
+            conservative < default,
+            "conservative {conservative} vs default {default}"
+        );
+        let preset = PackingConfig::conservative();
+        assert_eq!(
+            preset.support_ratio,
+            PackingConfig::CONSERVATIVE_SUPPORT_RATIO
+        );
+        assert_eq!(preset.max_overhang, Some(0.0));
+        assert_eq!(preset.primary_objective, PrimaryObjective::Balance);
+        assert!(!preset.allow_item_rotation);
+    }
+
+    #[test]
+    fn same_category_objects_touch_when_clustering() {
+        let categorized = |id: usize, category: &str| {
+            let mut object = Box3D::new(id, (5.0, 5.0, 5.0), 1.0).unwrap();
+            object.category = Some(category.to_string());
+            object
+        };
+        let mut cont = Container::new((30.0, 30.0, 10.0), 100.0).unwrap();
+        let anchor = PlacedBox::new(categorized(1, "tools"), (20.0, 10.0, 0.0));
+        cont.placed.push(anchor.clone());
+        let object = categorized(2, "tools");
+        let base = PackingConfig::builder().balance_limit_ratio(1.0);
+
+        let touches_at = |config: &PackingConfig| {
+            let position = find_stable_position(&object, &cont, config).unwrap();
+            touches_sideways(&PlacedBox::new(object.clone(), position), &anchor, 1e-6)
+        };
+        assert!(!touches_at(&base.clone().build()));
+        assert!(touches_at(&base.cluster_by_category(true).build()));
+    }
+
+    #[test]
+    fn settling_lowers_box_with_air_gap_onto_the_box_below() {
+        let config = PackingConfig::builder().settle(true).build();
+        let mut container = Container::new((20.0, 20.0, 40.0), 100.0).unwrap();
+        container.placed.push(PlacedBox::new(
+            Box3D::new(1, (10.0, 10.0, 10.0), 2.0).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        container.placed.push(PlacedBox::new(
+            Box3D::new(2, (10.0, 10.0, 5.0), 1.0).unwrap(),
+            (0.0, 0.0, 14.0),
+        ));
+
+        assert!(settle_container(&mut container, 0, &config));
+
+        let upper = container.placed.iter().find(|p| p.object.id == 2).unwrap();
+        assert_eq!(upper.position, (0.0, 0.0, 10.0));
+        assert!(!settle_container(&mut container, 0, &config));
+    }
+
+    #[test]
+    fn settling_drops_a_floating_stack_onto_the_floor() {
+        let config = PackingConfig::builder().settle(true).build();
+        let mut container = Container::new((20.0, 20.0, 60.0), 100.0).unwrap();
+        for (id, z, weight) in [(1, 30.0, 1.0), (2, 5.0, 3.0), (3, 18.0, 2.0)] {
+            container.placed.push(PlacedBox::new(
+                Box3D::new(id, (10.0, 10.0, 10.0), weight).unwrap(),
+                (0.0, 0.0, z),
+            ));
+        }
+
+        assert!(settle_container(&mut container, 0, &config));
+
+        let mut heights: Vec<_> = container
+            .placed
+            .iter()
+            .map(|p| (p.object.id, p.position.2))
+            .collect();
+        heights.sort_by_key(|&(id, _)| id);
+        assert_eq!(heights, vec![(1, 20.0), (2, 0.0), (3, 10.0)]);
+    }
+
+    #[test]
+    fn settling_lowers_a_touching_floating_stack_as_a_whole() {
+        let config = PackingConfig::builder().settle(true).build();
+        let mut container = Container::new((20.0, 20.0, 60.0), 100.0).unwrap();
+        for (id, z, weight) in [(1, 5.0, 2.0), (2, 15.0, 1.0)] {
+            container.placed.push(PlacedBox::new(
+                Box3D::new(id, (10.0, 10.0, 10.0), weight).unwrap(),
+                (0.0, 0.0, z),
+            ));
+        }
+
+        // Box 1 cannot drop alone without stranding box 2, which already rests on it.
+        assert!(settle_container(&mut container, 0, &config));
+
+        let heights: Vec<_> = container.placed.iter().map(|p| p.position.2).collect();
+        assert_eq!(heights, vec![0.0, 10.0]);
+        assert!(!settle_container(&mut container, 0, &config));
+
+        // A fixed object resting on the stack keeps it in place.
+        let mut pinned = Container::new((20.0, 20.0, 60.0), 100.0).unwrap();
+        for (id, z) in [(1, 15.0), (2, 5.0)] {
+            pinned.placed.push(PlacedBox::new(
+                Box3D::new(id, (10.0, 10.0, 10.0), 1.0).unwrap(),
+                (0.0, 0.0, z),
+            ));
+        }
+        assert!(!settle_container(&mut pinned, 1, &config));
+    }
+
+    #[test]
+    fn compaction_slides_loose_objects_toward_the_origin() {
+        let config = PackingConfig::builder()
+            .compact(true)
+            .balance_limit_ratio(1.0)
+            .build();
+        let mut container = Container::new((30.0, 30.0, 30.0), 100.0).unwrap();
+        for (id, position) in [
+            (1, (10.0, 10.0, 0.0)),
+            (2, (20.0, 15.0, 0.0)),
+            (3, (10.0, 10.0, 5.0)),
+            (4, (3.0, 22.0, 0.0)),
+        ] {
+            container.placed.push(PlacedBox::new(
+                Box3D::new(id, (5.0, 5.0, 5.0), 1.0).unwrap(),
+                position,
+            ));
+        }
+        let extent = |container: &Container, axis: usize| {
+            container
+                .placed
+                .iter()
+                .map(|p| axis_component(p.position, axis) + axis_component(p.object.dims, axis))
+                .fold(0.0, f64::max)
+        };
+        let (before_x, before_y) = (extent(&container, 0), extent(&container, 1));
+
+        assert!(compact_container(&mut container, 0, &config));
+
+        assert!(extent(&container, 0) < before_x);
+        assert!(extent(&container, 1) < before_y);
+        for (i, a) in container.placed.iter().enumerate() {
+            for b in &container.placed[i + 1..] {
+                assert!(
+                    !intersects(a, b),
+                    "{} overlaps {}",
+                    a.object.id,
+                    b.object.id
+                );
+            }
+            if a.position.2 > 0.0 {
+                let support = analyze_support_surface(a, &container, &config);
+                assert!(meets_support_requirements(&support, &config));
+            }
+        }
+    }
+
+    #[test]
+    fn compaction_respects_balance_hints_and_load_direction() {
+        let loose = |config: &PackingConfig| {
+            let mut container = Container::new((30.0, 30.0, 30.0), 100.0).unwrap();
+            let mut hinted = Box3D::new(2, (5.0, 5.0, 5.0), 1.0).unwrap();
+            hinted.position_hint = Some((20.0, 10.0, 0.0));
+            container.placed.push(PlacedBox::new(
+                Box3D::new(1, (5.0, 5.0, 5.0), 1.0).unwrap(),
+                (12.0, 12.0, 0.0),
+            ));
+            container
+                .placed
+                .push(PlacedBox::new(hinted, (20.0, 10.0, 0.0)));
+            compact_container(&mut container, 0, config);
+            container
+        };
+        let position = |container: &Container, id: usize| {
+            container
+                .placed
+                .iter()
+                .find(|p| p.object.id == id)
+                .unwrap()
+                .position
+        };
+
+        // A tight balance limit stops the slide into the corner.
+        let balanced = PackingConfig::builder()
+            .compact(true)
+            .balance_limit_ratio(0.3)
+            .build();
+        let container = loose(&balanced);
+        let mut rest = container.clone();
+        let last = rest.placed.pop().unwrap();
+        assert!(
+            calculate_balance_after(&rest, &last)
+                <= calculate_balance_limit(&container, &balanced) + 1e-6
+        );
+        assert_ne!(position(&container, 1), (0.0, 0.0, 0.0));
+
+        let free = PackingConfig::builder()
+            .compact(true)
+            .balance_limit_ratio(1.0);
+        let container = loose(&free.clone().build());
+        assert_eq!(position(&container, 1), (0.0, 0.0, 0.0));
+        assert_eq!(position(&container, 2), (20.0, 10.0, 0.0));
+
+        let container = loose(&free.load_direction(LoadDirection::BackToFront).build());
+        assert_eq!(position(&container, 1), (0.0, 25.0, 0.0));
+        assert_eq!(position(&container, 2), (20.0, 10.0, 0.0));
+    }
+
+    #[test]
+    fn compaction_sends_a_snapshot_with_the_final_positions() {
+        let config = PackingConfig::builder()
+            .compact(true)
+            .balance_limit_ratio(0.2)
+            .grid_step(5.0)
+            .build();
+        let objects = vec![
+            Box3D::new(1, (4.0, 5.0, 3.0), 10.0).unwrap(),
+            Box3D::new(2, (11.0, 10.0, 6.0), 9.0).unwrap(),
+            Box3D::new(3, (9.0, 8.0, 4.0), 8.0).unwrap(),
+        ];
+        let mut events = Vec::new();
+        let result = pack_objects_with_progress(
+            objects,
+            single_blueprint((30.0, 30.0, 30.0), 100.0),
+            config,
+            |event| events.push(event.clone()),
+        );
+
+        let final_positions: std::collections::HashMap<usize, (f64, f64, f64)> = result.containers
+            [0]
+        .placed
+        .iter()
+        .map(|p| (p.object.id, p.position))
+        .collect();
+        let streamed: std::collections::HashMap<usize, (f64, f64, f64)> = events
+            .iter()
+            .filter_map(|event| match event {
+                PackEvent::ObjectPlaced { id, pos, .. } => Some((*id, *pos)),
+                _ => None,
+            })
+            .collect();
+        assert_ne!(
+            streamed, final_positions,
+            "compaction should move an object"
+        );
+        let snapshot: std::collections::HashMap<usize, (f64, f64, f64)> = events
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                PackEvent::Snapshot { placed, .. } => {
+                    Some(placed.iter().map(|p| (p.id, p.pos)).collect())
+                }
+                _ => None,
+            })
+            .expect("missing snapshot after compaction");
+        assert_eq!(snapshot, final_positions);
+    }
+
+    #[test]
+    fn stricter_x_balance_limit_rejects_side_biased_load() {
+        let mut container = Container::new((100.0, 100.0, 50.0), 1000.0).unwrap();
+        container.placed.push(PlacedBox::new(
+            Box3D::new(1, (10.0, 10.0, 10.0), 10.0).unwrap(),
+            (45.0, 45.0, 0.0),
+        ));
+        // Moves the center of gravity 10 units to the side: within the combined limit, but
+        // beyond 10% of the half width.
+        let side = PlacedBox::new(
+            Box3D::new(2, (10.0, 10.0, 10.0), 10.0).unwrap(),
+            (65.0, 45.0, 0.0),
+        );
+
+        let check = |config: PackingConfig| {
+            let balance = calculate_balance_after(&container, &side);
+            let limit = calculate_balance_limit(&container, &config);
+            within_balance_limits(&container, &side, balance, limit, &config)
+        };
+
+        assert!(check(PackingConfig::default()));
+        assert!(!check(
+            PackingConfig::builder()
+                .balance_limit_ratio_per_axis(Some(0.1), None)
+                .build()
+        ));
+        assert!(check(
+            PackingConfig::builder()
+                .balance_limit_ratio_per_axis(None, Some(0.1))
+                .build()
+        ));
+    }
+
+    #[test]
+    fn reserved_weight_reduces_weight_capacity() {
+        let blueprint = ContainerBlueprint::new(0, None, (50.0, 50.0, 50.0), 100.0).unwrap();
+        let objects: Vec<Box3D> = (1..=10)
+            .map(|id| Box3D::new(id, (5.0, 5.0, 5.0), 10.0).unwrap())
+            .collect();
+
+        let plain = pack_objects(objects.clone(), vec![blueprint.clone()]);
+        assert_eq!(plain.containers.len(), 1);
+
+        let dunnage = blueprint.clone().with_reserved_weight(40.0).unwrap();
+        let reserved = pack_objects(objects, vec![dunnage]);
+        assert!(reserved.unplaced.is_empty());
+        assert_eq!(reserved.containers.len(), 2);
+        assert_eq!(reserved.containers[0].placed.len(), 6);
+        assert!(
+            reserved
+                .containers
+                .iter()
+                .all(|c| c.total_weight() <= 100.0 + 1e-9)
+        );
+        assert!((reserved.containers[0].total_weight() - 100.0).abs() < 1e-9);
+
+        assert!(blueprint.clone().with_reserved_weight(100.0).is_err());
+        assert!(blueprint.with_reserved_weight(-1.0).is_err());
+    }
+
+    #[test]
+    fn tall_box_fits_only_under_the_higher_front_ceiling() {
+        let blueprint = ContainerBlueprint::new(0, None, (100.0, 50.0, 50.0), 100.0)
+            .unwrap()
+            .with_height_profile(vec![(50.0, 20.0)])
+            .unwrap();
+        let cont = blueprint.instantiate();
+        let tall = Box3D::new(1, (30.0, 30.0, 40.0), 5.0).unwrap();
+
+        let (x, _, z) = find_stable_position(&tall, &cont, &PackingConfig::default())
+            .expect("the front section is tall enough");
+        assert!(
+            x + 30.0 <= 50.0 + 1e-9,
+            "box must stay in front of the step, got x = {x}"
+        );
+        assert_eq!(z, 0.0);
+
+        // The same box no longer fits once the low section covers the whole length.
+        let low = ContainerBlueprint::new(0, None, (100.0, 50.0, 50.0), 100.0)
+            .unwrap()
+            .with_height_profile(vec![(0.0, 20.0)])
+            .unwrap()
+            .instantiate();
+        assert_eq!(
+            find_stable_position(&tall, &low, &PackingConfig::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn incremental_packing_keeps_prior_placements() {
+        let templates = single_blueprint((30.0, 30.0, 30.0), 100.0);
+        let objects = (1..=3)
+            .map(|id| Box3D::new(id, (10.0, 10.0, 10.0), 5.0).unwrap())
+            .collect();
+        let prior = pack_objects(objects, templates.clone());
+        let before: Vec<(usize, (f64, f64, f64))> = prior.containers[0]
+            .placed
+            .iter()
+            .map(|p| (p.object.id, p.position))
+            .collect();
+
+        let new_object = Box3D::new(4, (10.0, 10.0, 10.0), 5.0).unwrap();
+        let result = pack_objects_incremental(
+            prior.containers,
+            vec![new_object],
+            templates,
+            PackingConfig::default(),
+        );
+
+        assert!(result.unplaced.is_empty());
+        assert_eq!(result.containers.len(), 1);
+        let cont = &result.containers[0];
+        assert_eq!(cont.placed.len(), 4);
+        for (id, position) in before {
+            let placed = cont.placed.iter().find(|p| p.object.id == id).unwrap();
+            assert_eq!(placed.position, position, "object {id} was moved");
+        }
+        assert_eq!(result.container_diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn reserved_space_forces_object_elsewhere() {
+        let mut blueprint = ContainerBlueprint::new(0, None, (20.0, 10.0, 10.0), 100.0).unwrap();
+        let object = Box3D::new(1, (10.0, 10.0, 10.0), 5.0).unwrap();
+
+        let free = blueprint.instantiate();
+        assert_eq!(
+            find_stable_position(&object, &free, &PackingConfig::default()),
+            Some((0.0, 0.0, 0.0))
+        );
+
+        blueprint.reserved.push(PlacedBox::new(
+            Box3D::reserved_space(0, (10.0, 10.0, 10.0)).unwrap(),
+            (0.0, 0.0, 0.0),
+        ));
+        let reserved = blueprint.instantiate();
+        assert_eq!(
+            find_stable_position(&object, &reserved, &PackingConfig::default()),
+            Some((10.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn streamed_diagnostics_report_volume_utilization() {
+        let objects = vec![
+            Box3D::new(1, (10.0, 10.0, 5.0), 2.0).unwrap(),
+            Box3D::new(2, (10.0, 10.0, 5.0), 1.0).unwrap(),
+        ];
+        let mut streamed = Vec::new();
+        pack_objects_with_progress(
+            objects,
+            single_blueprint((10.0, 10.0, 20.0), 100.0),
+            PackingConfig::default(),
+            |event| {
+                if let PackEvent::ContainerDiagnostics { diagnostics, .. } = event {
+                    streamed.push(diagnostics.clone());
+                }
+            },
+        );
+
+        assert_eq!(streamed.len(), 2);
+        let last = streamed.last().unwrap();
+        assert!((last.total_volume - 2000.0).abs() < 1e-9);
+        assert!((last.used_volume - 1000.0).abs() < 1e-9);
+        assert!((last.volume_utilization_percent - 50.0).abs() < 1e-9);
+        assert!((streamed[0].volume_utilization_percent - 25.0).abs() < 1e-9);
+    }
 }
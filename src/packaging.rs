@@ -15,7 +15,7 @@
 //! inputs so that a `void_volume` is never negative and percentages always fall in `0.0..=100.0`,
 //! regardless of how the caller obtained the raw volumes.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 /// Void-space / packaging-material requirement for a single container.
@@ -23,7 +23,7 @@ use utoipa::ToSchema;
 /// The four figures are derived from just two inputs — the container's interior volume and the
 /// volume occupied by the packed objects — but exposing them explicitly keeps every consumer
 /// (HTTP clients, the CLI, the live visualization) free of duplicated arithmetic (DRY).
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, ToSchema)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct PackagingFill {
     /// Total interior volume of the container (cubic units).
     pub container_volume: f64,
@@ -81,7 +81,7 @@ impl Default for PackagingFill {
 ///
 /// `total_void_volume` is the headline figure: the total amount of cushioning material a shipment
 /// needs across all of its containers.
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, ToSchema)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct PackagingSummary {
     /// Combined interior volume of all opened containers (cubic units).
     pub total_container_volume: f64,
@@ -0,0 +1,169 @@
+//! Token-bucket rate limiting keyed by client IP.
+//!
+//! Each client gets a bucket holding up to `requests_per_minute` tokens that refills continuously
+//! at `requests_per_minute / 60` tokens per second. A request consumes one token; an empty bucket
+//! means the client has to wait until the next token becomes available.
+//!
+//! Buckets live in a mutex-guarded map. Idle buckets (refilled to capacity) are pruned lazily at
+//! most once per [`CLEANUP_INTERVAL`], so the map cannot grow without bound under churn.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two sweeps over the bucket map.
+pub const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    buckets: HashMap<IpAddr, Bucket>,
+    last_cleanup: Instant,
+}
+
+/// Per-client token-bucket limiter.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_second: f64,
+    state: Mutex<LimiterState>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `requests_per_minute` requests per client.
+    ///
+    /// Returns `None` for `0`, which disables rate limiting.
+    pub fn per_minute(requests_per_minute: usize) -> Option<Self> {
+        if requests_per_minute == 0 {
+            return None;
+        }
+
+        let capacity = requests_per_minute as f64;
+        Some(Self {
+            capacity,
+            refill_per_second: capacity / 60.0,
+            state: Mutex::new(LimiterState {
+                buckets: HashMap::new(),
+                last_cleanup: Instant::now(),
+            }),
+        })
+    }
+
+    /// Consumes a token for `client`.
+    ///
+    /// Returns `Ok(())` if the request may proceed, or `Err(retry_after)` with the time until the
+    /// next token is available.
+    pub fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if now.saturating_duration_since(state.last_cleanup) >= CLEANUP_INTERVAL {
+            let (capacity, rate) = (self.capacity, self.refill_per_second);
+            state
+                .buckets
+                .retain(|_, bucket| refilled(bucket, now, capacity, rate) < capacity);
+            state.last_cleanup = now;
+        }
+
+        let bucket = state.buckets.entry(client).or_insert(Bucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+        bucket.tokens = refilled(bucket, now, self.capacity, self.refill_per_second);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err(Duration::from_secs_f64(missing / self.refill_per_second))
+        }
+    }
+
+    /// Number of tracked client buckets.
+    pub fn tracked_clients(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .buckets
+            .len()
+    }
+}
+
+fn refilled(bucket: &Bucket, now: Instant, capacity: f64, refill_per_second: f64) -> f64 {
+    let elapsed = now
+        .saturating_duration_since(bucket.last_refill)
+        .as_secs_f64();
+    (bucket.tokens + elapsed * refill_per_second).min(capacity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+    #[test]
+    fn zero_disables_the_limiter() {
+        assert!(RateLimiter::per_minute(0).is_none());
+    }
+
+    #[test]
+    fn bucket_empties_and_refills_over_time() {
+        let limiter = RateLimiter::per_minute(2).unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.check_at(CLIENT, start).is_ok());
+        assert!(limiter.check_at(CLIENT, start).is_ok());
+        let retry_after = limiter.check_at(CLIENT, start).unwrap_err();
+        assert!((retry_after.as_secs_f64() - 30.0).abs() < 1e-6);
+
+        // Two requests per minute refill one token every 30 seconds.
+        assert!(
+            limiter
+                .check_at(CLIENT, start + Duration::from_secs(30))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn clients_are_limited_independently() {
+        let limiter = RateLimiter::per_minute(1).unwrap();
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let now = Instant::now();
+
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+        assert!(limiter.check_at(CLIENT, now).is_err());
+        assert!(limiter.check_at(other, now).is_ok());
+    }
+
+    #[test]
+    fn idle_buckets_are_pruned_periodically() {
+        let limiter = RateLimiter::per_minute(60).unwrap();
+        let now = Instant::now();
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+        assert_eq!(limiter.tracked_clients(), 1);
+
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        assert!(limiter.check_at(other, now + CLEANUP_INTERVAL).is_ok());
+        assert_eq!(
+            limiter.tracked_clients(),
+            1,
+            "the idle bucket should be pruned"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Bounded in-memory cache of recent `/pack` responses.
+//!
+//! Interactive clients often resend identical requests. The cache keeps the last `capacity`
+//! serialized responses keyed by the normalized request and evicts the oldest entry once it is
+//! full. Keys hold the complete normalized request, so distinct requests never share an entry.
+//! Entries live only in memory, so nothing has to be flushed on shutdown.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, Value>,
+    /// Keys in insertion order, oldest first.
+    order: VecDeque<String>,
+}
+
+/// FIFO-evicting response cache shared by all handlers.
+#[derive(Debug)]
+pub struct ResultCache {
+    capacity: usize,
+    max_entry_bytes: usize,
+    state: Mutex<CacheState>,
+}
+
+impl ResultCache {
+    /// Largest entry (normalized request plus serialized response) kept by default, in bytes.
+    pub const DEFAULT_MAX_ENTRY_BYTES: usize = 1024 * 1024;
+
+    /// Creates a cache holding up to `capacity` responses of at most
+    /// [`Self::DEFAULT_MAX_ENTRY_BYTES`] each.
+    ///
+    /// Returns `None` for `0`, which disables caching.
+    pub fn with_capacity(capacity: usize) -> Option<Self> {
+        Self::with_limits(capacity, Self::DEFAULT_MAX_ENTRY_BYTES)
+    }
+
+    /// Creates a cache holding up to `capacity` responses of at most `max_entry_bytes` each.
+    ///
+    /// Returns `None` for a capacity of `0`, which disables caching.
+    pub fn with_limits(capacity: usize, max_entry_bytes: usize) -> Option<Self> {
+        if capacity == 0 {
+            return None;
+        }
+        Some(Self {
+            capacity,
+            max_entry_bytes,
+            state: Mutex::new(CacheState::default()),
+        })
+    }
+
+    /// Derives the cache key of a request body and its output-relevant options.
+    ///
+    /// The body is normalized by re-serializing the parsed JSON, so whitespace and key order do
+    /// not matter. Returns `None` if the body is not valid JSON.
+    pub fn key(body: &[u8], options: impl Serialize) -> Option<String> {
+        let normalized = serde_json::from_slice::<Value>(body).ok()?;
+        serde_json::to_string(&(options, normalized)).ok()
+    }
+
+    /// Returns a copy of the response stored under `key`.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.lock().entries.get(key).cloned()
+    }
+
+    /// Stores `response` under `key`, evicting the oldest entries beyond the capacity.
+    ///
+    /// Entries larger than the per-entry limit are not stored.
+    pub fn insert(&self, key: String, response: Value) {
+        if key.len() + response.to_string().len() > self.max_entry_bytes {
+            return;
+        }
+        let mut state = self.lock();
+        if state.entries.insert(key.clone(), response).is_none() {
+            state.order.push_back(key);
+        }
+        while state.order.len() > self.capacity {
+            if let Some(oldest) = state.order.pop_front() {
+                state.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Number of cached responses.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns `true` if no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn zero_disables_the_cache() {
+        assert!(ResultCache::with_capacity(0).is_none());
+    }
+
+    #[test]
+    fn keys_ignore_formatting_but_not_options() {
+        let compact = ResultCache::key(br#"{"a":1,"b":[1,2]}"#, "array").unwrap();
+        let spaced = ResultCache::key(b"{ \"b\": [1, 2],\n \"a\": 1 }", "array").unwrap();
+        assert_eq!(compact, spaced);
+        assert_ne!(
+            compact,
+            ResultCache::key(br#"{"a":1,"b":[1,2]}"#, "object").unwrap()
+        );
+        assert!(ResultCache::key(b"{ not json", "array").is_none());
+    }
+
+    #[test]
+    fn oldest_entries_are_evicted_beyond_the_capacity() {
+        let cache = ResultCache::with_capacity(2).unwrap();
+        cache.insert("1".to_string(), json!(1));
+        cache.insert("2".to_string(), json!(2));
+        // Replacing an entry keeps its age.
+        cache.insert("1".to_string(), json!("one"));
+        cache.insert("3".to_string(), json!(3));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("1"), None);
+        assert_eq!(cache.get("2"), Some(json!(2)));
+        assert_eq!(cache.get("3"), Some(json!(3)));
+    }
+
+    #[test]
+    fn oversized_entries_are_not_stored() {
+        let cache = ResultCache::with_limits(2, 16).unwrap();
+        cache.insert("small".to_string(), json!([1, 2]));
+        cache.insert("large".to_string(), json!("a response beyond the limit"));
+
+        assert_eq!(cache.get("small"), Some(json!([1, 2])));
+        assert_eq!(cache.get("large"), None);
+        assert_eq!(cache.len(), 1);
+    }
+}
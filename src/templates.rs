@@ -0,0 +1,165 @@
+//! Server-side library of named container templates.
+//!
+//! Larger deployments keep their container specs as files instead of repeating them in every
+//! request. At startup the server can scan a directory for `*.json` files, each holding one
+//! [`ContainerRequest`]; requests then reference these templates by name via `template_names`.
+//!
+//! Files that cannot be read, parsed, or validated are logged and skipped so a single broken
+//! spec never prevents the server from starting.
+
+use std::fs;
+use std::path::Path;
+
+use crate::api::ContainerRequest;
+
+/// Catalog of container templates loaded from disk, keyed by name.
+#[derive(Clone, Debug, Default)]
+pub struct TemplateLibrary {
+    templates: Vec<ContainerRequest>,
+}
+
+impl TemplateLibrary {
+    /// Loads every `*.json` file in `dir`, sorted by file name.
+    ///
+    /// Names are trimmed, and templates without a `name` are named after their file stem.
+    /// Invalid files, blank names and duplicate names are reported on stderr and skipped; a
+    /// missing directory yields an empty library.
+    pub fn load_dir(dir: &Path) -> Self {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                eprintln!(
+                    "⚠️ Could not read template directory {}: {}",
+                    dir.display(),
+                    err
+                );
+                return Self::default();
+            }
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json") && path.is_file())
+            .collect();
+        paths.sort();
+
+        let mut library = Self::default();
+        for path in paths {
+            match load_template_file(&path) {
+                Ok(template) => {
+                    let name = template.name.clone().unwrap_or_default();
+                    if library.get(&name).is_some() {
+                        eprintln!(
+                            "⚠️ Skipping template {}: name '{}' is already defined",
+                            path.display(),
+                            name
+                        );
+                        continue;
+                    }
+                    library.templates.push(template);
+                }
+                Err(reason) => {
+                    eprintln!("⚠️ Skipping template {}: {}", path.display(), reason);
+                }
+            }
+        }
+        library
+    }
+
+    /// Looks up a template by its exact name.
+    pub fn get(&self, name: &str) -> Option<&ContainerRequest> {
+        self.templates
+            .iter()
+            .find(|template| template.name.as_deref() == Some(name))
+    }
+
+    /// All templates in load order.
+    pub fn templates(&self) -> &[ContainerRequest] {
+        &self.templates
+    }
+
+    /// Number of loaded templates.
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Returns `true` if no template was loaded.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+fn load_template_file(path: &Path) -> Result<ContainerRequest, String> {
+    let raw = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let mut template: ContainerRequest =
+        serde_json::from_str(&raw).map_err(|err| format!("invalid JSON: {err}"))?;
+
+    template.name = match template.name.take() {
+        Some(name) if name.trim().is_empty() => return Err("name must not be empty".to_string()),
+        Some(name) => Some(name.trim().to_string()),
+        None => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned()),
+    };
+
+    template
+        .clone()
+        .into_blueprint(0, false)
+        .map_err(|err| err.to_string())?;
+    Ok(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_files_are_skipped_and_unnamed_templates_use_the_file_stem() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("pallet.json"),
+            r#"{"dims": [120.0, 80.0, 100.0], "max_weight": 500.0}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("broken.json"), "{ not json").unwrap();
+        fs::write(
+            dir.path().join("negative.json"),
+            r#"{"dims": [-1.0, 80.0, 100.0], "max_weight": 500.0}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let library = TemplateLibrary::load_dir(dir.path());
+
+        assert_eq!(library.len(), 1);
+        let pallet = library.get("pallet").expect("pallet template");
+        assert_eq!(pallet.dims, (120.0, 80.0, 100.0));
+    }
+
+    #[test]
+    fn names_are_trimmed_and_blank_names_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.json"),
+            r#"{"name": "  Euro pallet ", "dims": [120.0, 80.0, 100.0], "max_weight": 500.0}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b.json"),
+            r#"{"name": "   ", "dims": [120.0, 80.0, 100.0], "max_weight": 500.0}"#,
+        )
+        .unwrap();
+
+        let library = TemplateLibrary::load_dir(dir.path());
+
+        assert_eq!(library.len(), 1);
+        assert!(library.get("Euro pallet").is_some());
+    }
+
+    #[test]
+    fn missing_directory_yields_an_empty_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = TemplateLibrary::load_dir(&dir.path().join("missing"));
+        assert!(library.is_empty());
+    }
+}
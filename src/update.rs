@@ -566,6 +566,77 @@ fn format_wait(duration: Duration) -> String {
     parts.join(" ")
 }
 
+/// Environment variable naming the directory used when the running executable cannot be located.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+const FALLBACK_INSTALL_DIR_VAR: &str = "SORT_IT_NOW_UPDATE_FALLBACK_DIR";
+
+/// Target directory for an update.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+#[derive(Debug, PartialEq, Eq)]
+enum InstallDir {
+    /// Directory of the running executable, which the update replaces.
+    NextToExecutable(PathBuf),
+    /// The running executable could not be located; the update is only deposited here.
+    Fallback { dir: PathBuf, reason: String },
+}
+
+/// Resolves where an update is installed.
+///
+/// Uses the directory of `current_exe` when available. Otherwise (e.g. in sandboxes where
+/// `current_exe` fails) falls back to `configured_fallback` or, if unset, a fixed directory
+/// below the system temp directory.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn resolve_install_dir(
+    current_exe: std::io::Result<PathBuf>,
+    configured_fallback: Option<PathBuf>,
+) -> InstallDir {
+    let reason = match current_exe {
+        Ok(exe) => match exe.parent() {
+            Some(dir) => return InstallDir::NextToExecutable(dir.to_path_buf()),
+            None => format!("{} has no parent directory", exe.display()),
+        },
+        Err(err) => err.to_string(),
+    };
+    let dir =
+        configured_fallback.unwrap_or_else(|| std::env::temp_dir().join("sort-it-now-update"));
+    InstallDir::Fallback { dir, reason }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn current_install_dir() -> InstallDir {
+    let configured = std::env::var_os(FALLBACK_INSTALL_DIR_VAR)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    resolve_install_dir(std::env::current_exe(), configured)
+}
+
+/// Copies the new binary into the fallback directory and tells the user how to finish.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+async fn deposit_in_fallback_dir(
+    binary_path: &Path,
+    dir: &Path,
+    reason: &str,
+    tag_name: &str,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    fs::create_dir_all(dir).await?;
+    let file_name = binary_path
+        .file_name()
+        .ok_or("Extracted binary has no file name")?;
+    let target_path = dir.join(file_name);
+    fs::copy(binary_path, &target_path).await?;
+    println!(
+        "⚠️ Could not locate the running application ({}); it was not replaced.",
+        reason
+    );
+    println!(
+        "💡 Update {} was saved as {}. Move it over your installed binary to finish the update (set {} to choose this directory).",
+        tag_name,
+        target_path.display(),
+        FALLBACK_INSTALL_DIR_VAR
+    );
+    Ok(target_path)
+}
+
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 async fn install_on_unix(
     archive_path: &Path,
@@ -593,11 +664,17 @@ async fn install_on_unix(
         return Err("Binary sort_it_now was not found in the extracted package".into());
     }
 
-    let current_exe = std::env::current_exe()?;
-    let install_dir = current_exe
-        .parent()
-        .ok_or("Could not determine installation directory")?
-        .to_path_buf();
+    let install_dir = match current_install_dir() {
+        InstallDir::NextToExecutable(dir) => dir,
+        InstallDir::Fallback { dir, reason } => {
+            let target_path =
+                deposit_in_fallback_dir(&binary_path, &dir, &reason, tag_name).await?;
+            let mut permissions = fs::metadata(&target_path).await?.permissions();
+            permissions.set_mode(0o755);
+            fs::set_permissions(&target_path, permissions).await?;
+            return Ok(());
+        }
+    };
 
     let staged_path = install_dir.join("sort_it_now.tmp");
     let final_path = install_dir.join("sort_it_now");
@@ -693,11 +770,13 @@ async fn install_on_windows(
         return Err("Binary sort_it_now.exe was not found in the extracted package".into());
     }
 
-    let current_exe = std::env::current_exe()?;
-    let install_dir = current_exe
-        .parent()
-        .ok_or("Could not determine installation directory")?
-        .to_path_buf();
+    let install_dir = match current_install_dir() {
+        InstallDir::NextToExecutable(dir) => dir,
+        InstallDir::Fallback { dir, reason } => {
+            deposit_in_fallback_dir(&binary_path, &dir, &reason, tag_name).await?;
+            return Ok(());
+        }
+    };
     let target_path = install_dir.join("sort_it_now.exe");
 
     match fs::copy(&binary_path, &target_path).await {
@@ -788,3 +867,56 @@ fn ensure_windows_path(install_dir: &Path) -> Result<bool, std::io::Error> {
     env.set_value("Path", &new_path)?;
     Ok(true)
 }
+
+#[cfg(all(
+    test,
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_dir_is_next_to_the_running_executable() {
+        let exe = PathBuf::from("/opt/sort-it-now/sort_it_now");
+        assert_eq!(
+            resolve_install_dir(Ok(exe), Some(PathBuf::from("/unused"))),
+            InstallDir::NextToExecutable(PathBuf::from("/opt/sort-it-now"))
+        );
+    }
+
+    #[test]
+    fn install_dir_falls_back_when_current_exe_fails() {
+        let failure = || Err(std::io::Error::other("sandboxed"));
+
+        match resolve_install_dir(failure(), Some(PathBuf::from("/srv/updates"))) {
+            InstallDir::Fallback { dir, reason } => {
+                assert_eq!(dir, PathBuf::from("/srv/updates"));
+                assert!(reason.contains("sandboxed"));
+            }
+            other => panic!("expected fallback, got {other:?}"),
+        }
+        assert_eq!(
+            resolve_install_dir(failure(), None),
+            InstallDir::Fallback {
+                dir: std::env::temp_dir().join("sort-it-now-update"),
+                reason: "sandboxed".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_deposits_the_binary_in_the_fallback_dir() {
+        let source = tempfile::tempdir().unwrap();
+        let binary_path = source.path().join("sort_it_now");
+        std::fs::write(&binary_path, b"binary").unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let dir = target.path().join("nested");
+
+        let deposited = deposit_in_fallback_dir(&binary_path, &dir, "sandboxed", "v9.9.9")
+            .await
+            .unwrap();
+
+        assert_eq!(deposited, dir.join("sort_it_now"));
+        assert_eq!(std::fs::read(deposited).unwrap(), b"binary");
+    }
+}
@@ -8,8 +8,12 @@ use axum::Router;
 use axum::body::{Body, to_bytes};
 use axum::http::{Request, StatusCode, header};
 use serde_json::{Value, json};
-use sort_it_now::api::build_router;
+use sort_it_now::api::{
+    PackRequest, build_router, build_router_with_cache, build_router_with_rate_limit,
+    build_router_with_templates,
+};
 use sort_it_now::config::{OptimizerConfig, RequestLimits};
+use sort_it_now::templates::TemplateLibrary;
 use tower::ServiceExt; // for `oneshot`
 
 /// Builds a router with default optimizer configuration and the given request limits.
@@ -41,6 +45,30 @@ async fn get_json(app: Router, uri: &str) -> (StatusCode, Value) {
     (status, value)
 }
 
+/// Posts `body` to `/pack_stream` and returns the JSON payload of every SSE `data:` line.
+async fn post_sse_events(app: Router, body: String) -> Vec<Value> {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/pack_stream")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .expect("request builds"),
+        )
+        .await
+        .expect("router responds");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body collected");
+    String::from_utf8_lossy(&body)
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter_map(|data| serde_json::from_str(data).ok())
+        .collect()
+}
+
 /// Sends a POST request with a raw body and returns the status plus parsed JSON body.
 async fn post_json(app: Router, uri: &str, body: String) -> (StatusCode, Value) {
     let response = app
@@ -77,6 +105,41 @@ async fn version_endpoint_reports_build_metadata() {
     assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
 }
 
+#[tokio::test]
+async fn assets_endpoint_lists_embedded_web_ui() {
+    let (status, body) = get_json(router(), "/assets").await;
+    assert_eq!(status, StatusCode::OK);
+    let assets = body["assets"].as_array().expect("asset list");
+    assert!(assets.iter().any(|asset| asset == "index.html"));
+}
+
+#[tokio::test]
+async fn example_endpoint_returns_packable_request() {
+    let (status, example) = get_json(router(), "/example").await;
+    assert_eq!(status, StatusCode::OK);
+    serde_json::from_value::<PackRequest>(example.clone()).expect("example is a PackRequest");
+
+    let (_, openapi) = get_json(router(), "/docs/openapi.json").await;
+    assert_eq!(
+        openapi["components"]["schemas"]["PackRequest"]["example"],
+        example
+    );
+
+    let (status, body) = post_json(router(), "/pack", example.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], true);
+}
+
+#[tokio::test]
+async fn pack_request_schema_is_standalone_json_schema() {
+    let (status, schema) = get_json(router(), "/docs/schema/pack_request").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(schema["properties"]["containers"].is_object());
+    assert!(schema["properties"]["objects"].is_object());
+    assert!(schema["$defs"]["ContainerRequest"].is_object());
+    assert!(!schema.to_string().contains("#/components/schemas/"));
+}
+
 #[tokio::test]
 async fn config_endpoint_exposes_defaults() {
     let (status, body) = get_json(router(), "/config").await;
@@ -136,6 +199,25 @@ async fn pack_endpoint_returns_utilization_metrics() {
     );
 }
 
+#[tokio::test]
+async fn pack_endpoint_reports_status() {
+    let container = json!({ "dims": [10.0, 10.0, 10.0], "max_weight": 100.0 });
+
+    let payload = json!({
+        "containers": [container],
+        "objects": [{ "id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0 }]
+    });
+    let (status, body) = post_json(router(), "/pack", payload.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+
+    let payload = json!({ "containers": [container], "objects": [] });
+    let (status, body) = post_json(router(), "/pack", payload.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "no_objects");
+    assert_eq!(body["is_complete"], true);
+}
+
 #[tokio::test]
 async fn pack_endpoint_rejects_invalid_json() {
     let (status, body) = post_json(router(), "/pack", "not-json".to_string()).await;
@@ -191,6 +273,1387 @@ async fn pack_endpoint_reports_unplaced_oversized_object() {
     );
 }
 
+#[tokio::test]
+async fn pack_endpoint_passes_category_and_color_through() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0, "category": "fragile", "color": "#ff8800"},
+            {"id": 2, "dims": [20.0, 20.0, 20.0], "weight": 1.0, "category": "bulky", "color": "#0088ff"},
+            {"id": 3, "dims": [5.0, 5.0, 5.0], "weight": 1.0}
+        ]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let placed = body["results"][0]["placed"].as_array().unwrap();
+    let tagged = placed.iter().find(|o| o["id"] == 1).unwrap();
+    assert_eq!(tagged["category"], "fragile");
+    assert_eq!(tagged["color"], "#ff8800");
+    let untagged = placed.iter().find(|o| o["id"] == 3).unwrap();
+    assert!(untagged.get("category").is_none());
+    assert!(untagged.get("color").is_none());
+
+    assert_eq!(body["unplaced"][0]["category"], "bulky");
+    assert_eq!(body["unplaced"][0]["color"], "#0088ff");
+}
+
+#[tokio::test]
+async fn pack_endpoint_incremental_mode_keeps_prior_layout() {
+    let containers = json!([{"dims": [30.0, 30.0, 30.0], "max_weight": 100.0}]);
+    let first = json!({
+        "containers": containers,
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 10.0], "weight": 5.0},
+            {"id": 2, "dims": [10.0, 10.0, 10.0], "weight": 5.0},
+            {"id": 3, "dims": [10.0, 10.0, 10.0], "weight": 5.0}
+        ]
+    })
+    .to_string();
+    let (status, prior) = post_json(router(), "/pack", first).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let second = json!({
+        "containers": containers,
+        "objects": [{"id": 4, "dims": [10.0, 10.0, 10.0], "weight": 5.0}],
+        "prior_result": prior
+    })
+    .to_string();
+    let (status, body) = post_json(router(), "/pack", second).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], true);
+    assert_eq!(body["results"].as_array().unwrap().len(), 1);
+
+    let placed = body["results"][0]["placed"].as_array().unwrap();
+    assert_eq!(placed.len(), 4);
+    for old in prior["results"][0]["placed"].as_array().unwrap() {
+        let now = placed.iter().find(|p| p["id"] == old["id"]).unwrap();
+        assert_eq!(now["pos"], old["pos"], "object {} was moved", old["id"]);
+    }
+}
+
+#[tokio::test]
+async fn pack_endpoint_rejects_non_json_content_type() {
+    let response = router()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/pack")
+                .header(header::CONTENT_TYPE, "text/plain")
+                .body(Body::from("{}"))
+                .expect("request builds"),
+        )
+        .await
+        .expect("router responds");
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"], "Unsupported media type");
+    assert!(body["details"].as_str().unwrap().contains("text/plain"));
+}
+
+#[tokio::test]
+async fn pack_endpoint_avoids_reserved_space() {
+    let payload = json!({
+        "containers": [{"dims": [20.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [{"id": 1, "dims": [10.0, 10.0, 10.0], "weight": 5.0}],
+        "reserved": [{"dims": [10.0, 10.0, 10.0], "pos": [0.0, 0.0, 0.0]}]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    let placed = body["results"][0]["placed"].as_array().unwrap();
+    assert_eq!(
+        placed.len(),
+        1,
+        "reserved blocks must not appear as packed objects"
+    );
+    assert_eq!(placed[0]["pos"], json!([10.0, 0.0, 0.0]));
+}
+
+#[tokio::test]
+async fn pack_endpoint_rejects_reserved_space_outside_containers() {
+    let payload = json!({
+        "containers": [{"dims": [20.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [{"id": 1, "dims": [10.0, 10.0, 10.0], "weight": 5.0}],
+        "reserved": [{"dims": [10.0, 10.0, 10.0], "pos": [15.0, 0.0, 0.0]}]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(
+        body["details"]
+            .as_str()
+            .unwrap()
+            .contains("does not lie within any container")
+    );
+}
+
+#[tokio::test]
+async fn pack_endpoint_rate_limits_per_client_ip() {
+    let app = build_router_with_rate_limit(OptimizerConfig::default(), RequestLimits::default(), 2);
+    let payload = json!({
+        "containers": [{ "dims": [10.0, 10.0, 10.0], "max_weight": 100.0 }],
+        "objects": [{ "id": 1, "dims": [2.0, 2.0, 2.0], "weight": 1.0 }]
+    })
+    .to_string();
+    let send = |client: &'static str| {
+        let app = app.clone();
+        let payload = payload.clone();
+        async move {
+            app.oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/pack")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header("x-forwarded-for", client)
+                    .body(Body::from(payload))
+                    .expect("request builds"),
+            )
+            .await
+            .expect("router responds")
+        }
+    };
+
+    assert_eq!(send("203.0.113.7").await.status(), StatusCode::OK);
+    assert_eq!(send("203.0.113.7").await.status(), StatusCode::OK);
+
+    let limited = send("203.0.113.7").await;
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = limited
+        .headers()
+        .get(header::RETRY_AFTER)
+        .expect("Retry-After header present")
+        .to_str()
+        .expect("header is ASCII")
+        .parse()
+        .expect("header is a number of seconds");
+    assert!(retry_after >= 1);
+
+    // Other clients keep their own budget, and read-only endpoints are never limited.
+    assert_eq!(send("198.51.100.1").await.status(), StatusCode::OK);
+    let (status, _) = get_json(app.clone(), "/health").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn pack_endpoint_normalizes_pound_weights() {
+    let request = |object_weight: f64| {
+        json!({
+            "containers": [{ "dims": [50.0, 50.0, 50.0], "max_weight": 220.0 }],
+            "objects": [{ "id": 1, "dims": [10.0, 10.0, 10.0], "weight": object_weight }],
+            "weight_unit": "lb"
+        })
+        .to_string()
+    };
+
+    // 220 lb on a 220 lb limit sits exactly at the boundary and still fits.
+    let (status, body) = post_json(router(), "/pack", request(220.0)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["placed"].as_array().unwrap().len(), 1);
+    let reported = body["results"][0]["total_weight"].as_f64().unwrap();
+    assert!((reported - 99.790_321_4).abs() < 1e-6, "got {reported}");
+
+    let (status, body) = post_json(router(), "/pack", request(221.0)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["unplaced"].as_array().unwrap().len(), 1);
+
+    let mut invalid: Value = serde_json::from_str(&request(1.0)).unwrap();
+    invalid["weight_unit"] = json!("stone");
+    let (status, _) = post_json(router(), "/pack", invalid.to_string()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn fits_endpoint_reports_position_or_reason() {
+    let container = json!({ "dims": [20.0, 20.0, 20.0], "max_weight": 50.0 });
+
+    let (status, body) = post_json(
+        router(),
+        "/fits",
+        json!({
+            "container": container,
+            "object": { "id": 42, "dims": [10.0, 10.0, 10.0], "weight": 5.0 }
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["fits"], true);
+    let position = body["position"].as_array().expect("position reported");
+    assert_eq!(position.len(), 3);
+    assert_eq!(position[2], 0.0);
+    assert_eq!(body["dims"], json!([10.0, 10.0, 10.0]));
+    assert!(body.get("reason_code").is_none());
+
+    let (status, body) = post_json(
+        router(),
+        "/fits",
+        json!({
+            "container": container,
+            "object": { "id": 43, "dims": [30.0, 10.0, 10.0], "weight": 5.0 }
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["fits"], false);
+    assert_eq!(body["reason_code"], "dimensions_exceed_container");
+    assert!(body.get("position").is_none());
+}
+
+#[tokio::test]
+async fn pack_endpoint_packs_into_interior_when_walls_are_given() {
+    let request = |wall_thickness: Option<f64>| {
+        json!({
+            "containers": [{
+                "dims": [20.0, 20.0, 10.0],
+                "max_weight": 1000.0,
+                "wall_thickness": wall_thickness
+            }],
+            "objects": (1..=8)
+                .map(|id| json!({ "id": id, "dims": [10.0, 10.0, 5.0], "weight": 1.0 }))
+                .collect::<Vec<_>>(),
+            "allow_rotations": false
+        })
+        .to_string()
+    };
+
+    let (status, ignored) = post_json(router(), "/pack", request(None)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, walled) = post_json(router(), "/pack", request(Some(0.5))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let first_container_count =
+        |body: &Value| body["results"][0]["placed"].as_array().unwrap().len();
+    assert!(
+        first_container_count(&walled) < first_container_count(&ignored),
+        "walls should reduce how many boxes fit into one container"
+    );
+    assert_eq!(walled["results"][0]["dims"], json!([19.0, 19.0, 9.0]));
+    assert_eq!(
+        walled["results"][0]["outer_dims"],
+        json!([20.0, 20.0, 10.0])
+    );
+    assert_eq!(
+        ignored["results"][0]["outer_dims"],
+        json!([20.0, 20.0, 10.0])
+    );
+
+    let (status, _) = post_json(router(), "/pack", request(Some(5.0))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn pack_ws_streams_events_until_finished() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    // WebSocket upgrades need a real connection, so this test binds an ephemeral port.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("listener binds");
+    let addr = listener.local_addr().expect("local address");
+    tokio::spawn(async move {
+        axum::serve(listener, router()).await.expect("server runs");
+    });
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/pack_ws"))
+        .await
+        .expect("websocket connects");
+    let request = json!({
+        "containers": [{ "dims": [10.0, 10.0, 10.0], "max_weight": 100.0 }],
+        "objects": [{ "id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0 }]
+    });
+    socket
+        .send(Message::text(request.to_string()))
+        .await
+        .expect("request sent");
+
+    let mut types = Vec::new();
+    while let Some(message) = socket.next().await {
+        match message.expect("frame received") {
+            Message::Text(text) => {
+                let event: Value = serde_json::from_str(&text).expect("event is JSON");
+                types.push(event["type"].as_str().unwrap_or_default().to_string());
+            }
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+
+    assert!(
+        types.contains(&"ObjectPlaced".to_string()),
+        "events: {types:?}"
+    );
+    assert_eq!(types.last().map(String::as_str), Some("Finished"));
+}
+
+#[tokio::test]
+async fn pack_endpoint_resolves_rotation_from_tags() {
+    // Standing upright, the objects are too tall for the container; only rotated ones fit.
+    let payload = json!({
+        "containers": [{ "dims": [20.0, 20.0, 5.0], "max_weight": 100.0 }],
+        "objects": [
+            { "id": 1, "dims": [4.0, 4.0, 8.0], "weight": 1.0, "tags": ["rigid"] },
+            { "id": 2, "dims": [4.0, 4.0, 8.0], "weight": 1.0, "tags": ["liquid"] },
+            { "id": 3, "dims": [4.0, 4.0, 8.0], "weight": 1.0, "tags": ["rigid"], "allow_rotation": false }
+        ],
+        "rotate_tags": ["rigid"],
+        "no_rotate_tags": ["liquid"]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let placed = body["results"][0]["placed"].as_array().unwrap();
+    assert_eq!(placed.len(), 1);
+    assert_eq!(placed[0]["id"], 1);
+    assert!(placed[0]["dims"][2].as_f64().unwrap() <= 5.0);
+
+    // The explicit per-object setting wins over the matching tag rule.
+    let mut unplaced_ids: Vec<u64> = body["unplaced"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["id"].as_u64().unwrap())
+        .collect();
+    unplaced_ids.sort_unstable();
+    assert_eq!(unplaced_ids, vec![2, 3]);
+}
+
+#[tokio::test]
+async fn pack_endpoint_rounds_output_geometry_when_requested() {
+    let request = |round_decimals: Option<u32>| {
+        json!({
+            "containers": [{ "dims": [10.0, 10.0, 10.0], "max_weight": 100.0 }],
+            "objects": (1..=4)
+                .map(|id| json!({ "id": id, "dims": [3.33333, 3.33333, 3.33333], "weight": 1.0 }))
+                .collect::<Vec<_>>(),
+            "round_decimals": round_decimals
+        })
+        .to_string()
+    };
+    let is_rounded = |value: &Value| {
+        let scaled = value.as_f64().unwrap() * 100.0;
+        (scaled - scaled.round()).abs() < 1e-6
+    };
+
+    let (status, raw) = post_json(router(), "/pack", request(None)).await;
+    assert_eq!(status, StatusCode::OK);
+    let raw_placed = raw["results"][0]["placed"].as_array().unwrap();
+    assert!(raw_placed.iter().any(|o| !is_rounded(&o["dims"][0])));
+
+    let (status, body) = post_json(router(), "/pack", request(Some(2))).await;
+    assert_eq!(status, StatusCode::OK);
+    let placed = body["results"][0]["placed"].as_array().unwrap();
+    assert_eq!(placed.len(), 4);
+    for object in placed {
+        for axis in 0..3 {
+            assert!(is_rounded(&object["pos"][axis]), "pos: {}", object["pos"]);
+            assert!(
+                is_rounded(&object["dims"][axis]),
+                "dims: {}",
+                object["dims"]
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn pack_endpoint_rejects_duplicate_object_ids() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0},
+            {"id": 7, "dims": [5.0, 5.0, 5.0], "weight": 1.0},
+            {"id": 7, "dims": [4.0, 4.0, 4.0], "weight": 2.0}
+        ],
+        "reject_duplicate_object_ids": true
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(
+        body["details"]
+            .as_str()
+            .unwrap()
+            .contains("Duplicate object id 7"),
+        "details should name the duplicated id: {}",
+        body["details"]
+    );
+}
+
+#[tokio::test]
+async fn duplicate_object_ids_are_packed_with_a_warning_by_default() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 7, "dims": [5.0, 5.0, 5.0], "weight": 1.0},
+            {"id": 7, "dims": [4.0, 4.0, 4.0], "weight": 2.0}
+        ]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["placed"].as_array().unwrap().len(), 2);
+    let warnings = body["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].as_str().unwrap().contains("id 7"));
+
+    let events = post_sse_events(router(), payload).await;
+    assert_eq!(events[0]["type"], "Warning");
+    assert!(events[0]["message"].as_str().unwrap().contains("id 7"));
+    assert_eq!(events.last().unwrap()["type"], "Finished");
+}
+
+#[tokio::test]
+async fn pack_endpoint_dedupes_object_ids_with_warning() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0},
+            {"id": 7, "dims": [5.0, 5.0, 5.0], "weight": 1.0},
+            {"id": 7, "dims": [4.0, 4.0, 4.0], "weight": 2.0}
+        ],
+        "dedupe_object_ids": true
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    let placed: Vec<&Value> = body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|container| container["placed"].as_array().unwrap())
+        .collect();
+    assert_eq!(placed.len(), 2);
+    let kept = placed.iter().find(|object| object["id"] == 7).unwrap();
+    assert_eq!(kept["weight"], 1.0);
+    let warnings = body["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].as_str().unwrap().contains("id 7"));
+}
+
+#[tokio::test]
+async fn pack_endpoint_reports_phase_timings_when_profiling() {
+    let payload = json!({
+        "containers": [{"dims": [20.0, 20.0, 20.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 2.0},
+            {"id": 2, "dims": [10.0, 5.0, 5.0], "weight": 1.0}
+        ],
+        "profile": true
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    let timings = &body["timings"];
+    let field = |name: &str| {
+        timings[name]
+            .as_f64()
+            .unwrap_or_else(|| panic!("timings.{name} missing: {timings}"))
+    };
+    let phases = field("sort_ms") + field("cluster_ms") + field("placement_ms");
+    assert!(phases <= field("total_ms") + 1e-9, "{timings}");
+
+    let payload = json!({
+        "containers": [{"dims": [20.0, 20.0, 20.0], "max_weight": 100.0}],
+        "objects": [{"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 2.0}]
+    })
+    .to_string();
+    let (_, body) = post_json(router(), "/pack", payload).await;
+    assert!(body.get("timings").is_none());
+}
+
+#[tokio::test]
+async fn pack_endpoint_names_dimensions_in_object_format() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [5.0, 4.0, 3.0], "weight": 1.0},
+            {"id": 2, "dims": [20.0, 20.0, 20.0], "weight": 1.0}
+        ]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack?dim_format=object", payload.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    let container = &body["results"][0];
+    assert_eq!(container["dims"]["width"], 10.0);
+    assert_eq!(container["outer_dims"]["height"], 10.0);
+    let placed = &container["placed"][0];
+    assert_eq!(placed["dims"]["width"], 5.0);
+    assert_eq!(placed["dims"]["depth"], 4.0);
+    assert_eq!(placed["dims"]["height"], 3.0);
+    assert!(placed["pos"]["x"].is_number());
+    assert!(placed["pos"]["z"].is_number());
+    assert_eq!(body["unplaced"][0]["dims"]["depth"], 20.0);
+
+    let (_, body) = post_json(router(), "/pack", payload).await;
+    assert!(body["results"][0]["placed"][0]["dims"].is_array());
+}
+
+#[tokio::test]
+async fn pack_endpoint_paginates_placements_across_containers() {
+    let objects: Vec<Value> = (1..=10)
+        .map(|id| json!({"id": id, "dims": [5.0, 5.0, 5.0], "weight": 1.0}))
+        .collect();
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": objects
+    })
+    .to_string();
+    let placed_ids = |body: &Value| -> Vec<u64> {
+        body["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|container| container["placed"].as_array().unwrap())
+            .map(|object| object["id"].as_u64().unwrap())
+            .collect()
+    };
+
+    let (_, full) = post_json(router(), "/pack", payload.clone()).await;
+    assert!(full.get("pagination").is_none());
+    let all_ids = placed_ids(&full);
+    assert_eq!(all_ids.len(), 10);
+
+    for (page, expected) in [
+        (1, &all_ids[0..4]),
+        (2, &all_ids[4..8]),
+        (3, &all_ids[8..10]),
+    ] {
+        let uri = format!("/pack?page={page}&page_size=4");
+        let (status, body) = post_json(router(), &uri, payload.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(placed_ids(&body), expected);
+        assert_eq!(
+            body["results"].as_array().unwrap().len(),
+            full["results"].as_array().unwrap().len()
+        );
+        assert_eq!(body["diagnostics_summary"], full["diagnostics_summary"]);
+        assert_eq!(
+            body["pagination"],
+            json!({"page": page, "page_size": 4, "total_placements": 10, "total_pages": 3})
+        );
+    }
+
+    let (status, _) = post_json(router(), "/pack?page=0&page_size=4", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn pack_endpoint_reports_rotation_codes_on_request() {
+    let payload = |include_rotation: bool| {
+        json!({
+            "containers": [{ "dims": [10.0, 10.0, 40.0], "max_weight": 100.0 }],
+            "objects": [
+                { "id": 1, "dims": [30.0, 10.0, 10.0], "weight": 5.0 },
+                { "id": 2, "dims": [10.0, 10.0, 10.0], "weight": 1.0 }
+            ],
+            "allow_rotations": true,
+            "include_rotation": include_rotation
+        })
+        .to_string()
+    };
+
+    let (status, body) = post_json(router(), "/pack", payload(true)).await;
+    assert_eq!(status, StatusCode::OK);
+    let placed = body["results"][0]["placed"].as_array().expect("placed");
+    let rotation_of = |id: u64| {
+        placed
+            .iter()
+            .find(|p| p["id"] == id)
+            .map(|p| p["rotation"].clone())
+            .expect("placed object")
+    };
+    assert_eq!(rotation_of(1), "yzx");
+    // Cubes match every permutation and report the identity.
+    assert_eq!(rotation_of(2), "xyz");
+
+    let (_, body) = post_json(router(), "/pack", payload(false)).await;
+    assert!(body["results"][0]["placed"][0].get("rotation").is_none());
+}
+
+#[tokio::test]
+async fn pack_endpoint_includes_corners_on_request() {
+    let payload = |include_corners: bool| {
+        json!({
+            "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+            "objects": [{"id": 1, "dims": [5.0, 4.0, 3.0], "weight": 1.0}],
+            "include_corners": include_corners
+        })
+        .to_string()
+    };
+
+    let (status, body) = post_json(router(), "/pack", payload(true)).await;
+    assert_eq!(status, StatusCode::OK);
+    let placed = &body["results"][0]["placed"][0];
+    assert_eq!(placed["pos"], json!([0.0, 0.0, 0.0]));
+    assert_eq!(
+        placed["corners"],
+        json!([
+            [0.0, 0.0, 0.0],
+            [5.0, 0.0, 0.0],
+            [0.0, 4.0, 0.0],
+            [5.0, 4.0, 0.0],
+            [0.0, 0.0, 3.0],
+            [5.0, 0.0, 3.0],
+            [0.0, 4.0, 3.0],
+            [5.0, 4.0, 3.0]
+        ])
+    );
+
+    let (_, body) = post_json(router(), "/pack", payload(false)).await;
+    assert!(body["results"][0]["placed"][0].get("corners").is_none());
+}
+
+#[tokio::test]
+async fn pack_endpoint_places_bundles_as_one_unit() {
+    // The heavier item sits on top, which the optimizer would never choose for loose items.
+    let payload = json!({
+        "containers": [{"dims": [20.0, 20.0, 20.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 5.0], "weight": 1.0},
+            {"id": 2, "dims": [10.0, 10.0, 5.0], "weight": 8.0, "color": "#ff8800"},
+            {"id": 3, "dims": [5.0, 5.0, 5.0], "weight": 1.0}
+        ],
+        "bundles": [{"member_ids": [1, 2], "offsets": [[0.0, 0.0, 0.0], [0.0, 0.0, 5.0]]}]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], true);
+    let placed = body["results"][0]["placed"].as_array().unwrap();
+    assert_eq!(placed.len(), 3);
+    let find = |id: u64| placed.iter().find(|p| p["id"] == id).unwrap();
+    let (lower, upper) = (find(1), find(2));
+    let coord = |object: &Value, axis: usize| object["pos"][axis].as_f64().unwrap();
+    assert_eq!(coord(upper, 0), coord(lower, 0));
+    assert_eq!(coord(upper, 1), coord(lower, 1));
+    assert_eq!(coord(upper, 2), coord(lower, 2) + 5.0);
+    assert_eq!(upper["dims"], json!([10.0, 10.0, 5.0]));
+    assert_eq!(upper["weight"], 8.0);
+    assert_eq!(upper["color"], "#ff8800");
+}
+
+#[tokio::test]
+async fn bundles_are_streamed_as_their_members() {
+    let payload = json!({
+        "containers": [{"dims": [20.0, 20.0, 20.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 5.0], "weight": 1.0},
+            {"id": 2, "dims": [10.0, 10.0, 5.0], "weight": 8.0}
+        ],
+        "bundles": [{"member_ids": [1, 2], "offsets": [[0.0, 0.0, 0.0], [0.0, 0.0, 5.0]]}]
+    })
+    .to_string();
+
+    let events = post_sse_events(router(), payload).await;
+    let placed: Vec<&Value> = events
+        .iter()
+        .filter(|event| event["type"] == "ObjectPlaced")
+        .collect();
+    assert_eq!(placed.len(), 2, "{events:?}");
+    assert_eq!(
+        (placed[0]["id"].clone(), placed[1]["id"].clone()),
+        (json!(1), json!(2))
+    );
+    let z = |event: &Value| event["pos"][2].as_f64().unwrap();
+    assert_eq!(z(placed[1]), z(placed[0]) + 5.0);
+    assert_eq!(placed[1]["dims"], json!([10.0, 10.0, 5.0]));
+    assert_eq!(placed[0]["total_weight"], 1.0);
+    assert_eq!(placed[1]["total_weight"], 9.0);
+}
+
+#[tokio::test]
+async fn bundles_take_over_member_gaps_and_template_restrictions() {
+    let payload = json!({
+        "containers": [
+            {"dims": [40.0, 40.0, 20.0], "max_weight": 100.0},
+            {"dims": [50.0, 50.0, 20.0], "max_weight": 100.0}
+        ],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 5.0], "weight": 2.0, "allowed_template_ids": [0, 1]},
+            {"id": 2, "dims": [10.0, 10.0, 5.0], "weight": 1.0, "allowed_template_ids": [1]},
+            {"id": 3, "dims": [10.0, 10.0, 10.0], "weight": 1.0, "allowed_template_ids": [1]}
+        ],
+        "bundles": [{"member_ids": [1, 2], "offsets": [[0.0, 0.0, 0.0], [10.0, 0.0, 0.0]]}],
+        "min_gap_between": [{"a": 3, "b": 2, "gap": 5.0}]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["results"].as_array().unwrap().len(), 1);
+    assert_eq!(body["results"][0]["template_id"], 1);
+    let placed = body["results"][0]["placed"].as_array().unwrap();
+    let find = |id: u64| placed.iter().find(|p| p["id"] == id).unwrap();
+    let span = |object: &Value, axis: usize| {
+        let start = object["pos"][axis].as_f64().unwrap();
+        (start, start + object["dims"][axis].as_f64().unwrap())
+    };
+    // Object 3 keeps its gap to the bundle, which spans both members.
+    let (bundle, loose) = (find(1), find(3));
+    let gap = (0..2)
+        .map(|axis| {
+            let ((b0, _), (l0, l1)) = (span(bundle, axis), span(loose, axis));
+            let b1 = b0 + if axis == 0 { 20.0 } else { 10.0 };
+            (l0 - b1).max(b0 - l1)
+        })
+        .fold(f64::NEG_INFINITY, f64::max);
+    assert!(gap >= 5.0 - 1e-9, "gap {gap} in {placed:?}");
+
+    let payload = json!({
+        "containers": [{"dims": [40.0, 40.0, 20.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 5.0], "weight": 1.0},
+            {"id": 2, "dims": [10.0, 10.0, 5.0], "weight": 1.0}
+        ],
+        "bundles": [{"member_ids": [1, 2], "offsets": [[0.0, 0.0, 0.0], [10.0, 0.0, 0.0]]}],
+        "min_gap_between": [{"a": 1, "b": 2, "gap": 5.0}]
+    })
+    .to_string();
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(
+        body["details"].as_str().unwrap().contains("same bundle"),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn pack_endpoint_rejects_overlapping_bundle_members() {
+    let payload = json!({
+        "containers": [{"dims": [20.0, 20.0, 20.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 5.0], "weight": 1.0},
+            {"id": 2, "dims": [10.0, 10.0, 5.0], "weight": 1.0}
+        ],
+        "bundles": [{"member_ids": [1, 2], "offsets": [[0.0, 0.0, 0.0], [5.0, 0.0, 0.0]]}]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["details"].as_str().unwrap().contains("overlap"));
+}
+
+#[tokio::test]
+async fn pack_endpoint_resolves_library_templates_by_name() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    std::fs::write(
+        dir.path().join("small.json"),
+        r#"{"name": "small-box", "dims": [10.0, 10.0, 10.0], "max_weight": 50.0}"#,
+    )
+    .expect("template written");
+    std::fs::write(
+        dir.path().join("euro-pallet.json"),
+        r#"{"dims": [120.0, 80.0, 100.0], "max_weight": 500.0}"#,
+    )
+    .expect("template written");
+    let library = TemplateLibrary::load_dir(dir.path());
+    let app = || {
+        build_router_with_templates(
+            OptimizerConfig::default(),
+            RequestLimits::default(),
+            0,
+            library.clone(),
+        )
+    };
+
+    let (status, listing) = get_json(app(), "/templates").await;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = listing["templates"]
+        .as_array()
+        .expect("templates array")
+        .iter()
+        .map(|t| t["name"].as_str().expect("template name"))
+        .collect();
+    assert_eq!(names, vec!["euro-pallet", "small-box"]);
+
+    let payload = json!({
+        "containers": [],
+        "template_names": ["euro-pallet"],
+        "objects": [{ "id": 1, "dims": [60.0, 40.0, 30.0], "weight": 10.0 }]
+    });
+    let (status, body) = post_json(app(), "/pack", payload.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["label"], "euro-pallet");
+    assert_eq!(body["results"][0]["dims"], json!([120.0, 80.0, 100.0]));
+
+    let payload = json!({
+        "containers": [],
+        "template_names": ["missing"],
+        "objects": [{ "id": 1, "dims": [1.0, 1.0, 1.0], "weight": 1.0 }]
+    });
+    let (status, body) = post_json(app(), "/pack", payload.to_string()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["details"].as_str().unwrap().contains("missing"));
+}
+
+#[tokio::test]
+async fn pack_endpoint_exhausts_containers_in_fill_order() {
+    // Four slabs fill A exactly; the large object only fits B.
+    let mut objects: Vec<Value> = (1..=4)
+        .map(|id| json!({ "id": id, "dims": [20.0, 20.0, 5.0], "weight": 1.0 }))
+        .collect();
+    objects.push(json!({ "id": 9, "dims": [30.0, 30.0, 30.0], "weight": 2.0 }));
+    let payload = |fill_order: Value| {
+        json!({
+            "containers": [
+                { "name": "A", "dims": [20.0, 20.0, 20.0], "max_weight": 100.0 },
+                { "name": "B", "dims": [40.0, 40.0, 40.0], "max_weight": 100.0 }
+            ],
+            "fill_order": fill_order,
+            "objects": objects
+        })
+        .to_string()
+    };
+
+    // Without a fill order the large object opens B first and everything else follows it.
+    let (status, body) = post_json(router(), "/pack", payload(json!([]))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["label"], "B");
+
+    let (status, body) = post_json(router(), "/pack", payload(json!([0, 1]))).await;
+    assert_eq!(status, StatusCode::OK);
+    let results = body["results"].as_array().expect("results array");
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["label"], "A");
+    assert_eq!(results[0]["placed"].as_array().unwrap().len(), 4);
+    assert_eq!(
+        results[0]["diagnostics"]["volume_utilization_percent"],
+        100.0
+    );
+    assert_eq!(results[1]["label"], "B");
+    let b_ids: Vec<u64> = results[1]["placed"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(b_ids, vec![9]);
+
+    let (status, _) = post_json(router(), "/pack", payload(json!([0, 0]))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn pack_endpoint_serves_identical_requests_from_the_bounded_cache() {
+    let app = build_router_with_cache(
+        OptimizerConfig::default(),
+        RequestLimits::default(),
+        0,
+        TemplateLibrary::default(),
+        1,
+    );
+    let payload = |weight: f64| {
+        json!({
+            "containers": [{ "dims": [10.0, 10.0, 10.0], "max_weight": 100.0 }],
+            "objects": [{ "id": 1, "dims": [5.0, 5.0, 5.0], "weight": weight }]
+        })
+        .to_string()
+    };
+    let send = |uri: &'static str, body: String| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(uri)
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .body(Body::from(body))
+                        .expect("request builds"),
+                )
+                .await
+                .expect("router responds");
+            assert_eq!(response.status(), StatusCode::OK);
+            let cache = response.headers().get("x-cache").cloned();
+            let body = to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("body collected");
+            let value: Value = serde_json::from_slice(&body).expect("JSON body");
+            (cache.map(|v| v.to_str().unwrap().to_string()), value)
+        }
+    };
+
+    let (first, computed) = send("/pack", payload(1.0)).await;
+    assert_eq!(first.as_deref(), Some("miss"));
+    let (second, cached) = send("/pack", payload(1.0)).await;
+    assert_eq!(second.as_deref(), Some("hit"));
+    assert_eq!(cached, computed);
+
+    // `cache=false` neither reads nor fills the cache.
+    let (bypassed, _) = send("/pack?cache=false", payload(1.0)).await;
+    assert_eq!(bypassed, None);
+
+    // A different request displaces the only slot, so the first one is computed again.
+    let (other, _) = send("/pack", payload(2.0)).await;
+    assert_eq!(other.as_deref(), Some("miss"));
+    let (evicted, _) = send("/pack", payload(1.0)).await;
+    assert_eq!(evicted.as_deref(), Some("miss"));
+}
+
+#[tokio::test]
+async fn pack_endpoint_returns_collected_events_on_request() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 5.0], "weight": 10.0},
+            {"id": 2, "dims": [10.0, 10.0, 5.0], "weight": 10.0},
+            {"id": 3, "dims": [20.0, 20.0, 20.0], "weight": 10.0}
+        ]
+    })
+    .to_string();
+
+    let (status, plain) = post_json(router(), "/pack", payload.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(plain.get("events").is_none());
+
+    let (status, body) = post_json(router(), "/pack?events=true", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    let events = body["events"].as_array().expect("events array");
+    let placed = events
+        .iter()
+        .filter(|event| event["type"] == "ObjectPlaced")
+        .count();
+    assert_eq!(placed, 2);
+
+    let finished = events.last().expect("at least one event");
+    assert_eq!(finished["type"], "Finished");
+    assert_eq!(
+        finished["containers"].as_u64().unwrap() as usize,
+        body["results"].as_array().unwrap().len()
+    );
+    assert_eq!(
+        finished["unplaced"].as_u64().unwrap() as usize,
+        body["unplaced"].as_array().unwrap().len()
+    );
+    assert_eq!(finished["unplaced"], 1);
+}
+
+#[tokio::test]
+async fn collected_events_follow_the_final_container_order_and_origin() {
+    // The heavy slab fills half of container 1, the cube then opens container 2 and fills it.
+    let payload = json!({
+        "containers": [{
+            "dims": [10.0, 10.0, 10.0],
+            "max_weight": 100.0,
+            "origin": [100.0, 0.0, 0.0]
+        }],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 5.0], "weight": 90.0},
+            {"id": 2, "dims": [10.0, 10.0, 10.0], "weight": 20.0}
+        ],
+        "container_order": "utilization"
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack?events=true", payload.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    let results = body["results"].as_array().expect("results array");
+    assert_eq!(results[0]["placed"][0]["id"], 2);
+    let events = body["events"].as_array().expect("events array");
+    assert!(
+        events
+            .iter()
+            .all(|event| event["type"] != "ContainersReordered")
+    );
+    let placed_in = |id: u64| {
+        events
+            .iter()
+            .find(|event| event["type"] == "ObjectPlaced" && event["id"] == id)
+            .expect("placement event")
+    };
+    assert_eq!(placed_in(2)["container_id"], results[0]["id"]);
+    assert_eq!(placed_in(1)["container_id"], results[1]["id"]);
+    assert_eq!(placed_in(2)["pos"], results[0]["placed"][0]["pos"]);
+    assert_eq!(placed_in(2)["pos"], json!([100.0, 0.0, 0.0]));
+    let finished = events.last().expect("at least one event");
+    assert_eq!(finished["container_fill"][0][0], results[0]["id"]);
+    assert_eq!(finished["container_fill"][0][1], 100.0);
+
+    // Streams keep the creation ids and announce the renumbering right before `Finished`.
+    let streamed = post_sse_events(router(), payload).await;
+    let reordered = &streamed[streamed.len() - 2];
+    assert_eq!(reordered["type"], "ContainersReordered");
+    assert_eq!(reordered["creation_ids"], json!([2, 1]));
+    assert_eq!(streamed.last().unwrap()["type"], "Finished");
+}
+
+#[tokio::test]
+async fn pack_endpoint_maps_containers_to_the_submitted_template_order() {
+    // Submitted large, small, medium; the optimizer tries them smallest first.
+    let templates = [[40.0, 40.0, 40.0], [10.0, 10.0, 10.0], [20.0, 20.0, 20.0]];
+    let payload = json!({
+        "containers": templates
+            .iter()
+            .map(|dims| json!({"dims": dims, "max_weight": 100.0}))
+            .collect::<Vec<_>>(),
+        "objects": [
+            {"id": 1, "dims": [35.0, 35.0, 35.0], "weight": 3.0},
+            {"id": 2, "dims": [15.0, 15.0, 15.0], "weight": 2.0},
+            {"id": 3, "dims": [8.0, 8.0, 8.0], "weight": 1.0}
+        ]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    let mut used = Vec::new();
+    for container in results {
+        let index = container["template_submission_index"].as_u64().unwrap() as usize;
+        assert_eq!(
+            container["template_submission_index"],
+            container["template_id"]
+        );
+        assert_eq!(container["dims"], json!(templates[index]));
+        used.push(index);
+    }
+    used.sort_unstable();
+    assert_eq!(used, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn pack_endpoint_drops_blank_container_names() {
+    let payload = json!({
+        "containers": [{"name": "   ", "dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [{"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0}]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["label"], Value::Null);
+}
+
+#[tokio::test]
+async fn pack_endpoint_reports_the_occupied_envelope_of_each_container() {
+    let payload = json!({
+        "containers": [{"dims": [100.0, 100.0, 100.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [30.0, 20.0, 10.0], "weight": 5.0},
+            {"id": 2, "dims": [10.0, 40.0, 25.0], "weight": 4.0}
+        ]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    let container = &body["results"][0];
+    let placed = container["placed"].as_array().unwrap();
+    assert_eq!(placed.len(), 2);
+    let coord = |object: &Value, key: &str, axis: usize| object[key][axis].as_f64().unwrap();
+    for axis in 0..3 {
+        let min = placed
+            .iter()
+            .map(|object| coord(object, "pos", axis))
+            .fold(f64::INFINITY, f64::min);
+        let max = placed
+            .iter()
+            .map(|object| coord(object, "pos", axis) + coord(object, "dims", axis))
+            .fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(
+            container["occupied_envelope"][0][axis].as_f64().unwrap(),
+            min
+        );
+        assert_eq!(
+            container["occupied_envelope"][1][axis].as_f64().unwrap(),
+            max
+        );
+    }
+    // Neither object is taller than 25, so the envelope is far below the container height.
+    assert!(container["occupied_envelope"][1][2].as_f64().unwrap() < 100.0);
+}
+
+#[tokio::test]
+async fn pack_endpoint_reports_whether_rotation_was_used() {
+    let pack = |dims: [f64; 3]| {
+        json!({
+            "containers": [{"dims": [10.0, 20.0, 10.0], "max_weight": 100.0}],
+            "objects": [{"id": 1, "dims": dims, "weight": 1.0}],
+            "allow_rotations": true
+        })
+        .to_string()
+    };
+
+    // Only fits after turning it by 90° around the vertical axis.
+    let (status, body) = post_json(router(), "/pack", pack([20.0, 10.0, 10.0])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], true);
+    assert_eq!(body["rotation_used"], true);
+
+    let (status, body) = post_json(router(), "/pack", pack([10.0, 20.0, 10.0])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], true);
+    assert_eq!(body["rotation_used"], false);
+}
+
+#[tokio::test]
+async fn pack_endpoint_suggests_a_container_for_oversized_objects() {
+    let pack = |dims: [f64; 3]| {
+        json!({
+            "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+            "objects": [
+                {"id": 1, "dims": dims, "weight": 7.5},
+                {"id": 2, "dims": [50.0, 1.0, 1.0], "weight": 3.0}
+            ]
+        })
+        .to_string()
+    };
+
+    let (status, body) = post_json(router(), "/pack", pack([20.0, 30.0, 40.0])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], false);
+    // The dimensions follow the largest object, not the thin rod; the weight covers both.
+    let suggestion = &body["suggested_container"];
+    for (axis, size) in [20.0, 30.0, 40.0].into_iter().enumerate() {
+        let suggested = suggestion["dims"][axis].as_f64().unwrap();
+        assert!(suggested >= size && suggested < size * 1.2);
+    }
+    assert_eq!(suggestion["max_weight"], 10.5);
+
+    let (status, body) = post_json(router(), "/pack", pack([5.0, 5.0, 5.0])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["suggested_container"]["max_weight"], 3.0);
+}
+
+#[tokio::test]
+async fn pack_endpoint_omits_the_suggestion_once_everything_fits() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [{"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 7.5}]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("suggested_container").is_none());
+}
+
+#[tokio::test]
+async fn pack_endpoint_sums_the_cost_of_opened_containers() {
+    let payload = json!({
+        "containers": [
+            {"dims": [10.0, 10.0, 10.0], "max_weight": 100.0, "cost": 12.5},
+            {"dims": [20.0, 10.0, 10.0], "max_weight": 100.0, "cost": 30.0},
+            {"dims": [30.0, 10.0, 10.0], "max_weight": 100.0}
+        ],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 10.0], "weight": 1.0},
+            {"id": 2, "dims": [20.0, 10.0, 10.0], "weight": 1.0},
+            {"id": 3, "dims": [30.0, 10.0, 10.0], "weight": 1.0}
+        ]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], true);
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    let mut costs: Vec<Option<f64>> = results.iter().map(|c| c["cost"].as_f64()).collect();
+    costs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(costs, vec![None, Some(12.5), Some(30.0)]);
+    assert_eq!(body["total_cost"], 42.5);
+
+    let (status, _) = post_json(
+        router(),
+        "/pack",
+        json!({
+            "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0, "cost": -1.0}],
+            "objects": [{"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0}]
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn pack_endpoint_rejects_objects_referencing_unknown_template_ids() {
+    let pack = |allowed: Value| {
+        json!({
+            "containers": [
+                {"dims": [10.0, 10.0, 10.0], "max_weight": 100.0},
+                {"dims": [20.0, 20.0, 20.0], "max_weight": 100.0}
+            ],
+            "objects": [
+                {"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0},
+                {"id": 7, "dims": [5.0, 5.0, 5.0], "weight": 1.0, "allowed_template_ids": allowed}
+            ]
+        })
+        .to_string()
+    };
+
+    let (status, body) = post_json(router(), "/pack", pack(json!([1, 3]))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let details = body["details"].as_str().unwrap();
+    assert!(details.contains("Object 7"), "{details}");
+    assert!(details.contains("container type 3"), "{details}");
+
+    let (status, body) = post_json(router(), "/pack", pack(json!([1]))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_complete"], true);
+}
+
+#[tokio::test]
+async fn pack_endpoint_reports_positions_relative_to_origin() {
+    let container = |origin: Value| {
+        json!({
+            "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0, "origin": origin}],
+            "objects": [{"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0}]
+        })
+        .to_string()
+    };
+
+    let (_, plain) = post_json(router(), "/pack", container(Value::Null)).await;
+    let (status, shifted) =
+        post_json(router(), "/pack", container(json!([100.0, -10.0, 2.5]))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(shifted["results"][0]["origin"], json!([100.0, -10.0, 2.5]));
+    let pos = |body: &Value, axis: usize| {
+        body["results"][0]["placed"][0]["pos"][axis]
+            .as_f64()
+            .unwrap()
+    };
+    assert_eq!(pos(&shifted, 0), pos(&plain, 0) + 100.0);
+    assert_eq!(pos(&shifted, 1), pos(&plain, 1) - 10.0);
+    assert_eq!(pos(&shifted, 2), pos(&plain, 2) + 2.5);
+
+    // The exported result still validates, since restoring undoes the offset.
+    let (status, _) = post_json(router(), "/rediagnose", shifted.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let mut clamped: Value = serde_json::from_str(&container(json!([0.0, -10.0, 0.0]))).unwrap();
+    clamped["clamp_origin"] = json!(true);
+    let (status, body) = post_json(router(), "/pack", clamped.to_string()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["details"].as_str().unwrap().contains("negative"));
+}
+
+#[tokio::test]
+async fn pack_endpoint_strict_mode_rejects_partial_pack() {
+    let payload = json!({
+        "containers": [{"dims": [10.0, 10.0, 10.0], "max_weight": 100.0}],
+        "objects": [
+            {"id": 1, "dims": [5.0, 5.0, 5.0], "weight": 1.0},
+            {"id": 2, "dims": [20.0, 20.0, 20.0], "weight": 1.0}
+        ],
+        "strict": true
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "Incomplete pack");
+    assert!(
+        body["details"].as_str().unwrap().contains("ids: 2"),
+        "details should list the unplaced id: {}",
+        body["details"]
+    );
+}
+
+/// Asserts that two JSON values match, comparing numbers with a small tolerance.
+fn assert_json_close(actual: &Value, expected: &Value, path: &str) {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => {
+            let (a, b) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+            assert!((a - b).abs() < 1e-9, "{path}: {a} != {b}");
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            assert_eq!(a.len(), b.len(), "{path}: length mismatch");
+            for (i, (x, y)) in a.iter().zip(b).enumerate() {
+                assert_json_close(x, y, &format!("{path}[{i}]"));
+            }
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            assert_eq!(a.len(), b.len(), "{path}: key mismatch");
+            for (key, x) in a {
+                assert_json_close(x, &b[key], &format!("{path}.{key}"));
+            }
+        }
+        _ => assert_eq!(actual, expected, "{path}"),
+    }
+}
+
+#[tokio::test]
+async fn rediagnose_round_trips_exported_result() {
+    let payload = json!({
+        "containers": [{"dims": [20.0, 20.0, 20.0], "max_weight": 50.0}],
+        "objects": [
+            {"id": 1, "dims": [10.0, 10.0, 10.0], "weight": 8.0},
+            {"id": 2, "dims": [10.0, 10.0, 10.0], "weight": 6.0},
+            {"id": 3, "dims": [10.0, 10.0, 5.0], "weight": 2.0},
+            {"id": 4, "dims": [20.0, 20.0, 20.0], "weight": 40.0}
+        ]
+    })
+    .to_string();
+    let (status, exported) = post_json(router(), "/pack", payload).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = post_json(router(), "/rediagnose", exported.to_string()).await;
+    assert_eq!(status, StatusCode::OK, "unexpected body: {body}");
+
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), exported["results"].as_array().unwrap().len());
+    for (rediagnosed, original) in results.iter().zip(exported["results"].as_array().unwrap()) {
+        assert_eq!(rediagnosed["id"], original["id"]);
+        assert_json_close(
+            &rediagnosed["diagnostics"],
+            &original["diagnostics"],
+            "diagnostics",
+        );
+    }
+    assert_json_close(
+        &body["diagnostics_summary"],
+        &exported["diagnostics_summary"],
+        "diagnostics_summary",
+    );
+}
+
+#[tokio::test]
+async fn rediagnose_rejects_overlapping_placements() {
+    let payload = json!({
+        "results": [{
+            "id": 1,
+            "dims": [20.0, 20.0, 20.0],
+            "max_weight": 50.0,
+            "placed": [
+                {"id": 1, "pos": [0.0, 0.0, 0.0], "weight": 1.0, "dims": [10.0, 10.0, 10.0]},
+                {"id": 2, "pos": [5.0, 0.0, 0.0], "weight": 1.0, "dims": [10.0, 10.0, 10.0]}
+            ]
+        }]
+    })
+    .to_string();
+
+    let (status, body) = post_json(router(), "/rediagnose", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "Invalid placement");
+    assert!(
+        body["details"]
+            .as_str()
+            .unwrap()
+            .contains("objects 1 and 2 overlap")
+    );
+}
+
 #[tokio::test]
 async fn unknown_asset_returns_not_found() {
     let response = router()
@@ -1732,6 +1732,43 @@ function handleLiveEvent(evt) {
       });
       break;
     }
+    case 'Snapshot': {
+      // Replaces the streamed placements, e.g. after settling or compaction moved objects.
+      const idx = evt.container_id - 1;
+      if (idx >= 0 && idx < liveContainers.length && Array.isArray(evt.placed)) {
+        const cont = liveContainers[idx];
+        cont.placed = evt.placed.map((obj) => ({
+          id: obj.id,
+          pos: obj.pos,
+          weight: obj.weight,
+          dims: obj.dims,
+        }));
+        if (idx === currentContainerIndex) {
+          visualizeContainer(cont, resolveContainerDims(cont));
+        }
+      }
+      break;
+    }
+    case 'ContainersReordered': {
+      // Lists the containers in the final result order, renumbered like the result.
+      if (Array.isArray(evt.creation_ids) && evt.creation_ids.length === liveContainers.length) {
+        const current = liveContainers[currentContainerIndex];
+        liveContainers = evt.creation_ids.map((creationId, idx) => ({
+          ...liveContainers[creationId - 1],
+          id: idx + 1,
+        }));
+        currentContainerIndex = Math.max(
+          0,
+          evt.creation_ids.findIndex((creationId) => creationId === current?.id)
+        );
+        visualizeContainer(
+          liveContainers[currentContainerIndex],
+          resolveContainerDims(liveContainers[currentContainerIndex])
+        );
+        updateNavigationButtons();
+      }
+      break;
+    }
     case 'ContainerDiagnostics': {
       const idx = evt.container_id - 1;
       const diagnostics = evt.diagnostics ?? null;